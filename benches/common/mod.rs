@@ -0,0 +1,79 @@
+// Each benchmark only uses some of the helpers shared by the benchmarks.
+#![allow(dead_code)]
+
+use more_rust_structures::sorting::{Rng, SeedableRng, XorShift64};
+
+/// The shapes of input lists the sorts are benchmarked on.
+#[derive(Debug, Clone, Copy)]
+pub enum Distribution {
+    /// Uniformly distributed values, with almost no duplicates.
+    Random,
+    /// Values already in ascending order.
+    Sorted,
+    /// Values in descending order.
+    Reversed,
+    /// Uniformly distributed values among 16 distinct ones.
+    FewUnique,
+    /// Values in ascending order, but for 1% of them swapped with random others.
+    NearlySorted,
+}
+
+impl Distribution {
+    pub const ALL: [Distribution; 5] = [
+        Distribution::Random,
+        Distribution::Sorted,
+        Distribution::Reversed,
+        Distribution::FewUnique,
+        Distribution::NearlySorted,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Distribution::Random => "random",
+            Distribution::Sorted => "sorted",
+            Distribution::Reversed => "reversed",
+            Distribution::FewUnique => "few_unique",
+            Distribution::NearlySorted => "nearly_sorted",
+        }
+    }
+
+    /// A deterministic list of `len` values with this distribution.
+    pub fn list(&self, len: usize) -> Vec<u64> {
+        let mut rng = XorShift64::seed_from_u64(len as u64);
+        match self {
+            Distribution::Random => (0..len).map(|_| rng.next_u64()).collect(),
+            Distribution::Sorted => (0..len as u64).collect(),
+            Distribution::Reversed => (0..len as u64).rev().collect(),
+            Distribution::FewUnique => (0..len).map(|_| rng.next_u64() % 16).collect(),
+            Distribution::NearlySorted => {
+                let mut list: Vec<u64> = (0..len as u64).collect();
+                for _ in 0..len / 100 {
+                    list.swap(rng.below(len), rng.below(len));
+                }
+                list
+            }
+        }
+    }
+}
+
+/// A deterministic list of `len` strings, made of a few shared prefixes followed by random digits, the kind of keys
+/// string sorts are designed for.
+pub fn strings(len: usize) -> Vec<String> {
+    let prefixes = [
+        "https://example.com/",
+        "https://example.org/docs/",
+        "ftp://",
+    ];
+    let mut rng = XorShift64::seed_from_u64(len as u64);
+    (0..len)
+        .map(|_| format!("{}{}", prefixes[rng.below(prefixes.len())], rng.next_u64()))
+        .collect()
+}
+
+/// `count` deterministic pairs of random values in `0..n`, to connect in a `UnionFind` or query it with.
+pub fn pairs(n: u32, count: usize) -> Vec<(u32, u32)> {
+    let mut rng = XorShift64::seed_from_u64(count as u64);
+    (0..count)
+        .map(|_| (rng.below(n as usize) as u32, rng.below(n as usize) as u32))
+        .collect()
+}
@@ -0,0 +1,215 @@
+//! Doubling-ratio experiments, which time the sorts, stacks and union-finds of the crate on inputs of doubling length,
+//! and estimate the exponent of their running time, to check it against the complexity claimed by their docs.
+//!
+//! Run them all with `cargo bench --bench doubling`, or only those whose name contains a given string with
+//! `cargo bench --bench doubling -- QuickSort`.
+
+mod common;
+
+use std::{env, time::Duration};
+
+use common::{pairs, Distribution};
+use more_rust_structures::{
+    doubling::{DoublingExperiment, DoublingReport},
+    sorting::{
+        lsd_radix_sort, BinaryInsertionSort, HeapSort, InsertionSort, IntroSort, MergeSort,
+        PdqSort, RandomizedQuickSort, SelectionSort, ShellSort, SmartSort, Sorter, TimSort,
+    },
+    stack::{array_stack::ArrayStack, linked_list_stack::LinkedListStack, Stack},
+    union_find::{
+        quick_find::QuickFind, quick_union::QuickUnion, weighted_quick_union::WeightedQuickUnion,
+        weighted_quick_union_with_path_compression::WeightedQuickUnionWithPathCompression,
+        UnionFind,
+    },
+};
+
+/// An experiment, with the complexity claimed for the running time it measures by the docs, and the exponent of the
+/// growth of that complexity, which the estimated exponent is checked against.
+struct Experiment {
+    name: &'static str,
+    claim: &'static str,
+    exponent: f64,
+    run: Box<dyn Fn() -> DoublingReport>,
+}
+
+impl Experiment {
+    fn new<F>(name: &'static str, claim: &'static str, exponent: f64, run: F) -> Self
+    where
+        F: Fn() -> DoublingReport + 'static,
+    {
+        Experiment {
+            name,
+            claim,
+            exponent,
+            run: Box::new(run),
+        }
+    }
+}
+
+/// How far the estimated exponent may be from the claimed one before the experiment is flagged. It leaves room for the
+/// log factor of `O(n log n)` costs, and for the noise of the timings.
+const TOLERANCE: f64 = 0.3;
+
+/// The experiments start from longer inputs for the faster algorithms, so that their costs are not dominated by
+/// constant overheads, and from shorter ones for the quadratic algorithms, so that they finish in seconds.
+const QUADRATIC: (usize, usize) = (1_000, 6);
+const LINEARITHMIC: (usize, usize) = (10_000, 7);
+
+/// Experiments stop doubling once a run takes longer than this, so that algorithms slower than claimed still finish.
+const MAX_TIME: Duration = Duration::from_secs(2);
+
+fn experiment((start_len, steps): (usize, usize)) -> DoublingExperiment {
+    DoublingExperiment::new(start_len, steps).with_max_time(MAX_TIME)
+}
+
+/// Times sorting random lists with `sorter`.
+fn sort<S: Sorter + 'static>(sorter: S, range: (usize, usize)) -> DoublingReport {
+    experiment(range).time(
+        |len| Distribution::Random.list(len),
+        |mut list| {
+            sorter.sort(&mut list);
+            list
+        },
+    )
+}
+
+/// Times pushing n items onto a new stack, then popping them all.
+fn push_pop<S: Stack<u64>>(new: fn() -> S, range: (usize, usize)) -> DoublingReport {
+    experiment(range).time(
+        |len| len,
+        |len| {
+            let mut stack = new();
+            for item in 0..len as u64 {
+                stack.push(item);
+            }
+            while stack.count() > 0 {
+                stack.pop();
+            }
+        },
+    )
+}
+
+/// Times n unions of random pairs of values, followed by n connectivity queries of random pairs, on a new union-find of
+/// n values.
+fn union_find<U: UnionFind>(new: fn(u32) -> U, range: (usize, usize)) -> DoublingReport {
+    experiment(range).time(
+        |len| (pairs(len as u32, len), pairs(len as u32, len)),
+        |(unions, queries)| {
+            let mut union_find = new(unions.len() as u32);
+            for (p, q) in unions {
+                union_find.union(p, q);
+            }
+            queries
+                .into_iter()
+                .filter(|&(p, q)| union_find.is_connected(p, q))
+                .count()
+        },
+    )
+}
+
+fn experiments() -> Vec<Experiment> {
+    vec![
+        Experiment::new("InsertionSort", "O(n^2)", 2.0, || {
+            sort(InsertionSort, QUADRATIC)
+        }),
+        Experiment::new("BinaryInsertionSort", "O(n^2)", 2.0, || {
+            sort(BinaryInsertionSort, QUADRATIC)
+        }),
+        Experiment::new("SelectionSort", "O(n^2)", 2.0, || {
+            sort(SelectionSort, QUADRATIC)
+        }),
+        Experiment::new("ShellSort", "about O(n^(4/3))", 4.0 / 3.0, || {
+            sort(ShellSort::default(), LINEARITHMIC)
+        }),
+        Experiment::new("MergeSort", "O(n log n)", 1.0, || {
+            sort(MergeSort, LINEARITHMIC)
+        }),
+        Experiment::new("TimSort", "O(n log n)", 1.0, || sort(TimSort, LINEARITHMIC)),
+        Experiment::new("HeapSort", "O(n log n)", 1.0, || {
+            sort(HeapSort, LINEARITHMIC)
+        }),
+        Experiment::new("RandomizedQuickSort", "O(n log n)", 1.0, || {
+            sort(RandomizedQuickSort, LINEARITHMIC)
+        }),
+        Experiment::new("IntroSort", "O(n log n)", 1.0, || {
+            sort(IntroSort, LINEARITHMIC)
+        }),
+        Experiment::new("PdqSort", "O(n log n)", 1.0, || sort(PdqSort, LINEARITHMIC)),
+        Experiment::new("SmartSort", "O(n log n)", 1.0, || {
+            sort(SmartSort::default(), LINEARITHMIC)
+        }),
+        Experiment::new("lsd_radix_sort", "O(n)", 1.0, || {
+            experiment(LINEARITHMIC).time(
+                |len| Distribution::Random.list(len),
+                |mut list| {
+                    lsd_radix_sort(&mut list);
+                    list
+                },
+            )
+        }),
+        Experiment::new("ArrayStack", "O(1) amortized per operation", 1.0, || {
+            push_pop(|| ArrayStack::new(16), LINEARITHMIC)
+        }),
+        Experiment::new("LinkedListStack", "O(1) per operation", 1.0, || {
+            push_pop(LinkedListStack::new, LINEARITHMIC)
+        }),
+        Experiment::new("QuickFind", "O(n) per union", 2.0, || {
+            union_find(QuickFind::new, QUADRATIC)
+        }),
+        Experiment::new("QuickUnion", "O(n) per union and find", 2.0, || {
+            union_find(QuickUnion::new, QUADRATIC)
+        }),
+        Experiment::new("WeightedQuickUnion", "O(log n) per operation", 1.0, || {
+            union_find(WeightedQuickUnion::new, LINEARITHMIC)
+        }),
+        Experiment::new(
+            "WeightedQuickUnionWithPathCompression",
+            "nearly O(1) per operation",
+            1.0,
+            || union_find(WeightedQuickUnionWithPathCompression::new, LINEARITHMIC),
+        ),
+    ]
+}
+
+fn main() {
+    // Cargo passes `--bench` to benchmarks without the criterion harness.
+    let filter = env::args().skip(1).find(|arg| !arg.starts_with('-'));
+    let mut summary = Vec::new();
+    for experiment in experiments() {
+        if filter
+            .as_ref()
+            .is_some_and(|filter| !experiment.name.contains(filter.as_str()))
+        {
+            continue;
+        }
+        let report = (experiment.run)();
+        println!(
+            "{}, claimed {}:\n{}\n",
+            experiment.name, experiment.claim, report
+        );
+        summary.push((experiment, report));
+    }
+    println!(
+        "{:<40} {:<30} {:>8} {:>8}",
+        "experiment", "claimed", "expected", "measured"
+    );
+    let mut mismatches = 0;
+    for (experiment, report) in &summary {
+        let agrees = report.agrees_with(experiment.exponent, TOLERANCE);
+        println!(
+            "{:<40} {:<30} {:>8.2} {:>8.2}{}",
+            experiment.name,
+            experiment.claim,
+            experiment.exponent,
+            report.exponent(),
+            if agrees { "" } else { "  MISMATCH" }
+        );
+        mismatches += !agrees as usize;
+    }
+    println!(
+        "\n{} of {} experiments disagree with their claimed complexity, by more than {}",
+        mismatches,
+        summary.len(),
+        TOLERANCE
+    );
+}
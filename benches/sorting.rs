@@ -0,0 +1,245 @@
+mod common;
+
+use std::{fs, time::Duration};
+
+use common::{strings, Distribution};
+use criterion::{
+    black_box, criterion_group, criterion_main, measurement::WallTime, BatchSize, BenchmarkGroup,
+    BenchmarkId, Criterion,
+};
+use more_rust_structures::sorting::{
+    burst_sort, counting_sort, external_sort, lsd_radix_sort, msd_radix_sort,
+    smart_sort_by_radix_key, sort_network, sort_network_by, sort_network_slice_by,
+    three_way_radix_quick_sort, BinaryInsertionSort, BitonicSort, BottomUpMergeSort,
+    DoubleEndedSelectionSort, DualPivotQuickSort, HeapSort, InsertionSort, IntroSort, LibrarySort,
+    MedianOfThreeQuickSort, MergeSort, OddEvenMergeSort, PdqSort, RandomizedQuickSort,
+    SelectionSort, ShellSort, SmartSort, Sorter, StableSelectionSort, ThreeWayQuickSort, TimSort,
+};
+#[cfg(feature = "parallel")]
+use more_rust_structures::sorting::{ParallelMergeSort, ParallelQuickSort};
+use more_rust_structures::stack::{linked_list_stack::LinkedListStack, Stack};
+
+const LENS: [usize; 3] = [1_000, 10_000, 100_000];
+
+/// The quadratic sorts would dominate the run time on longer lists.
+const QUADRATIC_MAX_LEN: usize = 10_000;
+
+/// `LinkedListStack` clones its whole list on every push, which makes building longer stacks quadratic.
+const LINKED_LIST_MAX_LEN: usize = 10_000;
+
+/// The number of 16-item arrays sorted by each sorting network benchmark, so that a single array doesn't leave the
+/// measurement to its overhead.
+const NETWORK_ARRAYS: usize = 1_000;
+
+/// The memory budget of the external sort, small enough to split every input but the shortest into several runs.
+const EXTERNAL_MEMORY_BUDGET: usize = 256 * 1024;
+
+/// Benchmarks sorting a copy of `list`, with the copy left out of the measurement.
+fn bench_sort<T, F>(group: &mut BenchmarkGroup<'_, WallTime>, name: &str, list: &[T], mut sort: F)
+where
+    T: Clone,
+    F: FnMut(&mut Vec<T>),
+{
+    group.bench_with_input(BenchmarkId::new(name, list.len()), list, |b, list| {
+        b.iter_batched_ref(
+            || list.to_vec(),
+            |list| sort(black_box(list)),
+            BatchSize::LargeInput,
+        )
+    });
+}
+
+fn bench_sorter<S: Sorter>(
+    group: &mut BenchmarkGroup<'_, WallTime>,
+    name: &str,
+    sorter: S,
+    list: &[u64],
+) {
+    bench_sort(group, name, list, |list| sorter.sort(list));
+}
+
+fn comparison_sorts(c: &mut Criterion) {
+    for distribution in Distribution::ALL {
+        let mut group = c.benchmark_group(format!("sort/{}", distribution.name()));
+        for len in LENS {
+            let list = distribution.list(len);
+            let group = &mut group;
+            bench_sort(group, "slice::sort", &list, |list| list.sort());
+            bench_sort(group, "slice::sort_unstable", &list, |list| {
+                list.sort_unstable()
+            });
+            if len <= QUADRATIC_MAX_LEN {
+                bench_sorter(group, "InsertionSort", InsertionSort, &list);
+                bench_sorter(group, "BinaryInsertionSort", BinaryInsertionSort, &list);
+                bench_sorter(group, "SelectionSort", SelectionSort, &list);
+                bench_sorter(group, "StableSelectionSort", StableSelectionSort, &list);
+                bench_sorter(
+                    group,
+                    "DoubleEndedSelectionSort",
+                    DoubleEndedSelectionSort,
+                    &list,
+                );
+            }
+            bench_sorter(group, "LibrarySort", LibrarySort, &list);
+            bench_sorter(group, "ShellSort", ShellSort::default(), &list);
+            bench_sorter(group, "MergeSort", MergeSort, &list);
+            bench_sorter(group, "BottomUpMergeSort", BottomUpMergeSort, &list);
+            bench_sorter(group, "TimSort", TimSort, &list);
+            bench_sorter(group, "HeapSort", HeapSort, &list);
+            bench_sorter(group, "RandomizedQuickSort", RandomizedQuickSort, &list);
+            bench_sorter(
+                group,
+                "MedianOfThreeQuickSort",
+                MedianOfThreeQuickSort,
+                &list,
+            );
+            bench_sorter(group, "ThreeWayQuickSort", ThreeWayQuickSort, &list);
+            bench_sorter(group, "DualPivotQuickSort", DualPivotQuickSort, &list);
+            bench_sorter(group, "IntroSort", IntroSort, &list);
+            bench_sorter(group, "PdqSort", PdqSort, &list);
+            bench_sorter(group, "BitonicSort", BitonicSort, &list);
+            bench_sorter(group, "OddEvenMergeSort", OddEvenMergeSort, &list);
+            bench_sorter(group, "SmartSort", SmartSort::default(), &list);
+            #[cfg(feature = "parallel")]
+            {
+                let (merge_sort, quick_sort) =
+                    (ParallelMergeSort::default(), ParallelQuickSort::default());
+                bench_sort(group, "ParallelMergeSort", &list, |list| {
+                    merge_sort.sort(list)
+                });
+                bench_sort(group, "ParallelQuickSort", &list, |list| {
+                    quick_sort.sort(list)
+                });
+            }
+        }
+        group.finish();
+    }
+}
+
+fn integer_sorts(c: &mut Criterion) {
+    for distribution in [Distribution::Random, Distribution::FewUnique] {
+        let mut group = c.benchmark_group(format!("integer_sort/{}", distribution.name()));
+        for len in LENS {
+            let list = distribution.list(len);
+            let group = &mut group;
+            bench_sort(group, "slice::sort_unstable", &list, |list| {
+                list.sort_unstable()
+            });
+            // Counting sort needs keys in a small range, which only the few unique values have.
+            if let Distribution::FewUnique = distribution {
+                bench_sort(group, "counting_sort", &list, |list| {
+                    counting_sort(list, 16, |&item| item as usize)
+                });
+            }
+            bench_sort(group, "lsd_radix_sort", &list, |list| lsd_radix_sort(list));
+            bench_sort(group, "smart_sort_by_radix_key", &list, |list| {
+                smart_sort_by_radix_key(list, |&item| item);
+            });
+        }
+        group.finish();
+    }
+}
+
+fn string_sorts(c: &mut Criterion) {
+    let mut group = c.benchmark_group("string_sort");
+    for len in LENS {
+        let list = strings(len);
+        let group = &mut group;
+        bench_sort(group, "slice::sort_unstable", &list, |list| {
+            list.sort_unstable()
+        });
+        bench_sort(group, "PdqSort", &list, |list| PdqSort.sort(list));
+        bench_sort(group, "msd_radix_sort", &list, |list| msd_radix_sort(list));
+        bench_sort(group, "three_way_radix_quick_sort", &list, |list| {
+            three_way_radix_quick_sort(list)
+        });
+        bench_sort(group, "burst_sort", &list, |list| burst_sort(list));
+    }
+    group.finish();
+}
+
+fn linked_list_sorts(c: &mut Criterion) {
+    let mut group = c.benchmark_group("linked_list_sort");
+    for len in LENS.into_iter().filter(|&len| len <= LINKED_LIST_MAX_LEN) {
+        let list = Distribution::Random.list(len);
+        let mut stack = LinkedListStack::new();
+        for &item in &list {
+            stack.push(item);
+        }
+        group.bench_with_input(
+            BenchmarkId::new("LinkedListStack::sort", len),
+            &stack,
+            |b, stack| {
+                b.iter_batched_ref(
+                    || stack.clone(),
+                    |stack| black_box(stack).sort(),
+                    BatchSize::LargeInput,
+                )
+            },
+        );
+        bench_sorter(&mut group, "MergeSort", MergeSort, &list);
+    }
+    group.finish();
+}
+
+fn network_sorts(c: &mut Criterion) {
+    let mut group = c.benchmark_group("network_sort");
+    let arrays: Vec<[u64; 16]> = Distribution::Random
+        .list(16 * NETWORK_ARRAYS)
+        .chunks_exact(16)
+        .map(|chunk| chunk.try_into().unwrap())
+        .collect();
+    bench_sort(&mut group, "slice::sort_unstable", &arrays, |arrays| {
+        arrays.iter_mut().for_each(|array| array.sort_unstable())
+    });
+    bench_sort(&mut group, "InsertionSort", &arrays, |arrays| {
+        arrays
+            .iter_mut()
+            .for_each(|array| InsertionSort.sort(array))
+    });
+    bench_sort(&mut group, "sort_network", &arrays, |arrays| {
+        arrays.iter_mut().for_each(sort_network)
+    });
+    bench_sort(&mut group, "sort_network_by", &arrays, |arrays| {
+        arrays
+            .iter_mut()
+            .for_each(|array| sort_network_by(array, u64::cmp))
+    });
+    bench_sort(&mut group, "sort_network_slice_by", &arrays, |arrays| {
+        arrays
+            .iter_mut()
+            .for_each(|array| sort_network_slice_by(array, u64::cmp))
+    });
+    group.finish();
+}
+
+fn external_sorts(c: &mut Criterion) {
+    let mut group = c.benchmark_group("external_sort");
+    let dir = std::env::temp_dir().join(format!("external_sort_bench_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    for len in LENS {
+        let input = dir.join(format!("input_{}", len));
+        let output = dir.join(format!("output_{}", len));
+        let lines: String = strings(len)
+            .iter()
+            .map(|line| format!("{}\n", line))
+            .collect();
+        fs::write(&input, lines).unwrap();
+        group.bench_with_input(BenchmarkId::new("external_sort", len), &len, |b, _| {
+            b.iter(|| external_sort(&input, &output, EXTERNAL_MEMORY_BUDGET).unwrap())
+        });
+    }
+    group.finish();
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+criterion_group! {
+    name = benches;
+    // Short measurements keep the run of the whole suite, a few hundred benchmarks, within minutes.
+    config = Criterion::default()
+        .sample_size(10)
+        .warm_up_time(Duration::from_millis(500))
+        .measurement_time(Duration::from_secs(1));
+    targets = comparison_sorts, integer_sorts, string_sorts, linked_list_sorts, network_sorts, external_sorts
+}
+criterion_main!(benches);
@@ -0,0 +1,87 @@
+use std::time::Duration;
+
+use criterion::{
+    black_box, criterion_group, criterion_main, measurement::WallTime, BenchmarkGroup, BenchmarkId,
+    Criterion,
+};
+use more_rust_structures::stack::{
+    array_stack::ArrayStack, linked_list_stack::LinkedListStack, Stack,
+};
+
+const LENS: [usize; 3] = [1_000, 10_000, 100_000];
+
+/// `LinkedListStack` clones its whole list on every push and pop, which makes them linear, and would dominate the run
+/// time on longer stacks.
+const LINKED_LIST_MAX_LEN: usize = 10_000;
+
+/// Benchmarks pushing `len` items onto a new stack, then popping them all.
+fn bench_push_pop<S, F>(group: &mut BenchmarkGroup<'_, WallTime>, name: &str, len: usize, new: F)
+where
+    S: Stack<u64>,
+    F: Fn() -> S,
+{
+    group.bench_with_input(BenchmarkId::new(name, len), &len, |b, &len| {
+        b.iter(|| {
+            let mut stack = new();
+            for item in 0..len as u64 {
+                stack.push(black_box(item));
+            }
+            while stack.count() > 0 {
+                black_box(stack.pop());
+            }
+        })
+    });
+}
+
+/// Benchmarks `len` rounds of pushing two items and popping one, so that the stack keeps growing, with each pop
+/// followed by a peek.
+fn bench_interleaved<S, F>(group: &mut BenchmarkGroup<'_, WallTime>, name: &str, len: usize, new: F)
+where
+    S: Stack<u64>,
+    F: Fn() -> S,
+{
+    group.bench_with_input(BenchmarkId::new(name, len), &len, |b, &len| {
+        b.iter(|| {
+            let mut stack = new();
+            for item in 0..len as u64 {
+                stack.push(black_box(2 * item));
+                stack.push(black_box(2 * item + 1));
+                black_box(stack.pop());
+                black_box(stack.peek());
+            }
+            stack
+        })
+    });
+}
+
+fn push_pop(c: &mut Criterion) {
+    let mut group = c.benchmark_group("stack/push_pop");
+    for len in LENS {
+        bench_push_pop(&mut group, "ArrayStack", len, || ArrayStack::new(16));
+        if len <= LINKED_LIST_MAX_LEN {
+            bench_push_pop(&mut group, "LinkedListStack", len, LinkedListStack::new);
+        }
+    }
+    group.finish();
+}
+
+fn interleaved(c: &mut Criterion) {
+    let mut group = c.benchmark_group("stack/interleaved");
+    for len in LENS {
+        bench_interleaved(&mut group, "ArrayStack", len, || ArrayStack::new(16));
+        if len <= LINKED_LIST_MAX_LEN {
+            bench_interleaved(&mut group, "LinkedListStack", len, LinkedListStack::new);
+        }
+    }
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default()
+        .sample_size(10)
+        .warm_up_time(Duration::from_millis(500))
+        .measurement_time(Duration::from_secs(1));
+    targets = push_pop, interleaved
+}
+criterion_main!(benches);
@@ -0,0 +1,106 @@
+mod common;
+
+use std::time::Duration;
+
+use common::pairs;
+use criterion::{
+    black_box, criterion_group, criterion_main, measurement::WallTime, BatchSize, BenchmarkGroup,
+    BenchmarkId, Criterion,
+};
+use more_rust_structures::union_find::{
+    quick_find::QuickFind, quick_union::QuickUnion, weighted_quick_union::WeightedQuickUnion,
+    weighted_quick_union_with_path_compression::WeightedQuickUnionWithPathCompression, UnionFind,
+};
+
+const LENS: [u32; 3] = [1_000, 10_000, 100_000];
+
+/// `QuickFind` unions and `QuickUnion` finds are linear, which would dominate the run time on more values.
+const LINEAR_MAX_LEN: u32 = 10_000;
+
+/// Benchmarks `n` unions of random pairs of values, on a new union-find of `n` values.
+fn bench_union<U, F>(group: &mut BenchmarkGroup<'_, WallTime>, name: &str, n: u32, new: F)
+where
+    U: UnionFind,
+    F: Fn(u32) -> U,
+{
+    let unions = pairs(n, n as usize);
+    group.bench_with_input(BenchmarkId::new(name, n), &unions, |b, unions| {
+        b.iter_batched_ref(
+            || new(n),
+            |union_find| {
+                for &(p, q) in unions {
+                    union_find.union(black_box(p), black_box(q));
+                }
+            },
+            BatchSize::LargeInput,
+        )
+    });
+}
+
+/// Benchmarks `n` connectivity queries of random pairs of values, on a union-find of `n` values already joined by
+/// `n / 2` random unions, which leave it with many sets of various sizes.
+fn bench_is_connected<U, F>(group: &mut BenchmarkGroup<'_, WallTime>, name: &str, n: u32, new: F)
+where
+    U: UnionFind,
+    F: Fn(u32) -> U,
+{
+    let mut union_find = new(n);
+    for (p, q) in pairs(n, n as usize / 2) {
+        union_find.union(p, q);
+    }
+    let queries = pairs(n, n as usize);
+    group.bench_with_input(BenchmarkId::new(name, n), &queries, |b, queries| {
+        b.iter(|| {
+            queries
+                .iter()
+                .filter(|&&(p, q)| union_find.is_connected(black_box(p), black_box(q)))
+                .count()
+        })
+    });
+}
+
+fn union(c: &mut Criterion) {
+    let mut group = c.benchmark_group("union_find/union");
+    for n in LENS {
+        if n <= LINEAR_MAX_LEN {
+            bench_union(&mut group, "QuickFind", n, QuickFind::new);
+            bench_union(&mut group, "QuickUnion", n, QuickUnion::new);
+        }
+        bench_union(&mut group, "WeightedQuickUnion", n, WeightedQuickUnion::new);
+        bench_union(
+            &mut group,
+            "WeightedQuickUnionWithPathCompression",
+            n,
+            WeightedQuickUnionWithPathCompression::new,
+        );
+    }
+    group.finish();
+}
+
+fn is_connected(c: &mut Criterion) {
+    let mut group = c.benchmark_group("union_find/is_connected");
+    for n in LENS {
+        if n <= LINEAR_MAX_LEN {
+            bench_is_connected(&mut group, "QuickFind", n, QuickFind::new);
+            bench_is_connected(&mut group, "QuickUnion", n, QuickUnion::new);
+        }
+        bench_is_connected(&mut group, "WeightedQuickUnion", n, WeightedQuickUnion::new);
+        bench_is_connected(
+            &mut group,
+            "WeightedQuickUnionWithPathCompression",
+            n,
+            WeightedQuickUnionWithPathCompression::new,
+        );
+    }
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default()
+        .sample_size(10)
+        .warm_up_time(Duration::from_millis(500))
+        .measurement_time(Duration::from_secs(1));
+    targets = union, is_connected
+}
+criterion_main!(benches);
@@ -0,0 +1,62 @@
+//! Animates a sort of a few pseudo-random values in the terminal, or writes the steps to an HTML page.
+//!
+//! ```text
+//! cargo run --example sort_animation -- insertion
+//! cargo run --example sort_animation -- merge --html merge_sort.html
+//! ```
+
+use std::{env, fs::File, io, process, time::Duration};
+
+use more_rust_structures::sorting::{
+    render_ascii, render_html, trace, HeapSort, InsertionSort, MergeSort, Operation, PdqSort,
+    SelectionSort, ShellSort, TimSort,
+};
+
+const LEN: usize = 16;
+
+fn main() -> io::Result<()> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let algorithm = args.first().map(String::as_str).unwrap_or("insertion");
+
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    let values: Vec<u64> = (0..LEN)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            1 + state % 20
+        })
+        .collect();
+
+    let mut list = values.clone();
+    let operations: Vec<Operation> = match algorithm {
+        "selection" => trace(&SelectionSort, &mut list),
+        "insertion" => trace(&InsertionSort, &mut list),
+        "shell" => trace(&ShellSort::default(), &mut list),
+        "heap" => trace(&HeapSort, &mut list),
+        "merge" => trace(&MergeSort, &mut list),
+        "tim" => trace(&TimSort, &mut list),
+        "pdq" => trace(&PdqSort, &mut list),
+        _ => {
+            eprintln!("usage: sort_animation [selection|insertion|shell|heap|merge|tim|pdq] [--html FILE]");
+            process::exit(2);
+        }
+    };
+
+    match args.iter().position(|arg| arg == "--html") {
+        Some(index) => {
+            let Some(path) = args.get(index + 1) else {
+                eprintln!("--html needs a file name");
+                process::exit(2);
+            };
+            let title = format!("{} sort of {} items", algorithm, LEN);
+            render_html(&values, &operations, &title, &mut File::create(path)?)
+        }
+        None => render_ascii(
+            &values,
+            &operations,
+            &mut io::stdout(),
+            Duration::from_millis(150),
+        ),
+    }
+}
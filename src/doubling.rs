@@ -0,0 +1,363 @@
+use std::{
+    fmt,
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+/// The cost of running an experiment on an input of `len` items: a running time in seconds, a number of comparisons,
+/// or any other positive measure.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Measurement {
+    pub len: usize,
+    pub cost: f64,
+}
+
+///     ## Algorithm
+///     - This experiment, described by Sedgewick and Wayne, measures the cost of an algorithm on inputs of increasing
+///       length n, 2n, 4n, ..., doubling it at each step.
+///     - When the cost grows as `n^b`, the ratio between the costs of two consecutive steps tends to `2^b`, so the
+///       exponent b can be estimated from the measurements, whatever the constant factors of the cost are.
+///     - The exponent is estimated as the slope of the least squares line through the points `(log2(n), log2(cost))`
+///       of the second half of the steps, since on the shortest inputs the cost is dominated by lower order terms.
+///     - An `O(n log n)` cost has an estimated exponent slightly above 1, which gets closer to 1 as n grows.
+///     - When timing it, the experiment can stop doubling early, once a run takes longer than a maximum time, so that
+///       algorithms slower than expected still finish, with fewer measurements.
+///     ## Complexity
+///     - The experiment runs the algorithm on inputs of `n * (2^steps - 1)` items overall, and, when timing it, runs
+///       it repeatedly on each input length, for at least the minimum time.
+pub struct DoublingExperiment {
+    start_len: usize,
+    steps: usize,
+    min_time: Duration,
+    max_time: Option<Duration>,
+}
+
+/// The default minimum time `DoublingExperiment::time` spends measuring each input length.
+const DEFAULT_MIN_TIME: Duration = Duration::from_millis(100);
+
+impl DoublingExperiment {
+    /// Creates an experiment on inputs of `start_len`, `2 * start_len`, ... items, up to `start_len * 2^(steps - 1)`.
+    ///     ## Panics
+    ///     - When start_len is 0, or steps is smaller than 2.
+    pub fn new(start_len: usize, steps: usize) -> Self {
+        if start_len == 0 {
+            panic!("a doubling experiment should start from a positive length");
+        }
+        if steps < 2 {
+            panic!(
+                "a doubling experiment should run at least 2 steps, but runs {}",
+                steps
+            );
+        }
+        DoublingExperiment {
+            start_len,
+            steps,
+            min_time: DEFAULT_MIN_TIME,
+            max_time: None,
+        }
+    }
+
+    /// Sets the minimum time `time` spends measuring each input length, 100 milliseconds by default. Longer times
+    /// make the measurements of short inputs less noisy.
+    pub fn with_min_time(self, min_time: Duration) -> Self {
+        DoublingExperiment { min_time, ..self }
+    }
+
+    /// Makes `time` stop doubling once a single run of the routine takes longer than `max_time` on average, after the
+    /// first 2 steps, which are always measured. There is no maximum time by default.
+    pub fn with_max_time(self, max_time: Duration) -> Self {
+        DoublingExperiment {
+            max_time: Some(max_time),
+            ..self
+        }
+    }
+
+    /// The input lengths of the experiment, in increasing order.
+    pub fn lens(&self) -> impl Iterator<Item = usize> {
+        let start_len = self.start_len;
+        (0..self.steps).map(move |step| start_len << step)
+    }
+
+    /// Runs the experiment, measuring the cost of each input length with the `cost` function, which is called once
+    /// per length, in increasing order.
+    ///
+    /// ```
+    /// use more_rust_structures::{
+    ///     doubling::DoublingExperiment,
+    ///     sorting::{instrument, InsertionSort},
+    /// };
+    ///
+    /// let report = DoublingExperiment::new(100, 6).run(|len| {
+    ///     let mut list: Vec<usize> = (0..len).rev().collect();
+    ///     instrument(&InsertionSort, &mut list).comparisons as f64
+    /// });
+    /// assert!((report.exponent() - 2.0).abs() < 0.01);
+    /// ```
+    pub fn run<F>(&self, mut cost: F) -> DoublingReport
+    where
+        F: FnMut(usize) -> f64,
+    {
+        DoublingReport {
+            measurements: self
+                .lens()
+                .map(|len| Measurement {
+                    len,
+                    cost: cost(len),
+                })
+                .collect(),
+        }
+    }
+
+    /// Runs the experiment, measuring the average running time, in seconds, of `routine` on each input length.
+    /// For each length, inputs are built by `setup`, which is not timed, and `routine` is run on a new input until the
+    /// minimum time has elapsed. Longer inputs are skipped once a run takes longer than the maximum time.
+    pub fn time<I, R, S, F>(&self, mut setup: S, mut routine: F) -> DoublingReport
+    where
+        S: FnMut(usize) -> I,
+        F: FnMut(I) -> R,
+    {
+        let mut measurements: Vec<Measurement> = vec![];
+        for len in self.lens() {
+            if let (Some(max_time), [_, .., last]) = (self.max_time, measurements.as_slice()) {
+                if last.cost > max_time.as_secs_f64() {
+                    break;
+                }
+            }
+            let mut elapsed = Duration::ZERO;
+            let mut runs = 0;
+            while runs == 0 || elapsed < self.min_time {
+                let input = setup(len);
+                let start = Instant::now();
+                black_box(routine(black_box(input)));
+                elapsed += start.elapsed();
+                runs += 1;
+            }
+            measurements.push(Measurement {
+                len,
+                cost: elapsed.as_secs_f64() / runs as f64,
+            });
+        }
+        DoublingReport { measurements }
+    }
+}
+
+/// The measurements of a `DoublingExperiment`, from which the growth of the cost is estimated.
+/// It is displayed as a table of the input lengths, their costs and the ratios between consecutive costs, followed by
+/// the estimated exponent.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DoublingReport {
+    measurements: Vec<Measurement>,
+}
+
+impl DoublingReport {
+    pub fn measurements(&self) -> &[Measurement] {
+        &self.measurements
+    }
+
+    /// The ratios between the cost of each step and the cost of the previous one, which tend to `2^b` for costs
+    /// growing as `n^b`.
+    pub fn ratios(&self) -> Vec<f64> {
+        self.measurements
+            .windows(2)
+            .map(|pair| pair[1].cost / pair[0].cost)
+            .collect()
+    }
+
+    /// The estimated exponent b of the cost, when it grows as `n^b`: the slope of the least squares line through the
+    /// points `(log2(n), log2(cost))` of the second half of the steps. It is not a number when some cost is not
+    /// positive.
+    pub fn exponent(&self) -> f64 {
+        let points: Vec<(f64, f64)> = self.measurements[(self.measurements.len() - 1) / 2..]
+            .iter()
+            .map(|measurement| ((measurement.len as f64).log2(), measurement.cost.log2()))
+            .collect();
+        let count = points.len() as f64;
+        let mean_x = points.iter().map(|&(x, _)| x).sum::<f64>() / count;
+        let mean_y = points.iter().map(|&(_, y)| y).sum::<f64>() / count;
+        let covariance: f64 = points
+            .iter()
+            .map(|&(x, y)| (x - mean_x) * (y - mean_y))
+            .sum();
+        let variance: f64 = points.iter().map(|&(x, _)| (x - mean_x).powi(2)).sum();
+        covariance / variance
+    }
+
+    /// Returns whether the estimated exponent is within `tolerance` of the `exponent` claimed for the cost, such as 1
+    /// for `O(n)` or `O(n log n)`, and 2 for `O(n^2)`.
+    ///
+    /// ```
+    /// use more_rust_structures::doubling::DoublingExperiment;
+    ///
+    /// let report = DoublingExperiment::new(100, 6).run(|len| (len * len) as f64);
+    /// assert!(report.agrees_with(2.0, 0.25));
+    /// assert!(!report.agrees_with(1.0, 0.25));
+    /// ```
+    pub fn agrees_with(&self, exponent: f64, tolerance: f64) -> bool {
+        (self.exponent() - exponent).abs() <= tolerance
+    }
+}
+
+impl fmt::Display for DoublingReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{:>12} {:>12} {:>8}", "n", "cost", "ratio")?;
+        for (step, measurement) in self.measurements.iter().enumerate() {
+            write!(f, "{:>12} {:>12.4e}", measurement.len, measurement.cost)?;
+            match step.checked_sub(1) {
+                Some(previous) => writeln!(
+                    f,
+                    " {:>8.2}",
+                    measurement.cost / self.measurements[previous].cost
+                )?,
+                None => writeln!(f, " {:>8}", "-")?,
+            }
+        }
+        write!(f, "exponent: {:.2}", self.exponent())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{thread, time::Duration};
+
+    use crate::{
+        doubling::{DoublingExperiment, Measurement},
+        sorting::{
+            instrument, tests::pseudo_random_list, BinaryInsertionSort, HeapSort, InsertionSort,
+            MergeSort, PdqSort, SelectionSort, ShellSort, Sorter, TimSort,
+        },
+    };
+
+    #[test]
+    fn exponents() {
+        let experiment = DoublingExperiment::new(1_000, 8);
+        assert_eq!(
+            experiment.lens().collect::<Vec<_>>(),
+            [1_000, 2_000, 4_000, 8_000, 16_000, 32_000, 64_000, 128_000]
+        );
+        let quadratic = experiment.run(|len| 3.0 * (len * len) as f64 + 7.0 * len as f64);
+        assert!((quadratic.exponent() - 2.0).abs() < 0.001);
+        assert!(quadratic
+            .ratios()
+            .iter()
+            .all(|&ratio| (ratio - 4.0).abs() < 0.01));
+        assert_eq!(
+            quadratic.measurements()[0],
+            Measurement {
+                len: 1_000,
+                cost: 3_007_000.0
+            }
+        );
+
+        let linearithmic = experiment.run(|len| len as f64 * (len as f64).log2());
+        assert!((1.0..1.1).contains(&linearithmic.exponent()));
+        let constant = experiment.run(|_| 42.0);
+        assert_eq!(constant.exponent(), 0.0);
+        assert!(experiment.run(|_| 0.0).exponent().is_nan());
+    }
+
+    #[test]
+    fn complexity_claims() {
+        fn comparison_exponent<S: Sorter>(sorter: &S) -> f64 {
+            DoublingExperiment::new(256, 6)
+                .run(|len| {
+                    let mut list = pseudo_random_list(len, 31, u64::MAX);
+                    instrument(sorter, &mut list).comparisons as f64
+                })
+                .exponent()
+        }
+
+        for exponent in [
+            comparison_exponent(&InsertionSort),
+            comparison_exponent(&SelectionSort),
+        ] {
+            assert!((1.9..2.1).contains(&exponent), "{}", exponent);
+        }
+        for exponent in [
+            comparison_exponent(&BinaryInsertionSort),
+            comparison_exponent(&MergeSort),
+            comparison_exponent(&HeapSort),
+            comparison_exponent(&PdqSort),
+            comparison_exponent(&TimSort),
+        ] {
+            assert!((1.0..1.2).contains(&exponent), "{}", exponent);
+        }
+        let exponent = comparison_exponent(&ShellSort::default());
+        assert!((1.0..1.5).contains(&exponent), "{}", exponent);
+    }
+
+    #[test]
+    fn timing() {
+        let experiment = DoublingExperiment::new(10, 3).with_min_time(Duration::from_millis(1));
+        let mut setups = 0;
+        let report = experiment.time(
+            |len| {
+                setups += 1;
+                vec![0u8; len]
+            },
+            |list| list.len(),
+        );
+        assert!(setups >= 3);
+        assert_eq!(report.measurements().len(), 3);
+        assert!(report
+            .measurements()
+            .iter()
+            .all(|measurement| measurement.cost > 0.0));
+    }
+
+    #[test]
+    fn maximum_time() {
+        let experiment = DoublingExperiment::new(1_000, 5)
+            .with_min_time(Duration::from_millis(1))
+            .with_max_time(Duration::from_millis(1));
+        let report = experiment.time(
+            |len| len,
+            |len| thread::sleep(Duration::from_micros(len as u64)),
+        );
+        assert_eq!(
+            report
+                .measurements()
+                .iter()
+                .map(|measurement| measurement.len)
+                .collect::<Vec<_>>(),
+            [1_000, 2_000]
+        );
+    }
+
+    #[test]
+    fn claims() {
+        let linearithmic =
+            DoublingExperiment::new(1_000, 8).run(|len| len as f64 * (len as f64).log2());
+        assert!(linearithmic.agrees_with(1.0, 0.1));
+        assert!(!linearithmic.agrees_with(1.0, 0.01));
+        assert!(!linearithmic.agrees_with(2.0, 0.5));
+        let zero = DoublingExperiment::new(1_000, 2).run(|_| 0.0);
+        assert!(!zero.agrees_with(0.0, 1.0));
+    }
+
+    #[test]
+    fn display() {
+        let report = DoublingExperiment::new(10, 3).run(|len| len as f64);
+        assert_eq!(
+            report.to_string(),
+            concat!(
+                "           n         cost    ratio\n",
+                "          10     1.0000e1        -\n",
+                "          20     2.0000e1     2.00\n",
+                "          40     4.0000e1     2.00\n",
+                "exponent: 1.00"
+            )
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "a doubling experiment should start from a positive length")]
+    fn empty_start() {
+        DoublingExperiment::new(0, 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "a doubling experiment should run at least 2 steps, but runs 1")]
+    fn single_step() {
+        DoublingExperiment::new(10, 1);
+    }
+}
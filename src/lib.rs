@@ -0,0 +1,4 @@
+pub mod doubling;
+pub mod sorting;
+pub mod stack;
+pub mod union_find;
@@ -1,7 +1,3 @@
-mod sorting;
-mod stack;
-mod union_find;
-
 fn main() {
     println!("Hello, world!");
 }
@@ -0,0 +1,206 @@
+use crate::sorting::{
+    instrument::{NoProbe, ObserverProbe, SortObserver},
+    msd_radix_sort::byte_at,
+    permutation::apply_permutation_unchecked,
+    three_way_radix_quick_sort::three_way_radix_sort_by,
+};
+
+/// Buckets bigger than this are burst into a trie node, one bucket per byte.
+const BURST_THRESHOLD: usize = 64;
+
+/// A node of the burst trie: either a bucket of string indexes, sharing the prefix leading to it, or one child for
+/// each possible next byte, where the first child holds the strings ending at this node.
+enum BurstTrie {
+    Bucket(Vec<usize>),
+    Node(Box<[BurstTrie; 257]>),
+}
+
+///     ## Algorithm
+///     - This sorting algorithm, by Sinha and Zobel, inserts the strings of the list L into a burst trie: a trie whose
+///       leaves are unsorted buckets of strings sharing the prefix leading to them.
+///     - When a bucket grows bigger than `BURST_THRESHOLD`, it is burst into a trie node, redistributing its strings
+///       into new buckets by their next byte.
+///     - The trie is then visited in order. Each bucket is sorted on the remaining suffixes with
+///       `three_way_radix_quick_sort`, except buckets of strings ending at their node, which are all equal.
+///     - Items are only moved once, at the end, following the order of the visit.
+///     ## Complexity
+///     - Each byte of the distinguishing prefixes is read a constant number of times, and buckets are small enough to
+///       be sorted efficiently, with good cache locality.
+///     - Time Complexity is `O(D)` on average, where D is the total length of the distinguishing prefixes, and Space
+///       Complexity is `O(n)` plus the size of the trie. The sort is not stable.
+pub fn burst_sort<T: AsRef<[u8]>>(list: &mut [T]) {
+    let order = burst_argsort(list);
+    apply_permutation_unchecked(list, &order, &NoProbe);
+}
+
+/// Sorts the list like `burst_sort`, and reports the swaps which rearrange it to the `observer`. See `observe_by`.
+pub fn burst_sort_observed<T: AsRef<[u8]>>(list: &mut [T], observer: &mut dyn SortObserver) {
+    let order = burst_argsort(list);
+    apply_permutation_unchecked(list, &order, &ObserverProbe::new(list, observer));
+}
+
+/// Returns the permutation which sorts the list, read from the burst trie built by `burst_sort`.
+fn burst_argsort<T: AsRef<[u8]>>(list: &[T]) -> Vec<usize> {
+    let mut trie = BurstTrie::Bucket(vec![]);
+    for index in 0..list.len() {
+        insert(&mut trie, list, index);
+    }
+    let mut order = Vec::with_capacity(list.len());
+    collect(trie, list, &mut order);
+    order
+}
+
+fn insert<T: AsRef<[u8]>>(mut trie: &mut BurstTrie, list: &[T], index: usize) {
+    let mut depth = 0;
+    while let BurstTrie::Node(children) = trie {
+        trie = &mut children[byte_at(&list[index], depth)];
+        depth += 1;
+    }
+    if let BurstTrie::Bucket(bucket) = trie {
+        bucket.push(index);
+    }
+    burst(trie, list, depth);
+}
+
+/// Bursts the bucket if it is bigger than `BURST_THRESHOLD`. The bucket only held one string too many, so at most one
+/// of the new buckets can be too big in turn, when all the strings share their next byte: that one is burst by the
+/// next iteration, so that long shared prefixes are walked by this loop rather than by nested calls.
+fn burst<T: AsRef<[u8]>>(mut trie: &mut BurstTrie, list: &[T], mut depth: usize) {
+    loop {
+        let BurstTrie::Bucket(bucket) = trie else {
+            return;
+        };
+        // Strings in a bucket reached through the end-of-string child are all equal, so it is never burst.
+        if bucket.len() <= BURST_THRESHOLD
+            || (depth > 0 && byte_at(&list[bucket[0]], depth - 1) == 0)
+        {
+            return;
+        }
+        let mut buckets: [Vec<usize>; 257] = std::array::from_fn(|_| vec![]);
+        for index in bucket.drain(..) {
+            buckets[byte_at(&list[index], depth)].push(index);
+        }
+        let largest = (0..257).max_by_key(|&byte| buckets[byte].len()).unwrap();
+        *trie = BurstTrie::Node(Box::new(buckets.map(BurstTrie::Bucket)));
+        let BurstTrie::Node(children) = trie else {
+            unreachable!();
+        };
+        trie = &mut children[largest];
+        depth += 1;
+    }
+}
+
+/// Visits the trie in order, with an explicit stack, and takes it apart on the way, so that neither the visit nor
+/// dropping the nodes nests one call per level of the trie.
+fn collect<T: AsRef<[u8]>>(trie: BurstTrie, list: &[T], order: &mut Vec<usize>) {
+    let mut stack = vec![(trie, 0)];
+    while let Some((trie, depth)) = stack.pop() {
+        match trie {
+            BurstTrie::Bucket(mut bucket) => {
+                three_way_radix_sort_by(
+                    &mut bucket,
+                    depth,
+                    &|&index: &usize, d| byte_at(&list[index], d),
+                    &NoProbe,
+                );
+                order.extend_from_slice(&bucket);
+            }
+            BurstTrie::Node(children) => {
+                let mut children = (*children).into_iter();
+                if let Some(BurstTrie::Bucket(ended)) = children.next() {
+                    order.extend_from_slice(&ended);
+                }
+                let children = children.rev().filter(
+                    |child| !matches!(child, BurstTrie::Bucket(bucket) if bucket.is_empty()),
+                );
+                stack.extend(children.map(|child| (child, depth + 1)));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sorting::{
+        burst_sort::burst_sort,
+        tests::{path_corpus, pseudo_random_list, url_corpus},
+        InsertionSort, Sorter,
+    };
+
+    #[test]
+    fn burst_sort_test() {
+        let mut list = vec![
+            "she",
+            "sells",
+            "seashells",
+            "by",
+            "the",
+            "sea",
+            "shore",
+            "",
+            "s",
+            "sea",
+        ];
+        burst_sort(&mut list);
+        assert_eq!(
+            list,
+            vec![
+                "",
+                "by",
+                "s",
+                "sea",
+                "sea",
+                "seashells",
+                "sells",
+                "she",
+                "shore",
+                "the"
+            ]
+        );
+
+        let mut duplicates = vec!["same"; 500];
+        duplicates.extend(vec!["sam"; 500]);
+        burst_sort(&mut duplicates);
+        assert_eq!(&duplicates[..500], vec!["sam"; 500].as_slice());
+        assert_eq!(&duplicates[500..], vec!["same"; 500].as_slice());
+
+        let mut empty: Vec<String> = vec![];
+        burst_sort(&mut empty);
+        assert_eq!(empty, Vec::<String>::new());
+    }
+
+    #[test]
+    fn long_shared_prefix() {
+        // One call per shared byte, to burst or to visit the trie, would overflow the stack of the test thread.
+        let prefix = "a".repeat(10_000);
+        let mut list: Vec<String> = pseudo_random_list(200, 10, 1000)
+            .into_iter()
+            .map(|v| format!("{}{}", prefix, v))
+            .collect();
+        let mut expected = list.clone();
+        expected.sort();
+        burst_sort(&mut list);
+        assert_eq!(list, expected);
+    }
+
+    #[test]
+    fn urls() {
+        let mut list = url_corpus(2000);
+        let mut expected = list.clone();
+        InsertionSort.sort(&mut expected);
+        burst_sort(&mut list);
+        assert_eq!(list, expected);
+    }
+
+    #[test]
+    fn paths() {
+        let mut list: Vec<Vec<u8>> = path_corpus(2000)
+            .into_iter()
+            .map(String::into_bytes)
+            .collect();
+        let mut expected = list.clone();
+        InsertionSort.sort(&mut expected);
+        burst_sort(&mut list);
+        assert_eq!(list, expected);
+    }
+}
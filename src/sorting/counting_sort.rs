@@ -0,0 +1,114 @@
+use crate::sorting::{
+    instrument::{NoProbe, ObserverProbe, SortObserver},
+    permutation::apply_permutation_unchecked,
+};
+
+///     ## Algorithm
+///     - This sorting algorithm, also known as key-indexed counting, sorts the list L by integer keys in `0..radix`,
+///       extracted from each item by the `key` function, without comparing items.
+///     - It counts the number of items with each key, and turns the counts into the index at which the first item of
+///       each key goes, in the sorted list.
+///     - Items are then moved to their final position in a single pass, in their original order, so the sort is stable.
+///     ## Complexity
+///     - Keys are extracted once, and each of the passes is linear in either n or radix.
+///     - Therefore, Time Complexity is `O(n + radix)` and Space Complexity is `O(n + radix)`.
+///     ## Panics
+///     - When `key` returns a value not smaller than `radix`.
+pub fn counting_sort<T, F>(list: &mut [T], radix: usize, key: F)
+where
+    F: FnMut(&T) -> usize,
+{
+    let order = counting_argsort(list, radix, key);
+    apply_permutation_unchecked(list, &order, &NoProbe);
+}
+
+/// Sorts the list like `counting_sort`, and reports the swaps which rearrange it to the `observer`. See `observe_by`.
+///     ## Panics
+///     - When `key` returns a value not smaller than `radix`.
+pub fn counting_sort_observed<T, F>(
+    list: &mut [T],
+    radix: usize,
+    key: F,
+    observer: &mut dyn SortObserver,
+) where
+    F: FnMut(&T) -> usize,
+{
+    let order = counting_argsort(list, radix, key);
+    apply_permutation_unchecked(list, &order, &ObserverProbe::new(list, observer));
+}
+
+/// Returns the permutation which stably sorts the list by integer keys in `0..radix`, extracted from each item by the
+/// `key` function, without rearranging the list: the indexes of its items, in sorted order. It is the first part of
+/// `counting_sort`, and has the same complexity.
+///     ## Panics
+///     - When `key` returns a value not smaller than `radix`.
+pub fn counting_argsort<T, F>(list: &[T], radix: usize, key: F) -> Vec<usize>
+where
+    F: FnMut(&T) -> usize,
+{
+    let keys: Vec<usize> = list.iter().map(key).collect();
+    let mut starts = vec![0; radix + 1];
+    for &k in &keys {
+        if k >= radix {
+            panic!("key should be less than {:?}", radix);
+        }
+        starts[k + 1] += 1;
+    }
+    for r in 0..radix {
+        starts[r + 1] += starts[r];
+    }
+    let mut order = vec![0; list.len()];
+    for (index, &k) in keys.iter().enumerate() {
+        order[starts[k]] = index;
+        starts[k] += 1;
+    }
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sorting::{
+        counting_sort::{counting_argsort, counting_sort},
+        tests::{pseudo_random_list, stability_test_with},
+        InsertionSort, Sorter,
+    };
+
+    #[test]
+    fn counting_sort_test() {
+        let mut list = vec![3, 1, 4, 1, 5, 9, 2, 6];
+        counting_sort(&mut list, 10, |&item| item);
+        assert_eq!(list, vec![1, 1, 2, 3, 4, 5, 6, 9]);
+
+        let mut empty: Vec<usize> = vec![];
+        counting_sort(&mut empty, 10, |&item| item);
+        assert_eq!(empty, vec![]);
+
+        let mut list = pseudo_random_list(1000, 3, 256);
+        let mut expected = list.clone();
+        InsertionSort.sort(&mut expected);
+        counting_sort(&mut list, 256, |&item| item as usize);
+        assert_eq!(list, expected);
+    }
+
+    #[test]
+    fn counting_sort_stability() {
+        stability_test_with(|records| counting_sort(records, 5, |record| record.0 as usize));
+    }
+
+    #[test]
+    fn counting_argsort_test() {
+        let list = ["bb", "a", "ccc", "dd", "e"];
+        assert_eq!(
+            counting_argsort(&list, 4, |word| word.len()),
+            [1, 4, 0, 3, 2]
+        );
+        assert_eq!(list, ["bb", "a", "ccc", "dd", "e"]);
+        assert!(counting_argsort(&[0u8; 0], 0, |_| 0).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "key should be less than 4")]
+    fn counting_sort_key_out_of_range() {
+        counting_sort(&mut [1, 4, 2], 4, |&item| item);
+    }
+}
@@ -0,0 +1,373 @@
+use std::cmp::Ordering;
+
+use crate::sorting::permutation::check_permutation;
+
+/// Returns whether the list is sorted in ascending order. See `is_sorted_by`.
+pub fn is_sorted<T: Ord>(list: &[T]) -> bool {
+    is_sorted_by(list, T::cmp)
+}
+
+/// Returns whether the list is sorted according to the order defined by the `compare` function, that is whether no
+/// item is smaller than the one before it. Empty lists and lists of a single item are sorted. It runs at most `n - 1`
+/// comparisons, and stops at the first pair of items out of order.
+pub fn is_sorted_by<T, F>(list: &[T], mut compare: F) -> bool
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    list.windows(2)
+        .all(|pair| compare(&pair[1], &pair[0]) != Ordering::Less)
+}
+
+/// Returns whether the list is sorted in ascending order of the key extracted by the `key` function. See
+/// `is_sorted_by`.
+pub fn is_sorted_by_key<T, K, F>(list: &[T], mut key: F) -> bool
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    is_sorted_by(list, |a, b| key(a).cmp(&key(b)))
+}
+
+/// Returns the number of inversions of the list, in ascending order. See `count_inversions_by`.
+pub fn count_inversions<T: Ord>(list: &[T]) -> usize {
+    count_inversions_by(list, T::cmp)
+}
+
+///     ## Algorithm
+///     - An inversion of the list L is a pair of indexes `i < j` such that `L[j]` is smaller than `L[i]`, according to
+///       the order defined by the `compare` function. Sorted lists have no inversions, and lists sorted in descending
+///       order without duplicates have the maximum number, `n * (n - 1) / 2`. Equal items are never inverted.
+///     - The number of inversions is the number of swaps of adjacent items `InsertionSort` runs to sort L, and the
+///       Kendall tau distance of L from its sorted permutation.
+///     - The indexes of L are sorted with a top-down merge sort, which leaves L untouched. Whenever the merge takes an
+///       item from the right sub-list, that item is smaller than all the items still left in the left sub-list, and
+///       forms an inversion with each of them.
+///     ## Complexity
+///     - Time Complexity is `O(n log n)` and Space Complexity is `O(n)`, for the indexes and the merge buffer.
+pub fn count_inversions_by<T, F>(list: &[T], mut compare: F) -> usize
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let mut indexes: Vec<usize> = (0..list.len()).collect();
+    let mut buffer = indexes.clone();
+    merge_count(&mut indexes, &mut buffer, &mut |&a: &usize, &b: &usize| {
+        compare(&list[a], &list[b])
+    })
+}
+
+/// Sorts `list` with a merge sort using `buffer`, of the same length, as auxiliary space, and returns the number of
+/// inversions it had.
+fn merge_count<T: Copy, F>(list: &mut [T], buffer: &mut [T], compare: &mut F) -> usize
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let len = list.len();
+    if len < 2 {
+        return 0;
+    }
+    let mid = len / 2;
+    let mut inversions = merge_count(&mut list[..mid], &mut buffer[..mid], compare)
+        + merge_count(&mut list[mid..], &mut buffer[mid..], compare);
+    buffer.copy_from_slice(list);
+    let (left, right) = buffer.split_at(mid);
+    let (mut i, mut j) = (0, 0);
+    for slot in list.iter_mut() {
+        if j == right.len() || (i < left.len() && compare(&right[j], &left[i]) != Ordering::Less) {
+            *slot = left[i];
+            i += 1;
+        } else {
+            *slot = right[j];
+            j += 1;
+            inversions += left.len() - i;
+        }
+    }
+    inversions
+}
+
+/// Returns the number of runs of the list, in ascending order. See `count_runs_by`.
+pub fn count_runs<T: Ord>(list: &[T]) -> usize {
+    count_runs_by(list, T::cmp)
+}
+
+/// Returns the number of runs of the list: the maximal sub-lists sorted according to the order defined by the
+/// `compare` function, into which the list is split by each item smaller than the one before it. Empty lists have no
+/// runs, and sorted lists have one. It runs `n - 1` comparisons.
+/// This is the number of runs a natural merge sort, like `TimSort`, starts merging, before extending short runs.
+pub fn count_runs_by<T, F>(list: &[T], mut compare: F) -> usize
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    if list.is_empty() {
+        return 0;
+    }
+    1 + list
+        .windows(2)
+        .filter(|pair| compare(&pair[1], &pair[0]) == Ordering::Less)
+        .count()
+}
+
+/// Returns the length of the longest run of the list, in ascending order. See `longest_run_by`.
+pub fn longest_run<T: Ord>(list: &[T]) -> usize {
+    longest_run_by(list, T::cmp)
+}
+
+/// Returns the length of the longest of the runs of the list, according to the order defined by the `compare`
+/// function. See `count_runs_by`. It runs `n - 1` comparisons.
+pub fn longest_run_by<T, F>(list: &[T], mut compare: F) -> usize
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let mut longest = list.len().min(1);
+    let mut current = longest;
+    for pair in list.windows(2) {
+        if compare(&pair[1], &pair[0]) == Ordering::Less {
+            current = 1;
+        } else {
+            current += 1;
+            longest = longest.max(current);
+        }
+    }
+    longest
+}
+
+/// Returns the length of the longest strictly increasing subsequence of the list, in ascending order. See
+/// `longest_increasing_subsequence_by`.
+pub fn longest_increasing_subsequence<T: Ord>(list: &[T]) -> usize {
+    longest_increasing_subsequence_by(list, T::cmp)
+}
+
+///     ## Algorithm
+///     - A subsequence of the list L is made of items of L, not necessarily adjacent, in the order they appear in L.
+///       It is strictly increasing when each item is bigger than the one before it, according to the order defined by
+///       the `compare` function.
+///     - The length of the longest one is computed with patience sorting: `tails[k]` is the index of the smallest item
+///       ending a strictly increasing subsequence of length `k + 1` among the items scanned so far, so the items at
+///       `tails` are themselves strictly increasing.
+///     - Each item of L replaces the first tail not smaller than it, found with a binary search, or extends `tails`
+///       when it is bigger than all of them. The length of `tails` at the end is the result.
+///     - `n` minus this length is the minimum number of items to remove from L, to leave it sorted without duplicates.
+///     ## Complexity
+///     - Each item runs `O(log n)` comparisons, for the binary search.
+///     - Therefore, Time Complexity is `O(n log n)` and Space Complexity is `O(n)`, for the tails.
+pub fn longest_increasing_subsequence_by<T, F>(list: &[T], mut compare: F) -> usize
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let mut tails: Vec<usize> = vec![];
+    for (index, item) in list.iter().enumerate() {
+        let position = tails.partition_point(|&tail| compare(&list[tail], item) == Ordering::Less);
+        if position == tails.len() {
+            tails.push(index);
+        } else {
+            tails[position] = index;
+        }
+    }
+    tails.len()
+}
+
+/// Returns the Spearman footrule distance between two permutations of `0..n`, where `p[i]` and `q[i]` are the
+/// positions the two permutations give to item i: the sum, over all the items, of the distances between their two
+/// positions. It is 0 when the permutations are equal, and at most `n * n / 2`.
+///     ## Panics
+///     - When the permutations have different lengths, or either of them is not a permutation of `0..n`.
+pub fn spearman_distance(p: &[usize], q: &[usize]) -> usize {
+    check_permutations(p, q);
+    p.iter().zip(q).map(|(&a, &b)| a.abs_diff(b)).sum()
+}
+
+/// Returns the Kendall tau distance between two permutations of `0..n`, where `p[i]` and `q[i]` are the positions
+/// the two permutations give to item i: the number of pairs of items the two permutations put in opposite order. It
+/// is the number of swaps of adjacent items which turn one permutation into the other, and at most
+/// `n * (n - 1) / 2`. It is computed as the number of inversions of the positions in q of the items in the order of p,
+/// in `O(n log n)` time.
+///     ## Panics
+///     - When the permutations have different lengths, or either of them is not a permutation of `0..n`.
+pub fn kendall_distance(p: &[usize], q: &[usize]) -> usize {
+    check_permutations(p, q);
+    let mut positions = vec![0; q.len()];
+    for (&a, &b) in p.iter().zip(q) {
+        positions[a] = b;
+    }
+    count_inversions(&positions)
+}
+
+fn check_permutations(p: &[usize], q: &[usize]) {
+    if p.len() != q.len() {
+        panic!(
+            "permutations should have the same length, but have lengths {} and {}",
+            p.len(),
+            q.len()
+        );
+    }
+    check_permutation(p);
+    check_permutation(q);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sorting::{
+        disorder::{
+            count_inversions, count_inversions_by, count_runs, count_runs_by, is_sorted,
+            is_sorted_by, is_sorted_by_key, kendall_distance, longest_increasing_subsequence,
+            longest_increasing_subsequence_by, longest_run, spearman_distance,
+        },
+        instrument::instrument,
+        tests::pseudo_random_list,
+        InsertionSort,
+    };
+
+    /// All the lists of length `len` with items in `0..len`, including duplicates.
+    fn all_lists(len: usize) -> Vec<Vec<u64>> {
+        (0..len.pow(len as u32))
+            .map(|mut code| {
+                (0..len)
+                    .map(|_| {
+                        let item = code % len;
+                        code /= len;
+                        item as u64
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn brute_force_inversions(list: &[u64]) -> usize {
+        (0..list.len())
+            .flat_map(|i| (i + 1..list.len()).map(move |j| (i, j)))
+            .filter(|&(i, j)| list[j] < list[i])
+            .count()
+    }
+
+    fn brute_force_lis(list: &[u64]) -> usize {
+        (0..1u32 << list.len())
+            .map(|mask| {
+                let subsequence: Vec<u64> = (0..list.len())
+                    .filter(|i| mask >> i & 1 == 1)
+                    .map(|i| list[i])
+                    .collect();
+                subsequence
+            })
+            .filter(|subsequence| subsequence.windows(2).all(|pair| pair[0] < pair[1]))
+            .map(|subsequence| subsequence.len())
+            .max()
+            .unwrap()
+    }
+
+    #[test]
+    fn is_sorted_test() {
+        assert!(is_sorted::<u64>(&[]));
+        assert!(is_sorted(&[1]));
+        assert!(is_sorted(&[1, 1, 2, 3, 3]));
+        assert!(!is_sorted(&[1, 3, 2]));
+        assert!(is_sorted_by(&[3, 2, 2, 1], |a, b| b.cmp(a)));
+        assert!(is_sorted_by_key(&[("b", 1), ("a", 2)], |pair| pair.1));
+        for list in all_lists(5) {
+            let mut sorted = list.clone();
+            sorted.sort();
+            assert_eq!(is_sorted(&list), list == sorted);
+        }
+    }
+
+    #[test]
+    fn count_inversions_test() {
+        for len in 0..=6 {
+            for list in all_lists(len) {
+                assert_eq!(count_inversions(&list), brute_force_inversions(&list));
+            }
+        }
+        let descending: Vec<u64> = (0..1000).rev().collect();
+        assert_eq!(count_inversions(&descending), 1000 * 999 / 2);
+        assert_eq!(count_inversions_by(&descending, |a, b| b.cmp(a)), 0);
+        let list = pseudo_random_list(300, 5, 50);
+        assert_eq!(count_inversions(&list), brute_force_inversions(&list));
+    }
+
+    #[test]
+    fn inversions_are_insertion_sort_swaps() {
+        for len in [0, 1, 10, 200] {
+            let mut list = pseudo_random_list(len, len as u64 + 1, 20);
+            let inversions = count_inversions(&list);
+            assert_eq!(instrument(&InsertionSort, &mut list).swaps, inversions);
+        }
+    }
+
+    #[test]
+    fn runs_test() {
+        assert_eq!(count_runs::<u64>(&[]), 0);
+        assert_eq!(longest_run::<u64>(&[]), 0);
+        assert_eq!(count_runs(&[7]), 1);
+        assert_eq!(longest_run(&[7]), 1);
+        assert_eq!(count_runs(&[1, 2, 2, 3]), 1);
+        assert_eq!(longest_run(&[1, 2, 2, 3]), 4);
+        assert_eq!(count_runs(&[3, 4, 1, 2, 0]), 3);
+        assert_eq!(longest_run(&[3, 4, 1, 2, 5, 0]), 3);
+        assert_eq!(count_runs(&[5, 4, 3, 2, 1]), 5);
+        assert_eq!(count_runs_by(&[5, 4, 3, 2, 1], |a, b| b.cmp(a)), 1);
+    }
+
+    #[test]
+    fn longest_increasing_subsequence_test() {
+        for len in 0..=6 {
+            for list in all_lists(len) {
+                assert_eq!(
+                    longest_increasing_subsequence(&list),
+                    brute_force_lis(&list)
+                );
+            }
+        }
+        assert_eq!(longest_increasing_subsequence(&[3, 1, 4, 1, 5, 9, 2, 6]), 4);
+        assert_eq!(longest_increasing_subsequence(&[2, 2, 2]), 1);
+        assert_eq!(
+            longest_increasing_subsequence_by(&[1, 2, 3, 0], |a, b| b.cmp(a)),
+            2
+        );
+    }
+
+    #[test]
+    fn distances_test() {
+        let identity = [0, 1, 2, 3];
+        let reversed = [3, 2, 1, 0];
+        assert_eq!(spearman_distance(&identity, &identity), 0);
+        assert_eq!(kendall_distance(&identity, &identity), 0);
+        assert_eq!(spearman_distance(&identity, &reversed), 8);
+        assert_eq!(kendall_distance(&identity, &reversed), 6);
+        assert_eq!(spearman_distance(&[1, 0, 2], &[0, 1, 2]), 2);
+        assert_eq!(kendall_distance(&[1, 0, 2], &[0, 1, 2]), 1);
+        assert_eq!(kendall_distance(&[], &[]), 0);
+
+        // The Kendall distance from the identity is the number of inversions, and the distances are symmetric and
+        // within the Diaconis-Graham inequality.
+        let permutations: Vec<Vec<usize>> = all_lists(5)
+            .into_iter()
+            .filter(|list| {
+                let mut sorted = list.clone();
+                sorted.sort();
+                sorted == [0, 1, 2, 3, 4]
+            })
+            .map(|list| list.into_iter().map(|item| item as usize).collect())
+            .collect();
+        assert_eq!(permutations.len(), 120);
+        for p in &permutations {
+            assert_eq!(kendall_distance(&[0, 1, 2, 3, 4], p), count_inversions(p));
+            for q in &permutations {
+                let kendall = kendall_distance(p, q);
+                let spearman = spearman_distance(p, q);
+                assert_eq!(kendall, kendall_distance(q, p));
+                assert_eq!(spearman, spearman_distance(q, p));
+                assert!(kendall <= spearman && spearman <= 2 * kendall);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "permutations should have the same length")]
+    fn distances_of_different_lengths() {
+        kendall_distance(&[0, 1], &[0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "[0, 0] is not a permutation of 0..2")]
+    fn distances_of_non_permutations() {
+        spearman_distance(&[0, 1], &[0, 0]);
+    }
+}
@@ -0,0 +1,395 @@
+use std::{
+    cell::Cell,
+    cmp::Ordering,
+    fs::{self, File},
+    io::{self, BufRead, BufReader, BufWriter, ErrorKind, Write},
+    iter,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering as AtomicOrdering},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::sorting::{sorted_iter::merge_k_by, Sorter, TimSort};
+
+/// Sorted runs are merged at most this many at a time by default, to bound the number of open files.
+const DEFAULT_MAX_FAN_IN: usize = 128;
+
+/// Defines how records are read from and written to files, and how much memory they take once read.
+pub trait RecordCodec {
+    type Record;
+
+    /// Reads the next record, or returns `None` at the end of the input.
+    fn read<R: BufRead>(&self, reader: &mut R) -> io::Result<Option<Self::Record>>;
+
+    /// Writes the record, so that `read` reads it back.
+    fn write<W: Write>(&self, writer: &mut W, record: &Self::Record) -> io::Result<()>;
+
+    /// The approximate number of bytes the record takes in memory.
+    fn size(&self, record: &Self::Record) -> usize;
+}
+
+/// Newline-delimited records. Records don't include their trailing newline, and the last record of a file may have
+/// none.
+pub struct LineCodec;
+
+impl RecordCodec for LineCodec {
+    type Record = Vec<u8>;
+
+    fn read<R: BufRead>(&self, reader: &mut R) -> io::Result<Option<Vec<u8>>> {
+        let mut record = vec![];
+        if reader.read_until(b'\n', &mut record)? == 0 {
+            return Ok(None);
+        }
+        if record.last() == Some(&b'\n') {
+            record.pop();
+        }
+        Ok(Some(record))
+    }
+
+    fn write<W: Write>(&self, writer: &mut W, record: &Vec<u8>) -> io::Result<()> {
+        writer.write_all(record)?;
+        writer.write_all(b"\n")
+    }
+
+    fn size(&self, record: &Vec<u8>) -> usize {
+        std::mem::size_of::<Vec<u8>>() + record.len()
+    }
+}
+
+/// Records made of their length, as a little-endian `u32`, followed by that many bytes.
+pub struct LengthPrefixedCodec;
+
+impl RecordCodec for LengthPrefixedCodec {
+    type Record = Vec<u8>;
+
+    fn read<R: BufRead>(&self, reader: &mut R) -> io::Result<Option<Vec<u8>>> {
+        if reader.fill_buf()?.is_empty() {
+            return Ok(None);
+        }
+        let mut length = [0; 4];
+        reader.read_exact(&mut length)?;
+        let mut record = vec![0; u32::from_le_bytes(length) as usize];
+        reader.read_exact(&mut record)?;
+        Ok(Some(record))
+    }
+
+    fn write<W: Write>(&self, writer: &mut W, record: &Vec<u8>) -> io::Result<()> {
+        let length = u32::try_from(record.len()).map_err(|_| {
+            io::Error::new(ErrorKind::InvalidInput, "record longer than u32::MAX bytes")
+        })?;
+        writer.write_all(&length.to_le_bytes())?;
+        writer.write_all(record)
+    }
+
+    fn size(&self, record: &Vec<u8>) -> usize {
+        std::mem::size_of::<Vec<u8>>() + record.len()
+    }
+}
+
+///     ## Algorithm
+///     - This sorting algorithm sorts the records of a file which may not fit in memory, decoded and encoded by a
+///       `RecordCodec`, into another file.
+///     - The input is read in chunks of records taking up to `memory_budget` bytes in memory. Each chunk is sorted with
+///       `TimSort` and written to a temporary file, as a sorted run, in a fresh sub-directory of `temp_dir`.
+///     - The runs are then merged, at most `max_fan_in` at a time, with a k-way merge: the next record of each run is
+///       kept in a binary min-heap, and the smallest one is repeatedly written out and replaced with the following
+///       record of the same run. When there are more runs than `max_fan_in`, groups of runs are merged into longer
+///       runs first.
+///     - Ties between runs go to the run read first, so the sort is stable.
+///     - The temporary files are removed once the sort completes, or fails.
+///     ## Complexity
+///     - With r runs and a fan-in of k, the records are read and written `1 + log_k(r)` times, and Time Complexity is
+///       `O(n log n)`.
+///     - Space Complexity is `O(memory_budget)` in memory, and `O(n)` on disk, for the runs.
+pub struct ExternalSort<C> {
+    codec: C,
+    memory_budget: usize,
+    max_fan_in: usize,
+    temp_dir: PathBuf,
+}
+
+impl<C: RecordCodec> ExternalSort<C> {
+    /// Creates an external sort reading chunks of at most `memory_budget` bytes, and writing its runs under the system
+    /// temporary directory.
+    pub fn new(codec: C, memory_budget: usize) -> Self {
+        ExternalSort {
+            codec,
+            memory_budget,
+            max_fan_in: DEFAULT_MAX_FAN_IN,
+            temp_dir: std::env::temp_dir(),
+        }
+    }
+
+    /// Sets the directory under which the sorted runs are written.
+    pub fn temp_dir<P: Into<PathBuf>>(mut self, temp_dir: P) -> Self {
+        self.temp_dir = temp_dir.into();
+        self
+    }
+
+    /// Sets the maximum number of runs merged at once. Values smaller than 2 are treated as 2.
+    pub fn max_fan_in(mut self, max_fan_in: usize) -> Self {
+        self.max_fan_in = max_fan_in.max(2);
+        self
+    }
+
+    /// Sorts the records of the `input` file into the `output` file, in ascending order.
+    pub fn sort(&self, input: &Path, output: &Path) -> io::Result<()>
+    where
+        C::Record: Ord,
+    {
+        self.sort_by(input, output, C::Record::cmp)
+    }
+
+    /// Sorts the records of the `input` file into the `output` file, according to the order defined by the `compare`
+    /// function.
+    pub fn sort_by<F>(&self, input: &Path, output: &Path, mut compare: F) -> io::Result<()>
+    where
+        F: FnMut(&C::Record, &C::Record) -> Ordering,
+    {
+        let work_dir = WorkDir::create(&self.temp_dir)?;
+        let mut runs = self.write_runs(input, &work_dir, &mut compare)?;
+        while runs.len() > self.max_fan_in {
+            let mut merged = Vec::with_capacity(runs.len() / self.max_fan_in + 1);
+            for group in runs.chunks(self.max_fan_in) {
+                let run = work_dir.next_file();
+                self.merge(group, &run, &mut compare)?;
+                merged.push(run);
+            }
+            for run in &runs {
+                fs::remove_file(run)?;
+            }
+            runs = merged;
+        }
+        self.merge(&runs, output, &mut compare)
+    }
+
+    /// Reads the input in chunks within the memory budget, and writes each of them, sorted, to its own run file.
+    fn write_runs<F>(
+        &self,
+        input: &Path,
+        work_dir: &WorkDir,
+        compare: &mut F,
+    ) -> io::Result<Vec<PathBuf>>
+    where
+        F: FnMut(&C::Record, &C::Record) -> Ordering,
+    {
+        let mut reader = BufReader::new(File::open(input)?);
+        let mut runs = vec![];
+        let mut chunk = vec![];
+        let mut chunk_size = 0;
+        loop {
+            let record = self.codec.read(&mut reader)?;
+            let is_done = record.is_none();
+            if let Some(record) = record {
+                chunk_size += self.codec.size(&record);
+                chunk.push(record);
+            }
+            if (is_done && !chunk.is_empty()) || chunk_size >= self.memory_budget {
+                TimSort.sort_by(&mut chunk, &mut *compare);
+                let run = work_dir.next_file();
+                let mut writer = BufWriter::new(File::create(&run)?);
+                for record in chunk.drain(..) {
+                    self.codec.write(&mut writer, &record)?;
+                }
+                writer.flush()?;
+                runs.push(run);
+                chunk_size = 0;
+            }
+            if is_done {
+                return Ok(runs);
+            }
+        }
+    }
+
+    /// Merges the sorted `runs` into the `output` file.
+    /// Read errors are ordered before all the records, so that the merge yields them as soon as they happen.
+    fn merge<F>(&self, runs: &[PathBuf], output: &Path, compare: &mut F) -> io::Result<()>
+    where
+        F: FnMut(&C::Record, &C::Record) -> Ordering,
+    {
+        let readers = runs
+            .iter()
+            .map(|run| {
+                let mut reader = BufReader::new(File::open(run)?);
+                let mut has_failed = false;
+                Ok(iter::from_fn(move || {
+                    if has_failed {
+                        return None;
+                    }
+                    let record = self.codec.read(&mut reader).transpose();
+                    has_failed = matches!(record, Some(Err(_)));
+                    record
+                }))
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+        let mut writer = BufWriter::new(File::create(output)?);
+        let records = merge_k_by(readers, |a, b| match (a, b) {
+            (Ok(a), Ok(b)) => compare(a, b),
+            (Err(_), Err(_)) => Ordering::Equal,
+            (Err(_), Ok(_)) => Ordering::Less,
+            (Ok(_), Err(_)) => Ordering::Greater,
+        });
+        for record in records {
+            self.codec.write(&mut writer, &record?)?;
+        }
+        writer.flush()
+    }
+}
+
+/// Sorts the lines of the `input` file into the `output` file, in ascending byte order, holding at most around
+/// `memory_budget` bytes of lines in memory at once.
+pub fn external_sort(input: &Path, output: &Path, memory_budget: usize) -> io::Result<()> {
+    ExternalSort::new(LineCodec, memory_budget).sort(input, output)
+}
+
+/// A fresh directory for the runs of a single sort, removed with everything in it when dropped.
+struct WorkDir {
+    path: PathBuf,
+    files: Cell<usize>,
+}
+
+impl WorkDir {
+    fn create(parent: &Path) -> io::Result<Self> {
+        static SORTS: AtomicUsize = AtomicUsize::new(0);
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |time| time.as_nanos());
+        let path = parent.join(format!(
+            "external-sort-{}-{}-{}",
+            std::process::id(),
+            nanos,
+            SORTS.fetch_add(1, AtomicOrdering::Relaxed)
+        ));
+        fs::create_dir_all(&path)?;
+        Ok(WorkDir {
+            path,
+            files: Cell::new(0),
+        })
+    }
+
+    fn next_file(&self) -> PathBuf {
+        let file = self.files.get();
+        self.files.set(file + 1);
+        self.path.join(format!("run-{}", file))
+    }
+}
+
+impl Drop for WorkDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        io::{BufReader, Cursor},
+        path::PathBuf,
+    };
+
+    use crate::sorting::{
+        external_sort::{
+            external_sort, ExternalSort, LengthPrefixedCodec, LineCodec, RecordCodec, WorkDir,
+        },
+        tests::{pseudo_random_list, url_corpus},
+    };
+
+    #[test]
+    fn lines() {
+        let dir = WorkDir::create(&std::env::temp_dir()).unwrap();
+        let input = dir.next_file();
+        let output = dir.next_file();
+        let lines = url_corpus(5000);
+        fs::write(&input, lines.join("\n")).unwrap();
+
+        external_sort(&input, &output, 4096).unwrap();
+
+        let mut expected = lines.clone();
+        expected.sort();
+        let sorted = fs::read_to_string(&output).unwrap();
+        assert_eq!(sorted.lines().collect::<Vec<&str>>(), expected);
+    }
+
+    #[test]
+    fn multiple_merge_passes() {
+        let dir = WorkDir::create(&std::env::temp_dir()).unwrap();
+        let input = dir.next_file();
+        let output = dir.next_file();
+        let numbers: Vec<String> = pseudo_random_list(2000, 3, 1_000_000)
+            .iter()
+            .map(|v| format!("{:07}", v))
+            .collect();
+        fs::write(&input, numbers.join("\n") + "\n").unwrap();
+
+        let temp_dir = dir.path.join("runs");
+        ExternalSort::new(LineCodec, 256)
+            .max_fan_in(3)
+            .temp_dir(&temp_dir)
+            .sort(&input, &output)
+            .unwrap();
+
+        let mut expected = numbers.clone();
+        expected.sort();
+        let sorted = fs::read_to_string(&output).unwrap();
+        assert_eq!(sorted.lines().collect::<Vec<&str>>(), expected);
+        assert_eq!(fs::read_dir(&temp_dir).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn length_prefixed_records_with_comparator() {
+        let dir = WorkDir::create(&std::env::temp_dir()).unwrap();
+        let input = dir.next_file();
+        let output = dir.next_file();
+        // Records are an 8-byte big-endian timestamp followed by a payload, and contain arbitrary bytes, newlines included.
+        let records: Vec<Vec<u8>> = pseudo_random_list(3000, 5, 100)
+            .into_iter()
+            .enumerate()
+            .map(|(index, timestamp)| {
+                let mut record = timestamp.to_be_bytes().to_vec();
+                record.extend(format!("\n{}", index).into_bytes());
+                record
+            })
+            .collect();
+        let mut encoded = vec![];
+        for record in &records {
+            LengthPrefixedCodec.write(&mut encoded, record).unwrap();
+        }
+        fs::write(&input, encoded).unwrap();
+
+        ExternalSort::new(LengthPrefixedCodec, 1024)
+            .sort_by(&input, &output, |a, b| a[..8].cmp(&b[..8]))
+            .unwrap();
+
+        let mut reader = BufReader::new(fs::File::open(&output).unwrap());
+        let mut sorted = vec![];
+        while let Some(record) = LengthPrefixedCodec.read(&mut reader).unwrap() {
+            sorted.push(record);
+        }
+        let mut expected = records.clone();
+        expected.sort_by(|a, b| a[..8].cmp(&b[..8]));
+        assert_eq!(sorted, expected);
+    }
+
+    #[test]
+    fn empty_input() {
+        let dir = WorkDir::create(&std::env::temp_dir()).unwrap();
+        let input = dir.next_file();
+        let output = dir.next_file();
+        fs::write(&input, "").unwrap();
+        external_sort(&input, &output, 1024).unwrap();
+        assert_eq!(fs::read(&output).unwrap(), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn missing_input() {
+        let missing = PathBuf::from("/definitely/not/a/file");
+        assert!(external_sort(&missing, &std::env::temp_dir().join("unused"), 1024).is_err());
+    }
+
+    #[test]
+    fn truncated_length_prefixed_record() {
+        let mut reader = Cursor::new(vec![10, 0, 0, 0, 1, 2]);
+        assert!(LengthPrefixedCodec.read(&mut reader).is_err());
+    }
+}
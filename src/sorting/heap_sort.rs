@@ -0,0 +1,92 @@
+use std::cmp::Ordering;
+
+use crate::sorting::{
+    instrument::{swap, NoProbe, ObserverProbe, Probe},
+    SortObserver, Sorter,
+};
+
+///     ## Algorithm
+///     - This sorting algorithm first rearranges the list L into a binary max-heap, where the item at index `k` is not
+///       smaller than its children, at indexes `2 * k + 1` and `2 * k + 2`.
+///     - The heap is built bottom-up: every parent, from the last one, `L[n / 2 - 1]`, back to the root `L[0]`, is sunk
+///       down until it is not smaller than its children.
+///     - During the sortdown, the root, which is the biggest item of the heap, is swapped with the last item of the heap,
+///       the heap shrinks by one and the new root is sunk down to restore the heap order.
+///     - When the heap is empty, L is sorted.
+///     ## Complexity
+///     - The heap construction runs less than `2 * n` comparisons, and each of the n sinks of the sortdown runs at most
+///       `2 * log2(n)` comparisons.
+///     - Therefore, Time Complexity is `O(n log n)`, also in the worst case, and Space Complexity is `O(1)`, since the
+///       algorithm runs in place.
+pub struct HeapSort;
+
+impl Sorter for HeapSort {
+    fn sort_by<T, F>(&self, list: &mut [T], mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        heap_sort(list, &mut compare, &NoProbe);
+    }
+
+    fn sort_observed_by<T, F>(&self, list: &mut [T], compare: F, observer: &mut dyn SortObserver)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let probe = ObserverProbe::new(list, observer);
+        heap_sort(list, &mut probe.comparing(compare), &probe);
+    }
+}
+
+/// Sorts the `list` with `HeapSort`, and reports its swaps to the `probe`.
+pub(crate) fn heap_sort<T, F, P>(list: &mut [T], compare: &mut F, probe: &P)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    P: Probe,
+{
+    let len = list.len();
+    for parent in (0..len / 2).rev() {
+        sink(list, parent, len, compare, probe);
+    }
+    for end in (1..len).rev() {
+        swap(list, 0, end, probe);
+        sink(list, 0, end, compare, probe);
+    }
+}
+
+/// Moves the item at index `node` down the max-heap `list[..len]`, swapping it with its bigger child, until it is not
+/// smaller than any of its children.
+pub(crate) fn sink<T, F, P>(list: &mut [T], mut node: usize, len: usize, compare: &mut F, probe: &P)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    P: Probe,
+{
+    loop {
+        let mut child = 2 * node + 1;
+        if child >= len {
+            break;
+        }
+        if child + 1 < len && compare(&list[child], &list[child + 1]) == Ordering::Less {
+            child += 1;
+        }
+        if compare(&list[node], &list[child]) != Ordering::Less {
+            break;
+        }
+        swap(list, node, child, probe);
+        node = child;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sorting::{
+        heap_sort::HeapSort,
+        tests::{adversarial_test, pseudo_random_test, sorting_test},
+    };
+
+    #[test]
+    fn heap_sort_test() {
+        sorting_test(HeapSort);
+        pseudo_random_test(HeapSort);
+        adversarial_test(HeapSort);
+    }
+}
@@ -0,0 +1,417 @@
+use std::cmp::Ordering;
+
+use crate::sorting::{
+    instrument::{rotate_right_by_one, swap, NoProbe, ObserverProbe, Probe},
+    permutation::apply_permutation_unchecked,
+    search::upper_bound_by,
+    SortObserver, Sorter,
+};
+
+///     ## Algorithm
+///     - This sorting algorithm split the list L being sorted in two parts: the sorted part, located at the beginning
+///       of the list (`L[..i]`), and the unsorted part, located at the end of the list (`L[i..]`).
+///       <br/>
+///     - At the beginning the sorted part is empty (i.e. length 0) and the unsorted part covers the entire list (i.e.
+///       length n).
+///     - The algorithm runs n - 1 1-based iterations, where n is the number of items in the list.
+///     - At the beginning of iteration i, the sorted sub-list is `L[..i]` and the unsorted sub-list is `L[i..]`.
+///     - The first item `L[i]`, of the unsorted sub-list `L[i..]`, is compared against its predecessor, `L[i - 1]`.
+///     - If `L[i]` is smaller than `L[i - 1]`, the two items are swapped and the new `L[i - 1]` is compared with `L[i - 2]`.
+///       Comparisons and swapping continues until the predecessor is not bigger than its successor, potentially until
+///       the head of the list is reached.
+///     - When a `L[j]` is found, which is not strictly smaller than `L[j - 1]`, `L[.. (i + 1)]` is sorted, and the iteration
+///       i can terminate.
+///     ## Complexity
+///     - Each of the n - 1 iterations runs at most i - 1 comparisons, if it has to swap all the way up to the head of
+///       the list.
+///     - The total number of comparisons, over the n iterations, is around `n * n / 2`.
+///     - Therefore, Time Complexity is `O(n^2)` and Space Complexity is `O(1)`, since the algorithm runs in place and
+///       hence only requires additional constant space to perform the sorting.
+pub struct InsertionSort;
+
+impl Sorter for InsertionSort {
+    fn sort_by<T, F>(&self, list: &mut [T], mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        insertion_sort(list, &mut compare, &NoProbe);
+    }
+
+    fn sort_observed_by<T, F>(&self, list: &mut [T], compare: F, observer: &mut dyn SortObserver)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let probe = ObserverProbe::new(list, observer);
+        insertion_sort(list, &mut probe.comparing(compare), &probe);
+    }
+}
+
+/// Sorts the `list` with `InsertionSort`, and reports its swaps to the `probe`.
+pub(crate) fn insertion_sort<T, F, P>(list: &mut [T], compare: &mut F, probe: &P)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    P: Probe,
+{
+    for i in 0..list.len() {
+        for j in (1..i + 1).rev() {
+            if compare(&list[j], &list[j - 1]) == Ordering::Less {
+                swap(list, j, j - 1, probe)
+            } else {
+                break;
+            }
+        }
+    }
+}
+
+///     ## Algorithm
+///     - This sorting algorithm is a variant of `InsertionSort` which, at iteration i, finds where `L[i]` goes in the
+///       sorted sub-list `L[..i]` with a binary search, instead of comparing it with each of its predecessors.
+///     - The binary search looks for the first item of `L[..i]` bigger than `L[i]`, so `L[i]` goes after the items
+///       equal to it and the sort is stable.
+///     - `L[i]` is then moved into place by shifting the bigger items right by one position with a single bulk copy,
+///       rather than with a swap per item.
+///     ## Complexity
+///     - Each of the n - 1 iterations runs at most `ceil(log2(i + 1))` comparisons, so the total number of comparisons
+///       is at most around `n * log2(n)`, which makes it a good fit when comparisons are expensive.
+///     - Items are still moved up to `n * n / 2` times, so Time Complexity is `O(n^2)`, and Space Complexity is `O(1)`,
+///       since the algorithm runs in place.
+pub struct BinaryInsertionSort;
+
+impl Sorter for BinaryInsertionSort {
+    fn sort_by<T, F>(&self, list: &mut [T], mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        binary_insertion_sort(list, 1, &mut compare, &NoProbe);
+    }
+
+    fn sort_observed_by<T, F>(&self, list: &mut [T], compare: F, observer: &mut dyn SortObserver)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let probe = ObserverProbe::new(list, observer);
+        binary_insertion_sort(list, 1, &mut probe.comparing(compare), &probe);
+    }
+}
+
+/// Sorts the list, whose first `sorted` items are already sorted, inserting each following item after the last item
+/// not bigger than it, found with a binary search, and reports the moves to the `probe`.
+pub(crate) fn binary_insertion_sort<T, F, P>(
+    list: &mut [T],
+    sorted: usize,
+    compare: &mut F,
+    probe: &P,
+) where
+    F: FnMut(&T, &T) -> Ordering,
+    P: Probe,
+{
+    for i in sorted.max(1)..list.len() {
+        let lo = upper_bound_by(&list[..i], &list[i], &mut *compare);
+        if lo < i {
+            rotate_right_by_one(&mut list[lo..=i], probe);
+        }
+    }
+}
+
+/// The number of slots of the gapped array of `LibrarySort` for each item it will hold by the end of a round.
+const LIBRARY_SORT_SPREAD: usize = 2;
+
+///     ## Algorithm
+///     - This sorting algorithm, also known as gapped insertion sort, by Bender, Farach-Colton and Mosteiro, is a
+///       variant of `BinaryInsertionSort` which leaves gaps between the sorted items, like a library leaves room on
+///       its shelves for new books, so that each insertion only has to shift a few items before reaching a gap.
+///     - The items of the list L are inserted, in their original order, into a gapped array of indexes of L, in
+///       rounds: each round doubles the number of items inserted so far.
+///     - Before each round, the items inserted so far are spread evenly over an array with `LIBRARY_SORT_SPREAD`
+///       slots for each item it will hold at the end of the round.
+///     - Each item is inserted after the last item not bigger than it, found with a binary search over the items, so
+///       the sort is stable. If the slot is taken, the items up to the nearest gap are shifted by one slot.
+///     - The occupied slots are kept in a Fenwick tree, which finds the slot of the k-th item, and the nearest gap, in
+///       `O(log n)`, without scanning the gaps or the items in between.
+///     - Finally, L is rearranged in the order of the gapped array.
+///     ## Complexity
+///     - Each insertion runs `O(log n)` comparisons, `O(log^2 n)` other steps to look the items up, and, on average
+///       over random inputs, shifts `O(1)` items.
+///     - Therefore, Time Complexity is `O(n log^2 n)` with high probability, although adversarial inputs can make the
+///       shifts add up to `O(n^2)`, and Space Complexity is `O(n)`, for the gapped array and the tree.
+pub struct LibrarySort;
+
+impl Sorter for LibrarySort {
+    fn sort_by<T, F>(&self, list: &mut [T], mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        library_sort(list, &mut compare, &NoProbe);
+    }
+
+    fn sort_observed_by<T, F>(&self, list: &mut [T], compare: F, observer: &mut dyn SortObserver)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let probe = ObserverProbe::new(list, observer);
+        library_sort(list, &mut probe.comparing(compare), &probe);
+    }
+}
+
+/// Sorts the `list` with `LibrarySort`, and reports the swaps which rearrange it to the `probe`.
+fn library_sort<T, F, P>(list: &mut [T], compare: &mut F, probe: &P)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    P: Probe,
+{
+    let len = list.len();
+    if len < 2 {
+        return;
+    }
+    let mut slots = vec![Some(0)];
+    let mut inserted = 1;
+    while inserted < len {
+        let round_end = (2 * inserted).min(len);
+        slots = spread(slots, inserted, LIBRARY_SORT_SPREAD * round_end);
+        let mut occupancy = Occupancy::new(&slots);
+        for item in inserted..round_end {
+            // The items inserted so far are exactly the ones before `item` in the list.
+            let position = gapped_upper_bound(&slots, &occupancy, item, |other| {
+                compare(&list[item], &list[other]) != Ordering::Less
+            });
+            insert_into_gap(&mut slots, &mut occupancy, position, item);
+        }
+        inserted = round_end;
+    }
+    let order: Vec<usize> = slots.into_iter().flatten().collect();
+    apply_permutation_unchecked(list, &order, probe);
+}
+
+/// Returns a gapped array of `size` slots, with the `count` items of `slots` spread evenly over it, in order.
+fn spread(slots: Vec<Option<usize>>, count: usize, size: usize) -> Vec<Option<usize>> {
+    let mut spread = vec![None; size];
+    for (k, item) in slots.into_iter().flatten().enumerate() {
+        spread[k * size / count] = Some(item);
+    }
+    spread
+}
+
+/// The occupied slots of the gapped array of `LibrarySort`, as a Fenwick tree, which finds the slot of the k-th item
+/// or of the k-th gap in `O(log n)`, rather than by scanning the slots.
+struct Occupancy {
+    /// `tree[i]` is the number of occupied slots among the `i & i.wrapping_neg()` slots ending with slot `i - 1`.
+    tree: Vec<usize>,
+}
+
+impl Occupancy {
+    fn new(slots: &[Option<usize>]) -> Self {
+        let mut tree = vec![0; slots.len() + 1];
+        for i in 1..tree.len() {
+            tree[i] += slots[i - 1].is_some() as usize;
+            let parent = i + (i & i.wrapping_neg());
+            if parent < tree.len() {
+                tree[parent] += tree[i];
+            }
+        }
+        Occupancy { tree }
+    }
+
+    /// Marks a gap as occupied.
+    fn occupy(&mut self, slot: usize) {
+        let mut i = slot + 1;
+        while i < self.tree.len() {
+            self.tree[i] += 1;
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    /// Returns the number of occupied slots before `slot`.
+    fn occupied_before(&self, slot: usize) -> usize {
+        let mut count = 0;
+        let mut i = slot;
+        while i > 0 {
+            count += self.tree[i];
+            i -= i & i.wrapping_neg();
+        }
+        count
+    }
+
+    /// Returns the slot of the k-th item, counting from 0.
+    fn nth_item(&self, k: usize) -> usize {
+        self.find(k, |_, occupied| occupied)
+    }
+
+    /// Returns the slot of the k-th gap, counting from 0, or the number of slots when there are at most k gaps.
+    fn nth_gap(&self, k: usize) -> usize {
+        self.find(k, |step, occupied| step - occupied)
+    }
+
+    /// Descends the tree to the last slot with at most k counted slots before it, where `count` counts the slots of a
+    /// node from its length and its number of occupied slots.
+    fn find<C>(&self, mut k: usize, count: C) -> usize
+    where
+        C: Fn(usize, usize) -> usize,
+    {
+        let len = self.tree.len() - 1;
+        let mut slot = 0;
+        let mut step = if len == 0 { 0 } else { 1 << len.ilog2() };
+        while step > 0 {
+            let next = slot + step;
+            if next <= len && count(step, self.tree[next]) <= k {
+                slot = next;
+                k -= count(step, self.tree[next]);
+            }
+            step /= 2;
+        }
+        slot
+    }
+}
+
+/// Returns the slot right after the last of the `count` items for which `is_before` holds, which must hold for all
+/// the items before it and for none after it, or 0 when it holds for none. The slot returned may be a gap.
+fn gapped_upper_bound<P>(
+    slots: &[Option<usize>],
+    occupancy: &Occupancy,
+    count: usize,
+    mut is_before: P,
+) -> usize
+where
+    P: FnMut(usize) -> bool,
+{
+    let mut lo = 0;
+    let mut hi = count;
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        let item = slots[occupancy.nth_item(mid)].unwrap();
+        if is_before(item) {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+    match lo {
+        0 => 0,
+        _ => occupancy.nth_item(lo - 1) + 1,
+    }
+}
+
+/// Puts `item` in the slot at `position`, or, when it's taken, right before or after the items up to the nearest gap,
+/// shifting them by one slot to make room.
+fn insert_into_gap(
+    slots: &mut [Option<usize>],
+    occupancy: &mut Occupancy,
+    position: usize,
+    item: usize,
+) {
+    let gaps_before = position - occupancy.occupied_before(position);
+    let gap = occupancy.nth_gap(gaps_before);
+    if gap < slots.len() {
+        slots[position..=gap].rotate_right(1);
+        slots[position] = Some(item);
+        occupancy.occupy(gap);
+    } else {
+        assert!(
+            gaps_before > 0,
+            "the gapped array should have more slots than items"
+        );
+        let gap = occupancy.nth_gap(gaps_before - 1);
+        slots[gap..position].rotate_left(1);
+        slots[position - 1] = Some(item);
+        occupancy.occupy(gap);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sorting::{
+        insertion_sort::{BinaryInsertionSort, InsertionSort, LibrarySort, Occupancy},
+        instrument,
+        tests::{
+            panic_safety_test, pseudo_random_list, pseudo_random_test, sorting_test, stability_test,
+        },
+    };
+
+    #[test]
+    fn insertion_sort_test() {
+        sorting_test(InsertionSort);
+    }
+
+    #[test]
+    fn binary_insertion_sort_test() {
+        sorting_test(BinaryInsertionSort);
+        pseudo_random_test(BinaryInsertionSort);
+        stability_test(BinaryInsertionSort);
+        panic_safety_test(BinaryInsertionSort);
+    }
+
+    #[test]
+    fn binary_insertion_sort_comparisons() {
+        for len in [2, 10, 100, 1000] {
+            let bound: usize = (1..len)
+                .map(|i: usize| (i + 1).next_power_of_two().ilog2() as usize)
+                .sum();
+            for mut list in [
+                pseudo_random_list(len, 17, u64::MAX),
+                (0..len as u64).rev().collect(),
+                (0..len as u64).collect(),
+            ] {
+                assert!(instrument(&BinaryInsertionSort, &mut list).comparisons <= bound);
+            }
+        }
+    }
+
+    #[test]
+    fn library_sort_test() {
+        sorting_test(LibrarySort);
+        pseudo_random_test(LibrarySort);
+        stability_test(LibrarySort);
+        panic_safety_test(LibrarySort);
+    }
+
+    #[test]
+    fn library_sort_comparisons() {
+        let len = 4096;
+        for mut list in [
+            pseudo_random_list(len, 19, u64::MAX),
+            pseudo_random_list(len, 23, 10),
+            (0..len as u64).rev().collect(),
+            (0..len as u64).collect(),
+        ] {
+            let comparisons = instrument(&LibrarySort, &mut list).comparisons;
+            assert!(
+                comparisons <= 2 * len * len.ilog2() as usize,
+                "{} comparisons",
+                comparisons
+            );
+        }
+    }
+
+    #[test]
+    fn occupancy() {
+        for len in [0, 1, 7, 16, 100] {
+            let mut slots: Vec<Option<usize>> = pseudo_random_list(len, 29, 3)
+                .into_iter()
+                .map(|v| (v == 0).then_some(0))
+                .collect();
+            let mut occupancy = Occupancy::new(&slots);
+            for round in 0..2 {
+                let items: Vec<usize> = (0..len).filter(|&i| slots[i].is_some()).collect();
+                let gaps: Vec<usize> = (0..len).filter(|&i| slots[i].is_none()).collect();
+                for (k, &slot) in items.iter().enumerate() {
+                    assert_eq!(occupancy.nth_item(k), slot);
+                }
+                for (k, &slot) in gaps.iter().enumerate() {
+                    assert_eq!(occupancy.nth_gap(k), slot);
+                }
+                assert_eq!(occupancy.nth_gap(gaps.len()), len);
+                for slot in 0..=len {
+                    assert_eq!(
+                        occupancy.occupied_before(slot),
+                        items.iter().filter(|&&item| item < slot).count()
+                    );
+                }
+                if round == 0 {
+                    for &gap in gaps.iter().step_by(2) {
+                        slots[gap] = Some(0);
+                        occupancy.occupy(gap);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,560 @@
+use std::{
+    cell::{Cell, RefCell},
+    cmp::Ordering,
+    mem::{self, MaybeUninit},
+    ops::AddAssign,
+    ptr,
+};
+
+use crate::sorting::Sorter;
+
+/// Where an item is, while it's being sorted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Location {
+    /// At this index of the list being sorted.
+    List(usize),
+    /// In auxiliary memory, like the buffer of a merge, at this address.
+    Aux(usize),
+}
+
+/// Receives the operations a sorter runs on the list it sorts, with the locations of the items involved.
+/// Moves within auxiliary memory only are not reported.
+pub trait SortObserver {
+    /// Two items are compared.
+    fn compare(&mut self, _a: Location, _b: Location) {}
+
+    /// The items at indexes `a` and `b` of the list are swapped.
+    fn swap(&mut self, _a: usize, _b: usize) {}
+
+    /// The item at `from` is copied to `to`, where it replaces the previous item. At least one of the two locations
+    /// is in the list.
+    fn move_item(&mut self, _from: Location, _to: Location) {}
+}
+
+/// The number of operations run by a sort.
+/// Reads and writes count single item accesses to the list: a comparison reads the compared items which are in the
+/// list, and a swap reads and writes two items, besides counting as a swap.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Counts {
+    pub comparisons: usize,
+    pub swaps: usize,
+    pub reads: usize,
+    pub writes: usize,
+}
+
+impl SortObserver for Counts {
+    fn compare(&mut self, a: Location, b: Location) {
+        self.comparisons += 1;
+        self.reads +=
+            matches!(a, Location::List(_)) as usize + matches!(b, Location::List(_)) as usize;
+    }
+
+    fn swap(&mut self, _a: usize, _b: usize) {
+        self.swaps += 1;
+        self.reads += 2;
+        self.writes += 2;
+    }
+
+    fn move_item(&mut self, from: Location, to: Location) {
+        self.reads += matches!(from, Location::List(_)) as usize;
+        self.writes += matches!(to, Location::List(_)) as usize;
+    }
+}
+
+impl AddAssign for Counts {
+    fn add_assign(&mut self, other: Counts) {
+        self.comparisons += other.comparisons;
+        self.swaps += other.swaps;
+        self.reads += other.reads;
+        self.writes += other.writes;
+    }
+}
+
+/// Wraps a sorter, and counts the operations run by all the sorts it runs.
+///
+/// ```
+/// use more_rust_structures::sorting::{Instrumented, InsertionSort, Sorter};
+///
+/// let sorter = Instrumented::new(InsertionSort);
+/// sorter.sort(&mut [3, 2, 1]);
+/// assert_eq!(sorter.counts().comparisons, 3);
+/// assert_eq!(sorter.counts().swaps, 3);
+/// ```
+pub struct Instrumented<S> {
+    sorter: S,
+    counts: Cell<Counts>,
+}
+
+impl<S: Sorter> Instrumented<S> {
+    pub fn new(sorter: S) -> Self {
+        Instrumented {
+            sorter,
+            counts: Cell::new(Counts::default()),
+        }
+    }
+
+    /// Returns the operations counted since the sorter was created, or last reset.
+    pub fn counts(&self) -> Counts {
+        self.counts.get()
+    }
+
+    /// Resets the counts to 0, and returns the counts until now.
+    pub fn reset(&self) -> Counts {
+        self.counts.take()
+    }
+}
+
+impl<S: Sorter> Sorter for Instrumented<S> {
+    fn sort_by<T, F>(&self, list: &mut [T], compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let mut counts = self.counts.get();
+        self.sorter.sort_observed_by(list, compare, &mut counts);
+        self.counts.set(counts);
+    }
+
+    fn sort_observed_by<T, F>(&self, list: &mut [T], compare: F, observer: &mut dyn SortObserver)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let mut counts = self.counts.get();
+        self.sorter
+            .sort_observed_by(list, compare, &mut Tee(&mut counts, observer));
+        self.counts.set(counts);
+    }
+}
+
+/// Reports every operation to both its observers.
+struct Tee<'a>(&'a mut dyn SortObserver, &'a mut dyn SortObserver);
+
+impl SortObserver for Tee<'_> {
+    fn compare(&mut self, a: Location, b: Location) {
+        self.0.compare(a, b);
+        self.1.compare(a, b);
+    }
+
+    fn swap(&mut self, a: usize, b: usize) {
+        self.0.swap(a, b);
+        self.1.swap(a, b);
+    }
+
+    fn move_item(&mut self, from: Location, to: Location) {
+        self.0.move_item(from, to);
+        self.1.move_item(from, to);
+    }
+}
+
+/// Sorts the provided `list` in ascending order with the given `sorter`, and returns the operations it ran.
+pub fn instrument<S: Sorter, T: Ord>(sorter: &S, list: &mut [T]) -> Counts {
+    let mut counts = Counts::default();
+    observe_by(sorter, list, T::cmp, &mut counts);
+    counts
+}
+
+/// Sorts the provided `list` with the given `sorter`, according to the order defined by the `compare` function, and
+/// reports every operation run on the list to the `observer`, with `Sorter::sort_observed_by`.
+/// Swaps and moves are reported by the sorters of this crate; other sorters only report comparisons. Sorts run by
+/// `compare`, or by the observer, are not reported.
+///
+/// Only `Sorter`s can be observed this way:
+/// - The sorts by keys and bytes have their own entry points, `counting_sort_observed`,
+///   `lsd_radix_sort_by_key_observed`, `msd_radix_sort_observed`, `burst_sort_observed` and
+///   `three_way_radix_quick_sort_observed`, which report the swaps run on the list, and, for
+///   `three_way_radix_quick_sort_observed`, the comparisons of suffixes run on its small sub-lists.
+///   What they leave untraced is the rest of their work: extracting keys, reading bytes, comparing them with a pivot
+///   byte, and sorting their own arrays of indexes. `counting_sort`, `lsd_radix_sort`, `msd_radix_sort` and
+///   `burst_sort` do all of their sorting on such an array, and only then rearrange the list with swaps, so their
+///   traces only show that last step.
+/// - `ParallelMergeSort` and `ParallelQuickSort` need `Send` items and a `Sync` comparison, which `Sorter` doesn't
+///   require, and run their operations on several threads at once, which a single `&mut` observer can't receive
+///   without locking on every operation. They can't be observed.
+pub fn observe_by<S, T, F, O>(sorter: &S, list: &mut [T], compare: F, observer: &mut O)
+where
+    S: Sorter,
+    F: FnMut(&T, &T) -> Ordering,
+    O: SortObserver,
+{
+    sorter.sort_observed_by(list, compare, observer);
+}
+
+/// Receives the swaps and moves run by the sorts of this module. Sorts are generic over their probe, so that
+/// `NoProbe`, which `Sorter::sort_by` uses, compiles away, and only observed sorts pay for reporting.
+pub(crate) trait Probe {
+    /// The items pointed to by `a` and `b` are about to be compared, by a comparison which the sort runs itself,
+    /// rather than through a `compare` function wrapped by `ObserverProbe::comparing`.
+    fn compared<T>(&self, a: *const T, b: *const T);
+
+    /// The items at indexes `a` and `b` of the list were swapped.
+    fn swapped<T>(&self, list: &[T], a: usize, b: usize);
+
+    /// `count` items were copied from `src` to `dst`. Only the addresses are used.
+    fn moved<T>(&self, src: *const T, dst: *const T, count: usize);
+}
+
+/// The probe of the sorts which nothing observes, which reports nothing.
+pub(crate) struct NoProbe;
+
+impl Probe for NoProbe {
+    #[inline(always)]
+    fn compared<T>(&self, _a: *const T, _b: *const T) {}
+
+    #[inline(always)]
+    fn swapped<T>(&self, _list: &[T], _a: usize, _b: usize) {}
+
+    #[inline(always)]
+    fn moved<T>(&self, _src: *const T, _dst: *const T, _count: usize) {}
+}
+
+/// The probe of a sort under observation, which locates the items involved in each operation from their addresses,
+/// and reports it to the observer.
+pub(crate) struct ObserverProbe<'a> {
+    start: usize,
+    end: usize,
+    size: usize,
+    observer: RefCell<&'a mut dyn SortObserver>,
+}
+
+impl<'a> ObserverProbe<'a> {
+    /// Creates a probe reporting the operations run on `list` to the `observer`.
+    pub(crate) fn new<T>(list: &[T], observer: &'a mut dyn SortObserver) -> Self {
+        let range = list.as_ptr_range();
+        ObserverProbe {
+            start: range.start as usize,
+            end: range.end as usize,
+            size: mem::size_of::<T>(),
+            observer: RefCell::new(observer),
+        }
+    }
+
+    /// Wraps `compare`, so that it reports each comparison before running it.
+    pub(crate) fn comparing<'p, T, F>(
+        &'p self,
+        mut compare: F,
+    ) -> impl FnMut(&T, &T) -> Ordering + use<'p, 'a, T, F>
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        move |a, b| {
+            self.compared(a, b);
+            compare(a, b)
+        }
+    }
+
+    /// Returns the location of the item pointed to by `item`, which is auxiliary memory when not in the list under
+    /// observation.
+    fn locate<T>(&self, item: *const T) -> Location {
+        let address = item as usize;
+        if self.size == 0 || address < self.start || address >= self.end {
+            return Location::Aux(address);
+        }
+        Location::List((address - self.start) / self.size)
+    }
+}
+
+impl Probe for ObserverProbe<'_> {
+    fn compared<T>(&self, a: *const T, b: *const T) {
+        self.observer
+            .borrow_mut()
+            .compare(self.locate(a), self.locate(b));
+    }
+
+    fn swapped<T>(&self, list: &[T], a: usize, b: usize) {
+        let list = list.as_ptr();
+        if let (Location::List(a), Location::List(b)) = (
+            self.locate(list.wrapping_add(a)),
+            self.locate(list.wrapping_add(b)),
+        ) {
+            self.observer.borrow_mut().swap(a, b);
+        }
+    }
+
+    /// Reports the items one at a time, in an order which replays correctly when the two ranges overlap.
+    fn moved<T>(&self, src: *const T, dst: *const T, count: usize) {
+        let mut observer = self.observer.borrow_mut();
+        let mut report = |i: usize| {
+            let (from, to) = (
+                self.locate(src.wrapping_add(i)),
+                self.locate(dst.wrapping_add(i)),
+            );
+            if matches!(from, Location::List(_)) || matches!(to, Location::List(_)) {
+                observer.move_item(from, to);
+            }
+        };
+        if dst > src {
+            (0..count).rev().for_each(&mut report);
+        } else {
+            (0..count).for_each(&mut report);
+        }
+    }
+}
+
+/// Swaps the items at indexes `a` and `b` of the list, like `slice::swap`, and reports it to the probe.
+#[inline]
+pub(crate) fn swap<T, P: Probe>(list: &mut [T], a: usize, b: usize, probe: &P) {
+    list.swap(a, b);
+    probe.swapped(list, a, b);
+}
+
+/// Reverses the order of the items of the list, like `slice::reverse`, and reports the swaps it runs to the probe.
+pub(crate) fn reverse<T, P: Probe>(list: &mut [T], probe: &P) {
+    let len = list.len();
+    for i in 0..len / 2 {
+        swap(list, i, len - 1 - i, probe);
+    }
+}
+
+/// Rotates the list in place by one position to the right, like `slice::rotate_right(1)`, and reports it as the moves
+/// it amounts to: the last item to a temporary, the others one position to the right, and the temporary to the front.
+/// The moves are reported once the list is rotated, so an observer which panics can't leave it half rotated.
+pub(crate) fn rotate_right_by_one<T, P: Probe>(list: &mut [T], probe: &P) {
+    let Some(last) = list.len().checked_sub(1) else {
+        return;
+    };
+    list.rotate_right(1);
+    let temporary = MaybeUninit::<T>::uninit();
+    let list = list.as_ptr();
+    probe.moved(list.wrapping_add(last), temporary.as_ptr(), 1);
+    probe.moved(list, list.wrapping_add(1), last);
+    probe.moved(temporary.as_ptr(), list, 1);
+}
+
+/// Copies `count` items from `src` to `dst`, like `ptr::copy_nonoverlapping`, and reports it to the probe.
+/// The report runs after the copy, so callers must keep their items owned exactly once even if it panics.
+///
+/// # Safety
+///
+/// Same as `ptr::copy_nonoverlapping`.
+#[inline]
+pub(crate) unsafe fn copy_nonoverlapping<T, P: Probe>(
+    src: *const T,
+    dst: *mut T,
+    count: usize,
+    probe: &P,
+) {
+    ptr::copy_nonoverlapping(src, dst, count);
+    probe.moved(src, dst, count);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        panic::{self, AssertUnwindSafe},
+        rc::Rc,
+    };
+
+    use crate::sorting::{
+        disorder::is_sorted,
+        instrument::{instrument, observe_by, Counts, Instrumented, Location, SortObserver},
+        tests::pseudo_random_list,
+        BinaryInsertionSort, BottomUpMergeSort, DualPivotQuickSort, HeapSort, InsertionSort,
+        IntroSort, MedianOfThreeQuickSort, MergeSort, PdqSort, RandomizedQuickSort, SelectionSort,
+        ShellSort, Sorter, ThreeWayQuickSort, TimSort,
+    };
+
+    /// Records every swap, to replay them on a copy of the original list, and counts moves.
+    #[derive(Default)]
+    struct Recorder {
+        swaps: Vec<(usize, usize)>,
+        moves: usize,
+    }
+
+    impl SortObserver for Recorder {
+        fn swap(&mut self, a: usize, b: usize) {
+            self.swaps.push((a, b));
+        }
+
+        fn move_item(&mut self, _from: Location, _to: Location) {
+            self.moves += 1;
+        }
+    }
+
+    /// Checks that sorting through swaps only, all of them reported, can be replayed on the original list.
+    fn replay_test<S: Sorter>(sorter: S) {
+        for len in [0, 1, 2, 10, 100, 1000] {
+            let original = pseudo_random_list(len, len as u64 + 1, 100);
+            let mut list = original.clone();
+            let mut recorder = Recorder::default();
+            observe_by(&sorter, &mut list, u64::cmp, &mut recorder);
+            assert_eq!(recorder.moves, 0);
+            let mut replayed = original.clone();
+            for (a, b) in recorder.swaps {
+                replayed.swap(a, b);
+            }
+            assert_eq!(replayed, list);
+        }
+    }
+
+    #[test]
+    fn swap_sorters_replay() {
+        replay_test(SelectionSort);
+        replay_test(InsertionSort);
+        replay_test(ShellSort::default());
+        replay_test(HeapSort);
+        replay_test(RandomizedQuickSort);
+        replay_test(MedianOfThreeQuickSort);
+        replay_test(ThreeWayQuickSort);
+        replay_test(DualPivotQuickSort);
+        replay_test(IntroSort);
+        replay_test(PdqSort);
+    }
+
+    #[test]
+    fn quadratic_sorts() {
+        let n = 200;
+        let counts = instrument(&SelectionSort, &mut pseudo_random_list(n, 1, u64::MAX));
+        assert_eq!(counts.comparisons, n * (n - 1) / 2);
+
+        let counts = instrument(&InsertionSort, &mut (0..n).rev().collect::<Vec<usize>>());
+        assert_eq!(
+            counts,
+            Counts {
+                comparisons: n * (n - 1) / 2,
+                swaps: n * (n - 1) / 2,
+                reads: 2 * n * (n - 1),
+                writes: n * (n - 1),
+            }
+        );
+
+        let counts = instrument(&InsertionSort, &mut (0..n).collect::<Vec<usize>>());
+        assert_eq!(counts.comparisons, n - 1);
+        assert_eq!(counts.swaps, 0);
+    }
+
+    #[test]
+    fn linearithmic_sorts() {
+        let n = 4096;
+        let log_n = 12;
+        let list = pseudo_random_list(n, 7, u64::MAX);
+
+        for counts in [
+            instrument(&MergeSort, &mut list.clone()),
+            instrument(&BottomUpMergeSort, &mut list.clone()),
+        ] {
+            assert!(counts.comparisons <= n * log_n, "{:?}", counts);
+            assert!(counts.writes <= 2 * n * log_n, "{:?}", counts);
+        }
+
+        let counts = instrument(&TimSort, &mut list.clone());
+        assert!(counts.comparisons <= n * log_n + n, "{:?}", counts);
+        assert!(counts.writes <= 2 * n * log_n, "{:?}", counts);
+
+        let counts = instrument(&HeapSort, &mut list.clone());
+        assert!(counts.comparisons <= 2 * n * log_n, "{:?}", counts);
+        assert!(counts.swaps <= n * log_n + n, "{:?}", counts);
+
+        for counts in [
+            instrument(&IntroSort, &mut list.clone()),
+            instrument(&PdqSort, &mut list.clone()),
+        ] {
+            assert!(counts.comparisons <= 2 * n * log_n, "{:?}", counts);
+            assert!(counts.swaps <= n * log_n, "{:?}", counts);
+        }
+    }
+
+    #[test]
+    fn merges_report_list_writes_only() {
+        let mut list: Vec<u64> = (0..64).rev().collect();
+        let counts = instrument(&MergeSort, &mut list);
+        assert!(is_sorted(&list));
+        assert!(counts.writes > 0);
+        assert!(counts.reads >= counts.writes);
+
+        let mut sorted: Vec<u64> = (0..64).collect();
+        let counts = instrument(&MergeSort, &mut sorted);
+        assert_eq!(counts.writes, 0);
+    }
+
+    #[test]
+    fn instrumented_accumulates() {
+        let sorter = Instrumented::new(InsertionSort);
+        sorter.sort(&mut [2, 1]);
+        sorter.sort_by_key(&mut ["bb", "a"], |s| s.len());
+        assert_eq!(sorter.counts().comparisons, 2);
+        assert_eq!(sorter.reset().swaps, 2);
+        assert_eq!(sorter.counts(), Counts::default());
+    }
+
+    /// Checks that the sorter leaves every item owned exactly once when the observer panics on any of its moves.
+    fn panicking_observer_test<S: Sorter>(sorter: S, values: &[usize]) {
+        /// Panics on the move after the given number of moves.
+        struct PanicOnMove(usize);
+
+        impl SortObserver for PanicOnMove {
+            fn move_item(&mut self, _from: Location, _to: Location) {
+                if self.0 == 0 {
+                    panic!("observer panicked");
+                }
+                self.0 -= 1;
+            }
+        }
+
+        let tracker = Rc::new(());
+        let mut sorted = values.to_vec();
+        sorted.sort();
+        // Every move of the sort, up to the last one, is tried in turn.
+        for moves in 0.. {
+            let mut list: Vec<(usize, Rc<()>)> = values
+                .iter()
+                .map(|&value| (value, Rc::clone(&tracker)))
+                .collect();
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                observe_by(
+                    &sorter,
+                    &mut list,
+                    |a, b| a.0.cmp(&b.0),
+                    &mut PanicOnMove(moves),
+                )
+            }));
+            let is_done = result.is_ok();
+            let mut after: Vec<usize> = list.iter().map(|item| item.0).collect();
+            after.sort();
+            assert_eq!(after, sorted);
+            drop(list);
+            assert_eq!(Rc::strong_count(&tracker), 1);
+            if is_done {
+                break;
+            }
+        }
+    }
+
+    #[test]
+    fn observers_panicking_within_a_rotation() {
+        panicking_observer_test(BinaryInsertionSort, &[4, 3, 2, 1, 0]);
+    }
+
+    #[test]
+    fn observers_panicking_within_a_merge() {
+        let values: Vec<usize> = pseudo_random_list(40, 31, 100)
+            .into_iter()
+            .map(|value| value as usize)
+            .collect();
+        panicking_observer_test(MergeSort, &values);
+        // Two runs, such that TimSort gallops over most of the right one, moving it in bulk onto its own first items.
+        let values: Vec<usize> = (0..60)
+            .chain(61..71)
+            .chain([1000, 1001, 60])
+            .chain(71..251)
+            .collect();
+        panicking_observer_test(TimSort, &values);
+    }
+
+    #[test]
+    fn nested_sorts_are_not_reported() {
+        let mut counts = Counts::default();
+        let mut list = vec![3, 1, 2];
+        observe_by(
+            &InsertionSort,
+            &mut list,
+            |a, b| {
+                InsertionSort.sort(&mut [4, 3, 2, 1]);
+                a.cmp(b)
+            },
+            &mut counts,
+        );
+        assert_eq!(list, vec![1, 2, 3]);
+        assert_eq!(counts.comparisons, 3);
+        assert_eq!(counts.swaps, 2);
+        assert_eq!(instrument(&InsertionSort, &mut [2, 1]).swaps, 1);
+    }
+}
@@ -0,0 +1,101 @@
+use std::cmp::Ordering;
+
+use crate::sorting::{
+    heap_sort::heap_sort,
+    insertion_sort::insertion_sort,
+    instrument::{swap, NoProbe, ObserverProbe, Probe},
+    quick_sort::{median_of_three, partition},
+    SortObserver, Sorter,
+};
+
+/// Sub-lists up to this length are sorted with `InsertionSort`.
+const INSERTION_SORT_CUTOFF: usize = 16;
+
+///     ## Algorithm
+///     - This sorting algorithm, by David Musser, starts as `MedianOfThreeQuickSort` on the list L.
+///     - It keeps track of the recursion depth, and when it exceeds `2 * log2(n)`, which only happens when the chosen
+///       pivots keep producing unbalanced partitions, the current sub-list is sorted with `HeapSort` instead.
+///     - Sub-lists of at most `INSERTION_SORT_CUTOFF` items are left to `InsertionSort`.
+///     ## Complexity
+///     - Quicksort is abandoned before it can become quadratic, and heapsort is `O(n log n)` in the worst case.
+///     - Therefore, Time Complexity is `O(n log n)`, also in the worst case, and Space Complexity is `O(log n)`, for the
+///       recursion. The algorithm never allocates.
+pub struct IntroSort;
+
+impl Sorter for IntroSort {
+    fn sort_by<T, F>(&self, list: &mut [T], mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let depth_limit = 2 * list.len().max(1).ilog2();
+        intro_sort(list, depth_limit, &mut compare, &NoProbe);
+    }
+
+    fn sort_observed_by<T, F>(&self, list: &mut [T], compare: F, observer: &mut dyn SortObserver)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let probe = ObserverProbe::new(list, observer);
+        let depth_limit = 2 * list.len().max(1).ilog2();
+        intro_sort(list, depth_limit, &mut probe.comparing(compare), &probe);
+    }
+}
+
+fn intro_sort<T, F, P>(mut list: &mut [T], mut depth_limit: u32, compare: &mut F, probe: &P)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    P: Probe,
+{
+    while list.len() > INSERTION_SORT_CUTOFF {
+        if depth_limit == 0 {
+            heap_sort(list, compare, probe);
+            return;
+        }
+        depth_limit -= 1;
+        let len = list.len();
+        let median = median_of_three(list, len / 4, len / 2, 3 * len / 4, compare);
+        swap(list, 0, median, probe);
+        let pivot = partition(list, compare, probe);
+        let (left, right) = list.split_at_mut(pivot);
+        let right = &mut right[1..];
+        if left.len() < right.len() {
+            intro_sort(left, depth_limit, compare, probe);
+            list = right;
+        } else {
+            intro_sort(right, depth_limit, compare, probe);
+            list = left;
+        }
+    }
+    insertion_sort(list, compare, probe);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use crate::sorting::{
+        intro_sort::IntroSort,
+        tests::{adversarial_test, pseudo_random_test, sorting_test},
+        Sorter,
+    };
+
+    #[test]
+    fn intro_sort_test() {
+        sorting_test(IntroSort);
+        pseudo_random_test(IntroSort);
+        adversarial_test(IntroSort);
+    }
+
+    #[test]
+    fn falls_back_to_heap_sort() {
+        // A comparator claiming every item is bigger than the pivot makes each partition as unbalanced as possible.
+        let len: usize = 1000;
+        let mut list: Vec<usize> = (0..len).collect();
+        let mut comparisons = 0;
+        IntroSort.sort_by(&mut list, |_, _| {
+            comparisons += 1;
+            Ordering::Less
+        });
+        assert!(comparisons < len * len / 4);
+    }
+}
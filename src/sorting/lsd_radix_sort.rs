@@ -0,0 +1,219 @@
+use crate::sorting::{
+    instrument::{NoProbe, ObserverProbe, SortObserver},
+    permutation::apply_permutation_unchecked,
+};
+
+/// Keys which can be sorted one byte at a time, by mapping them to unsigned integers with the same order.
+pub trait RadixKey: Copy {
+    /// The number of significant bytes of the key.
+    const BYTES: usize;
+
+    /// Maps the key to an unsigned integer, such that the order of the integers matches the order of the keys.
+    fn to_radix(self) -> u64;
+}
+
+macro_rules! unsigned_radix_key {
+    ($($t:ty),*) => {$(
+        impl RadixKey for $t {
+            const BYTES: usize = std::mem::size_of::<$t>();
+
+            fn to_radix(self) -> u64 {
+                self as u64
+            }
+        }
+    )*};
+}
+
+macro_rules! signed_radix_key {
+    ($($t:ty => $u:ty),*) => {$(
+        impl RadixKey for $t {
+            const BYTES: usize = std::mem::size_of::<$t>();
+
+            /// Flips the sign bit, so that negative values come before positive ones.
+            fn to_radix(self) -> u64 {
+                (self as $u ^ (1 << (<$u>::BITS - 1))) as u64
+            }
+        }
+    )*};
+}
+
+unsigned_radix_key!(u8, u16, u32, u64, usize);
+signed_radix_key!(i8 => u8, i16 => u16, i32 => u32, i64 => u64, isize => usize);
+
+impl RadixKey for f32 {
+    const BYTES: usize = 4;
+
+    /// Follows the order of `f32::total_cmp`: negative NaNs first, then negative infinity, negative numbers, `-0.0`,
+    /// `+0.0`, positive numbers, positive infinity and positive NaNs last.
+    fn to_radix(self) -> u64 {
+        let bits = self.to_bits();
+        let mask = if bits >> 31 == 1 { u32::MAX } else { 1 << 31 };
+        (bits ^ mask) as u64
+    }
+}
+
+impl RadixKey for f64 {
+    const BYTES: usize = 8;
+
+    /// Follows the order of `f64::total_cmp`: negative NaNs first, then negative infinity, negative numbers, `-0.0`,
+    /// `+0.0`, positive numbers, positive infinity and positive NaNs last.
+    fn to_radix(self) -> u64 {
+        let bits = self.to_bits();
+        let mask = if bits >> 63 == 1 { u64::MAX } else { 1 << 63 };
+        bits ^ mask
+    }
+}
+
+/// Sorts the list of integers or floats in ascending order with `lsd_radix_sort_by_key`.
+pub fn lsd_radix_sort<K: RadixKey>(list: &mut [K]) {
+    lsd_radix_sort_by_key(list, |&item| item);
+}
+
+///     ## Algorithm
+///     - This sorting algorithm sorts the list L by fixed-width keys, extracted from each item by the `key` function,
+///       one byte at a time, from the least significant byte to the most significant one.
+///     - Each pass is a stable counting sort on a single byte, over 256 possible values. Since every pass preserves the
+///       order established by the previous ones, after the last pass L is sorted by the whole key.
+///     - Signed integers and floats are mapped to unsigned integers with the same order, as defined by `RadixKey`.
+///     - Passes where every key has the same byte are skipped. Items are only moved once, at the end, following the
+///       permutation computed by the passes.
+///     ## Complexity
+///     - Each of the `K::BYTES` passes is linear in n.
+///     - Therefore, Time Complexity is `O(n * K::BYTES)` and Space Complexity is `O(n)`.
+pub fn lsd_radix_sort_by_key<T, K, F>(list: &mut [T], key: F)
+where
+    K: RadixKey,
+    F: FnMut(&T) -> K,
+{
+    let order = lsd_radix_argsort(list, key);
+    apply_permutation_unchecked(list, &order, &NoProbe);
+}
+
+/// Sorts the list like `lsd_radix_sort_by_key`, and reports the swaps which rearrange it to the `observer`. See
+/// `observe_by`.
+pub fn lsd_radix_sort_by_key_observed<T, K, F>(
+    list: &mut [T],
+    key: F,
+    observer: &mut dyn SortObserver,
+) where
+    K: RadixKey,
+    F: FnMut(&T) -> K,
+{
+    let order = lsd_radix_argsort(list, key);
+    apply_permutation_unchecked(list, &order, &ObserverProbe::new(list, observer));
+}
+
+/// Returns the permutation which stably sorts the list by the keys, computed by the passes of
+/// `lsd_radix_sort_by_key`.
+fn lsd_radix_argsort<T, K, F>(list: &[T], mut key: F) -> Vec<usize>
+where
+    K: RadixKey,
+    F: FnMut(&T) -> K,
+{
+    let keys: Vec<u64> = list.iter().map(|item| key(item).to_radix()).collect();
+    let mut order: Vec<usize> = (0..list.len()).collect();
+    let mut aux = vec![0; list.len()];
+    for byte in 0..K::BYTES {
+        let shift = 8 * byte;
+        let mut starts = [0; 257];
+        for &k in &keys {
+            starts[((k >> shift) & 0xff) as usize + 1] += 1;
+        }
+        if starts.contains(&list.len()) {
+            continue;
+        }
+        for r in 0..256 {
+            starts[r + 1] += starts[r];
+        }
+        for &index in &order {
+            let r = ((keys[index] >> shift) & 0xff) as usize;
+            aux[starts[r]] = index;
+            starts[r] += 1;
+        }
+        std::mem::swap(&mut order, &mut aux);
+    }
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sorting::{
+        lsd_radix_sort::{lsd_radix_sort, lsd_radix_sort_by_key},
+        tests::{pseudo_random_list, stability_test_with},
+        InsertionSort, Sorter,
+    };
+
+    #[test]
+    fn unsigned() {
+        let mut list = pseudo_random_list(1000, 5, u64::MAX);
+        let mut expected = list.clone();
+        InsertionSort.sort(&mut expected);
+        lsd_radix_sort(&mut list);
+        assert_eq!(list, expected);
+
+        let mut bytes = vec![255u8, 0, 7, 7, 128];
+        lsd_radix_sort(&mut bytes);
+        assert_eq!(bytes, vec![0, 7, 7, 128, 255]);
+
+        let mut empty: Vec<u32> = vec![];
+        lsd_radix_sort(&mut empty);
+        assert_eq!(empty, vec![]);
+    }
+
+    #[test]
+    fn signed() {
+        let mut list: Vec<i64> = pseudo_random_list(1000, 6, u64::MAX)
+            .into_iter()
+            .map(|v| v as i64)
+            .collect();
+        list.extend([i64::MIN, i64::MAX, 0, -1, 1]);
+        let mut expected = list.clone();
+        InsertionSort.sort(&mut expected);
+        lsd_radix_sort(&mut list);
+        assert_eq!(list, expected);
+
+        let mut small = vec![3i8, -128, 127, -1, 0];
+        lsd_radix_sort(&mut small);
+        assert_eq!(small, vec![-128, -1, 0, 3, 127]);
+    }
+
+    #[test]
+    fn floats() {
+        let mut list: Vec<f64> = pseudo_random_list(1000, 7, 2_000_000)
+            .into_iter()
+            .map(|v| (v as f64 - 1_000_000.0) / 3.0)
+            .collect();
+        list.extend([
+            f64::NAN,
+            -f64::NAN,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+            0.0,
+            -0.0,
+            f64::MIN_POSITIVE,
+            -f64::MIN_POSITIVE,
+        ]);
+        let mut expected = list.clone();
+        InsertionSort.sort_by(&mut expected, f64::total_cmp);
+        lsd_radix_sort(&mut list);
+        let bits = |list: &[f64]| list.iter().map(|v| v.to_bits()).collect::<Vec<u64>>();
+        assert_eq!(bits(&list), bits(&expected));
+        assert!(list[0].is_nan() && list[0].is_sign_negative());
+        assert!(list[list.len() - 1].is_nan() && list[list.len() - 1].is_sign_positive());
+
+        let mut singles = vec![1.5f32, -2.0, f32::NAN, -0.0, 0.0];
+        lsd_radix_sort(&mut singles);
+        assert_eq!(&singles[..4], &[-2.0, -0.0, 0.0, 1.5]);
+        assert!(
+            singles[0].is_sign_negative()
+                && singles[1].is_sign_negative()
+                && singles[2].is_sign_positive()
+        );
+        assert!(singles[4].is_nan());
+    }
+
+    #[test]
+    fn stability() {
+        stability_test_with(|records| lsd_radix_sort_by_key(records, |record| record.0));
+    }
+}
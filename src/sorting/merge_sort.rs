@@ -0,0 +1,213 @@
+use std::{cmp::Ordering, ptr, thread};
+
+use crate::sorting::{
+    insertion_sort::insertion_sort,
+    instrument::{copy_nonoverlapping, NoProbe, ObserverProbe, Probe},
+    SortObserver, Sorter,
+};
+
+/// Sub-lists up to this length are sorted with `InsertionSort`, which is faster than merging on small inputs.
+const INSERTION_SORT_CUTOFF: usize = 8;
+
+///     ## Algorithm
+///     - This sorting algorithm recursively splits the list L in two halves, `L[..mid]` and `L[mid..]`, sorts each of
+///       them and then merges the two sorted halves into a single sorted list.
+///     - Halves of at most `INSERTION_SORT_CUTOFF` items are sorted with `InsertionSort` instead of being split further.
+///     - When the last item of `L[..mid]` is not bigger than the first item of `L[mid..]`, the two halves are already in
+///       order and the merge is skipped, which makes the algorithm linear on already sorted lists.
+///     - The merge copies `L[..mid]` into an auxiliary buffer, allocated once for the whole sort, and then repeatedly
+///       moves the smaller of the two heads into place. Ties are resolved in favour of the left half, so the sort is
+///       stable.
+///     ## Complexity
+///     - The recursion is `log2(n)` levels deep and each level runs at most n comparisons.
+///     - Therefore, Time Complexity is `O(n log n)` and Space Complexity is `O(n)`, for the auxiliary buffer.
+pub struct MergeSort;
+
+impl Sorter for MergeSort {
+    fn sort_by<T, F>(&self, list: &mut [T], mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let mut buffer = Vec::with_capacity(list.len() / 2);
+        top_down_sort(list, &mut buffer, &mut compare, &NoProbe);
+    }
+
+    fn sort_observed_by<T, F>(&self, list: &mut [T], compare: F, observer: &mut dyn SortObserver)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let probe = ObserverProbe::new(list, observer);
+        let mut buffer = Vec::with_capacity(list.len() / 2);
+        top_down_sort(list, &mut buffer, &mut probe.comparing(compare), &probe);
+    }
+}
+
+fn top_down_sort<T, F, P>(list: &mut [T], buffer: &mut Vec<T>, compare: &mut F, probe: &P)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    P: Probe,
+{
+    if list.len() <= INSERTION_SORT_CUTOFF {
+        insertion_sort(list, compare, probe);
+        return;
+    }
+    let mid = list.len() / 2;
+    top_down_sort(&mut list[..mid], buffer, compare, probe);
+    top_down_sort(&mut list[mid..], buffer, compare, probe);
+    merge(list, mid, buffer, compare, probe);
+}
+
+///     ## Algorithm
+///     - This sorting algorithm merges adjacent sorted sub-lists of width 1 into sorted sub-lists of width 2, then
+///       those into sub-lists of width 4, and so on, until a single sorted sub-list covers the entire list.
+///     - The last sub-list of each pass may be shorter than the others, when n is not a power of two.
+///     - It uses the same stable merge as `MergeSort`, sharing a single auxiliary buffer across all passes, but it
+///       doesn't need recursion.
+///     ## Complexity
+///     - There are `log2(n)` passes, each running at most n comparisons.
+///     - Therefore, Time Complexity is `O(n log n)` and Space Complexity is `O(n)`, for the auxiliary buffer.
+pub struct BottomUpMergeSort;
+
+impl Sorter for BottomUpMergeSort {
+    fn sort_by<T, F>(&self, list: &mut [T], mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        bottom_up_sort(list, &mut compare, &NoProbe);
+    }
+
+    fn sort_observed_by<T, F>(&self, list: &mut [T], compare: F, observer: &mut dyn SortObserver)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let probe = ObserverProbe::new(list, observer);
+        bottom_up_sort(list, &mut probe.comparing(compare), &probe);
+    }
+}
+
+/// Sorts the `list` with `BottomUpMergeSort`, and reports its moves to the `probe`.
+fn bottom_up_sort<T, F, P>(list: &mut [T], compare: &mut F, probe: &P)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    P: Probe,
+{
+    let len = list.len();
+    let mut buffer = Vec::with_capacity(len);
+    let mut width = 1;
+    while width < len {
+        let mut lo = 0;
+        while lo + width < len {
+            let hi = std::cmp::min(lo + 2 * width, len);
+            merge(&mut list[lo..hi], width, &mut buffer, compare, probe);
+            lo += 2 * width;
+        }
+        width *= 2;
+    }
+}
+
+/// Merges the sorted sub-lists `list[..mid]` and `list[mid..]` into a single sorted list, preserving the relative order
+/// of equal items.
+/// `buffer` must be empty, and is only used as raw storage for the items of `list[..mid]`. The moves are reported to the
+/// `probe`.
+pub(crate) fn merge<T, F, P>(
+    list: &mut [T],
+    mid: usize,
+    buffer: &mut Vec<T>,
+    compare: &mut F,
+    probe: &P,
+) where
+    F: FnMut(&T, &T) -> Ordering,
+    P: Probe,
+{
+    let len = list.len();
+    if mid == 0 || mid == len || compare(&list[mid], &list[mid - 1]) != Ordering::Less {
+        return;
+    }
+    buffer.reserve(mid);
+    let list = list.as_mut_ptr();
+    // SAFETY: `buffer` has room for `mid` items, and its length stays 0, so it never drops the copies it holds.
+    // From here on every item is owned either by `hole` or by `list[right..]`, and `hole` moves its remaining items
+    // back into the gap they belong to when dropped, even when `compare` panics.
+    unsafe {
+        copy_nonoverlapping(list, buffer.as_mut_ptr(), mid, probe);
+        let mut hole = MergeHole {
+            start: buffer.as_mut_ptr(),
+            end: buffer.as_mut_ptr().add(mid),
+            dest: list,
+            probe,
+        };
+        let mut right = list.add(mid);
+        let right_end = list.add(len);
+        while hole.start < hole.end && right < right_end {
+            if compare(&*right, &*hole.start) == Ordering::Less {
+                copy_nonoverlapping(right, hole.dest, 1, probe);
+                right = right.add(1);
+            } else {
+                copy_nonoverlapping(hole.start, hole.dest, 1, probe);
+                hole.start = hole.start.add(1);
+            }
+            hole.dest = hole.dest.add(1);
+        }
+    }
+}
+
+/// The items of the left sub-list which haven't been merged yet, and the position they have to be written back to.
+pub(crate) struct MergeHole<'a, T, P: Probe> {
+    pub(crate) start: *mut T,
+    pub(crate) end: *mut T,
+    pub(crate) dest: *mut T,
+    pub(crate) probe: &'a P,
+}
+
+impl<T, P: Probe> Drop for MergeHole<'_, T, P> {
+    fn drop(&mut self) {
+        // SAFETY: `start..end` and `dest..` never overlap, and the gap starting at `dest` is exactly as long as the
+        // number of items left in `start..end`.
+        let remaining = unsafe { self.end.offset_from(self.start) as usize };
+        unsafe { ptr::copy_nonoverlapping(self.start, self.dest, remaining) };
+        // The moves are not reported when unwinding from a panic, which a panicking observer would turn into an abort.
+        if !thread::panicking() {
+            self.probe.moved(self.start, self.dest, remaining);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sorting::{
+        merge_sort::{BottomUpMergeSort, MergeSort},
+        tests::{panic_safety_test, pseudo_random_test, sorting_test, stability_test},
+    };
+
+    #[test]
+    fn merge_sort_test() {
+        sorting_test(MergeSort);
+        pseudo_random_test(MergeSort);
+    }
+
+    #[test]
+    fn merge_sort_stability() {
+        stability_test(MergeSort);
+    }
+
+    #[test]
+    fn merge_sort_panic_safety() {
+        panic_safety_test(MergeSort);
+    }
+
+    #[test]
+    fn bottom_up_merge_sort_test() {
+        sorting_test(BottomUpMergeSort);
+        pseudo_random_test(BottomUpMergeSort);
+    }
+
+    #[test]
+    fn bottom_up_merge_sort_stability() {
+        stability_test(BottomUpMergeSort);
+    }
+
+    #[test]
+    fn bottom_up_merge_sort_panic_safety() {
+        panic_safety_test(BottomUpMergeSort);
+    }
+}
@@ -1,111 +1,394 @@
-///     ## Advantages and Disadvantages
-///     - The algorithm performs sorting in place and is online.
-///     - It is not stable in its basic form and requires additional space or specific assumptions on the type of list
-///       being sorted (such as it being a linked list).
-///     - Compared to other quadratic comparison-based algorithms, such as <see cref="InsertionSort"/>, it is generally
-///       simpler but requires in average an higher number of comparisons, therefore yielding worse performance.
-///     - Compared to linearithmic comparison-based algorithms, such as <see cref="HeapSort"/>, it is way simpler to
-///       understand and predict in exact number of operations executed. However, the performance is sensibly worse.
-///     - Compared to non-comparison-based algorithms, such as counting sort, it doesn't require any assumption on the
-///       type or values of the items in the input, the only requirement being their total comparability and the
-///       comparison behaving according to total order operators rules.
-///     ## Algorithm
-///     - This sorting algorithm split the list L being sorted in two parts: the sorted part, located at the beginning
-///       of the list (`L[..i]`), and the unsorted part, located at the end of the list (`L[i..]`).
-///     - At the beginning the sorted part is empty (i.e. length 0) and the unsorted part covers the entire list (i.e.
-///       length n).
-///     - The algorithm runs n iterations, where n is the number of items in the list.
-///     - At the beginning of iteration `i`, the sorted sub-list is `L[..i]` and the unsorted sub-list is `L[i..]`.
-///     - The unsorted sub-list `L[i..]` is scanned linearly, looking for the index `j`, between `i` and `n - 1`, of the item
-///       of `L[i..]` with minimum value.
-///     - `L[i]` is swapped with `L[j]` and the iteration `i` terminates.
-///     - Now `L[..(i + 1)]` is the new sorted sub-list, and `L[(i + 1)..]` is the new unsorted sub-list.
-///     ## Complexity
-///     - Each of the n iterations runs `n - i - 1` comparisons, to identify the index of the item with the minimum value
-///       in the sub-list `L[i..]`.
-///     - The total number of comparisons, over the n iterations, is around `n * n / 2`.
-///     - Therefore, Time Complexity is `O(n^2)` and Space Complexity is `O(1)`, since the algorithm runs in place.
-fn selection_sort<T: Ord>(list: &mut Vec<T>) {
-    for i in 0..list.len() {
-        for j in i + 1..list.len() {
-            if list[j] < list[i] {
-                list.swap(i, j)
-            }
-        }
+use std::cmp::Ordering;
+
+use instrument::ObserverProbe;
+
+pub mod burst_sort;
+pub mod counting_sort;
+pub mod disorder;
+pub mod external_sort;
+pub mod heap_sort;
+pub mod insertion_sort;
+pub mod instrument;
+pub mod intro_sort;
+pub mod lsd_radix_sort;
+pub mod merge_sort;
+pub mod msd_radix_sort;
+pub mod multi_key;
+#[cfg(feature = "parallel")]
+pub mod parallel_sort;
+pub mod pdq_sort;
+pub mod permutation;
+pub mod quick_sort;
+pub mod random;
+pub mod search;
+pub mod select;
+pub mod selection_sort;
+pub mod shell_sort;
+pub mod smart_sort;
+pub mod sorted_iter;
+pub mod sorting_network;
+pub mod three_way_radix_quick_sort;
+pub mod tim_sort;
+pub mod trace;
+pub mod visualize;
+
+pub use burst_sort::{burst_sort, burst_sort_observed};
+pub use counting_sort::{counting_argsort, counting_sort, counting_sort_observed};
+pub use disorder::{
+    count_inversions, count_inversions_by, count_runs, count_runs_by, is_sorted, is_sorted_by,
+    is_sorted_by_key, kendall_distance, longest_increasing_subsequence,
+    longest_increasing_subsequence_by, longest_run, longest_run_by, spearman_distance,
+};
+pub use external_sort::{external_sort, ExternalSort, LengthPrefixedCodec, LineCodec, RecordCodec};
+pub use heap_sort::HeapSort;
+pub use insertion_sort::{BinaryInsertionSort, InsertionSort, LibrarySort};
+pub use instrument::{instrument, observe_by, Counts, Instrumented, Location, SortObserver};
+pub use intro_sort::IntroSort;
+pub use lsd_radix_sort::{
+    lsd_radix_sort, lsd_radix_sort_by_key, lsd_radix_sort_by_key_observed, RadixKey,
+};
+pub use merge_sort::{BottomUpMergeSort, MergeSort};
+pub use msd_radix_sort::{msd_radix_sort, msd_radix_sort_observed};
+pub use multi_key::{
+    argsort_by_keys, argsort_columns, asc, asc_by, compare_by_keys, desc, desc_by, permute_columns,
+    sort_by_keys, Column, Direction, SortKey,
+};
+#[cfg(feature = "parallel")]
+pub use parallel_sort::{ParallelMergeSort, ParallelQuickSort};
+pub use pdq_sort::PdqSort;
+pub use permutation::{
+    apply_permutation, argsort, argsort_by, argsort_by_key, inverse_permutation, next_permutation,
+    next_permutation_by, random_permutation, sample_without_replacement, shuffle, stable_argsort,
+    stable_argsort_by, stable_argsort_by_key,
+};
+pub use quick_sort::{
+    DualPivotQuickSort, MedianOfThreeQuickSort, RandomizedQuickSort, ThreeWayQuickSort,
+};
+pub use random::{Rng, SeedableRng, XorShift64};
+pub use search::{
+    equal_range, equal_range_by, equal_range_by_key, exponential_search, exponential_search_by,
+    exponential_search_by_key, interpolation_search, interpolation_search_by_key, lower_bound,
+    lower_bound_by, lower_bound_by_key, multi_equal_range, multi_equal_range_by,
+    multi_equal_range_by_key, multi_lower_bound, multi_lower_bound_by, multi_lower_bound_by_key,
+    upper_bound, upper_bound_by, upper_bound_by_key,
+};
+pub use select::{
+    median, median_by, partial_sort, partial_sort_by, partial_sort_by_key, percentile,
+    percentile_by, select_nth, select_nth_by, select_nth_by_key, top_k, top_k_by, top_k_by_key,
+};
+pub use selection_sort::{DoubleEndedSelectionSort, SelectionSort, StableSelectionSort};
+pub use shell_sort::{GapSequence, ShellSort};
+pub use smart_sort::{
+    smart_sort, smart_sort_by, smart_sort_by_radix_key, Decision, DefaultPolicy, Profile,
+    SmartSort, SortPolicy, Strategy,
+};
+pub use sorted_iter::{
+    dedup, dedup_by, difference, difference_by, intersection, intersection_by, merge_k, merge_k_by,
+    symmetric_difference, symmetric_difference_by, union, union_by, Compare, Dedup, MergeK,
+    SetOperation,
+};
+pub use sorting_network::{
+    sort_network, sort_network_by, sort_network_slice_by, sorting_network, BitonicSort,
+    OddEvenMergeSort,
+};
+pub use three_way_radix_quick_sort::{
+    three_way_radix_quick_sort, three_way_radix_quick_sort_observed,
+};
+pub use tim_sort::TimSort;
+pub use trace::{trace, trace_by, Operation, Replay};
+pub use visualize::{ascii_frame, render_ascii, render_html};
+
+/// Defines the interface common to all *Sorter* implementations.
+/// Sorters work on slices, so they can be used on vectors, arrays, sub-slices or any other contiguous sequence of
+/// items, and order them by an arbitrary criterion.
+pub trait Sorter {
+    /// Sorts the provided `list` in place, according to the order defined by the `compare` function.
+    /// `compare` has to define a total order over the items of the list.
+    fn sort_by<T, F>(&self, list: &mut [T], compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering;
+
+    /// Sorts the provided `list` in place, in ascending order.
+    fn sort<T: Ord>(&self, list: &mut [T]) {
+        self.sort_by(list, T::cmp);
     }
-}
 
-///     ## Algorithm
-///     - This sorting algorithm split the list L being sorted in two parts: the sorted part, located at the beginning
-///       of the list (`L[..i]`), and the unsorted part, located at the end of the list (`L[i..]`).
-///       <br/>
-///     - At the beginning the sorted part is empty (i.e. length 0) and the unsorted part covers the entire list (i.e.
-///       length n).
-///     - The algorithm runs n - 1 1-based iterations, where n is the number of items in the list.
-///     - At the beginning of iteration i, the sorted sub-list is `L[..i]` and the unsorted sub-list is `L[i..]`.
-///     - The first item `L[i]`, of the unsorted sub-list `L[i..]`, is compared against its predecessor, `L[i - 1]`.
-///     - If `L[i]` is smaller than `L[i - 1]`, the two items are swapped and the new `L[i - 1]` is compared with `L[i - 2]`.
-///       Comparisons and swapping continues until the predecessor is not bigger than its successor, potentially until
-///       the head of the list is reached.
-///     - When a `L[j]` is found, which is not strictly smaller than `L[j - 1]`, `L[.. (i + 1)]` is sorted, and the iteration
-///       i can terminate.
-///     ## Complexity
-///     - Each of the n - 1 iterations runs at most i - 1 comparisons, if it has to swap all the way up to the head of
-///       the list.
-///     - The total number of comparisons, over the n iterations, is around `n * n / 2`.
-///     - Therefore, Time Complexity is `O(n^2)` and Space Complexity is `O(1)`, since the algorithm runs in place and
-///       hence only requires additional constant space to perform the sorting.
-fn insertion_sort<T: Ord>(list: &mut Vec<T>) {
-    for i in 0..list.len() {
-        for j in (1..i + 1).rev() {
-            if list[j] < list[j - 1] {
-                list.swap(j, j - 1)
-            } else {
-                break;
-            }
-        }
+    /// Sorts the provided `list` in place, in ascending order of the key extracted by the `key` function from each
+    /// item.
+    /// The key is extracted again on every comparison, so `key` is expected to be cheap.
+    fn sort_by_key<T, K, F>(&self, list: &mut [T], mut key: F)
+    where
+        K: Ord,
+        F: FnMut(&T) -> K,
+    {
+        self.sort_by(list, |a, b| key(a).cmp(&key(b)));
+    }
+
+    /// Sorts the provided `list` in place, in descending order.
+    /// To sort by key in descending order, wrap the key in `std::cmp::Reverse`.
+    fn sort_reverse<T: Ord>(&self, list: &mut [T]) {
+        self.sort_by(list, |a, b| b.cmp(a));
     }
+
+    /// Sorts the provided `list` in place like `sort_by`, and reports the operations it runs on the list to the
+    /// `observer`. See `observe_by`.
+    /// Only comparisons are reported by default: the sorters of this crate override it to also report their swaps and
+    /// moves, while `sort_by` runs without any reporting.
+    fn sort_observed_by<T, F>(&self, list: &mut [T], compare: F, observer: &mut dyn SortObserver)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let probe = ObserverProbe::new(list, observer);
+        self.sort_by(list, probe.comparing(compare));
+    }
+}
+
+/// Sorts the provided `list` in place, in ascending order, with `PdqSort`, the crate's default unstable sort.
+pub fn sort_unstable<T: Ord>(list: &mut [T]) {
+    PdqSort.sort(list);
+}
+
+/// Sorts the provided `list` in place, according to the order defined by the `compare` function, with `PdqSort`.
+pub fn sort_unstable_by<T, F>(list: &mut [T], compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    PdqSort.sort_by(list, compare);
+}
+
+/// Sorts the provided `list` in place, in ascending order of the key extracted by the `key` function, with `PdqSort`.
+pub fn sort_unstable_by_key<T, K, F>(list: &mut [T], key: F)
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    PdqSort.sort_by_key(list, key);
 }
 
 #[cfg(test)]
-mod tests {
-    use crate::sorting::{insertion_sort, selection_sort};
+pub(crate) mod tests {
+    use std::{
+        collections::VecDeque,
+        panic::{catch_unwind, AssertUnwindSafe},
+    };
+
+    use crate::sorting::{disorder::is_sorted, Sorter};
 
-    #[test]
-    fn selection_sort_test() {
-        sorting_test(selection_sort);
-        sorting_test_str(insertion_sort);
+    /// A deterministic list of `len` pseudo-random values in `0..modulo`, generated by a xorshift generator.
+    pub(crate) fn pseudo_random_list(len: usize, seed: u64, modulo: u64) -> Vec<u64> {
+        let mut state = seed.max(1);
+        (0..len)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                state % modulo
+            })
+            .collect()
     }
 
-    #[test]
-    fn insertion_sort_test() {
-        sorting_test(insertion_sort);
-        sorting_test_str(insertion_sort);
+    /// A deterministic list of `len` URLs, sharing long prefixes, with some duplicates.
+    pub(crate) fn url_corpus(len: usize) -> Vec<String> {
+        let hosts = [
+            "https://example.com",
+            "https://example.com.au",
+            "http://api.example.org",
+        ];
+        let resources = ["users", "orders", "user", "order-items", "usersettings"];
+        pseudo_random_list(len, 11, u64::MAX)
+            .into_iter()
+            .map(|v| {
+                format!(
+                    "{}/api/v{}/{}/{}?page={}",
+                    hosts[(v % 3) as usize],
+                    v / 3 % 2 + 1,
+                    resources[(v / 7 % 5) as usize],
+                    v / 37 % 200,
+                    v / 7001 % 3
+                )
+            })
+            .collect()
     }
 
-    fn sorting_test<F>(sorting_function: F)
-    where
-        F: (Fn(&mut Vec<i32>) -> ()),
-    {
+    /// A deterministic list of `len` file system paths, sharing long prefixes, with some duplicates.
+    pub(crate) fn path_corpus(len: usize) -> Vec<String> {
+        let roots = [
+            "/usr/local/lib/",
+            "/usr/local/libexec/",
+            "/usr/lib/",
+            "/home/user/projects/crate/src/",
+        ];
+        let names = ["mod", "main", "lib", "sorting", "sort"];
+        pseudo_random_list(len, 13, u64::MAX)
+            .into_iter()
+            .map(|v| {
+                format!(
+                    "{}{}/{}{}.rs",
+                    roots[(v % 4) as usize],
+                    names[(v / 4 % 5) as usize],
+                    names[(v / 20 % 5) as usize],
+                    v / 100 % 50
+                )
+            })
+            .collect()
+    }
+
+    /// Runs the checks every `Sorter` implementation is expected to pass.
+    pub(crate) fn sorting_test<S: Sorter>(sorter: S) {
+        sorting_test_int(&sorter);
+        sorting_test_str(&sorter);
+        sorting_test_by_key(&sorter);
+        sorting_test_reverse(&sorter);
+        sorting_test_sub_slices(&sorter);
+    }
+
+    fn sorting_test_int<S: Sorter>(sorter: &S) {
         let mut list = vec![5, 1, 42, 0];
-        sorting_function(&mut list);
+        sorter.sort(&mut list);
         assert_eq!(list, vec![0, 1, 5, 42]);
 
         let mut empty_array: Vec<i32> = vec![];
-        sorting_function(&mut empty_array);
-        assert_eq!(empty_array, empty_array);
+        sorter.sort(&mut empty_array);
+        assert_eq!(empty_array, vec![]);
 
         let mut singleton_array: Vec<i32> = vec![42];
-        sorting_function(&mut singleton_array);
+        sorter.sort(&mut singleton_array);
         assert_eq!(singleton_array, vec![42]);
+
+        let mut duplicates = vec![3, 1, 3, 2, 1, 3];
+        sorter.sort(&mut duplicates);
+        assert_eq!(duplicates, vec![1, 1, 2, 3, 3, 3]);
     }
 
-    fn sorting_test_str<F>(sorting_function: F)
-    where
-        F: (Fn(&mut Vec<char>) -> ()),
-    {
+    fn sorting_test_str<S: Sorter>(sorter: &S) {
         let mut list = vec!['d', 'b', 'c', 'a'];
-        sorting_function(&mut list);
+        sorter.sort(&mut list);
         assert_eq!(list, vec!['a', 'b', 'c', 'd']);
     }
+
+    fn sorting_test_by_key<S: Sorter>(sorter: &S) {
+        let mut records = vec![("c", 30), ("a", 10), ("d", 5), ("b", 20)];
+        sorter.sort_by_key(&mut records, |record| record.1);
+        assert_eq!(records, vec![("d", 5), ("a", 10), ("b", 20), ("c", 30)]);
+
+        sorter.sort_by(&mut records, |a, b| a.0.cmp(b.0));
+        assert_eq!(records, vec![("a", 10), ("b", 20), ("c", 30), ("d", 5)]);
+    }
+
+    fn sorting_test_reverse<S: Sorter>(sorter: &S) {
+        let mut list = [5, 1, 42, 0];
+        sorter.sort_reverse(&mut list);
+        assert_eq!(list, [42, 5, 1, 0]);
+    }
+
+    fn sorting_test_sub_slices<S: Sorter>(sorter: &S) {
+        let mut list = vec![9, 8, 7, 6, 5, 4, 3, 2, 1];
+        sorter.sort(&mut list[2..6]);
+        assert_eq!(list, vec![9, 8, 4, 5, 6, 7, 3, 2, 1]);
+
+        let mut deque: VecDeque<i32> = (0..10).rev().collect();
+        deque.rotate_left(3);
+        let (front, back) = deque.as_mut_slices();
+        sorter.sort(front);
+        sorter.sort(back);
+        assert!(is_sorted(front));
+        assert!(is_sorted(back));
+    }
+
+    /// Checks the sorter against the standard library sort, on pseudo-random lists of several sizes and with
+    /// different amounts of duplicates.
+    pub(crate) fn pseudo_random_test<S: Sorter>(sorter: S) {
+        for len in [2, 3, 10, 17, 100, 1000] {
+            for modulo in [2, 10, u64::MAX] {
+                let mut list = pseudo_random_list(len, len as u64 ^ modulo, modulo);
+                let mut expected = list.clone();
+                expected.sort();
+                sorter.sort(&mut list);
+                assert_eq!(list, expected);
+
+                sorter.sort(&mut list);
+                assert_eq!(list, expected);
+
+                list.reverse();
+                sorter.sort(&mut list);
+                assert_eq!(list, expected);
+            }
+        }
+    }
+
+    /// Checks that items with equal keys keep their original relative order.
+    pub(crate) fn stability_test<S: Sorter>(sorter: S) {
+        stability_test_with(|records| sorter.sort_by_key(records, |record| record.0));
+    }
+
+    /// Checks that `sort`, which has to sort `(key, original index)` records by key, is stable.
+    pub(crate) fn stability_test_with<F>(mut sort: F)
+    where
+        F: FnMut(&mut [(u64, usize)]),
+    {
+        for len in [10, 100, 1000] {
+            let keys = pseudo_random_list(len, 42, 5);
+            let mut records: Vec<(u64, usize)> = keys.into_iter().zip(0..len).collect();
+            sort(&mut records);
+            for pair in records.windows(2) {
+                assert!(pair[0].0 < pair[1].0 || (pair[0].0 == pair[1].0 && pair[0].1 < pair[1].1));
+            }
+        }
+    }
+
+    /// Checks that the list still holds every one of its items, exactly once, after `compare` panics mid-sort.
+    pub(crate) fn panic_safety_test<S: Sorter>(sorter: S) {
+        let mut list: Vec<String> = pseudo_random_list(100, 7, 1000)
+            .into_iter()
+            .map(|value| value.to_string())
+            .collect();
+        let mut expected = list.clone();
+        expected.sort();
+        let mut comparisons = 0;
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            sorter.sort_by(&mut list, |a, b| {
+                comparisons += 1;
+                if comparisons == 200 {
+                    panic!("compare failed");
+                }
+                a.cmp(b)
+            })
+        }));
+        assert!(result.is_err());
+        list.sort();
+        assert_eq!(list, expected);
+    }
+
+    /// Checks the sorter on inputs known to make naive quicksort quadratic, and that it stays within
+    /// `4 * n * log2(n)` comparisons on them.
+    pub(crate) fn adversarial_test<S: Sorter>(sorter: S) {
+        let len: usize = 2000;
+        let inputs: Vec<(&str, Vec<usize>)> = vec![
+            ("sorted", (0..len).collect()),
+            ("reverse-sorted", (0..len).rev().collect()),
+            ("all-equal", vec![42; len]),
+            (
+                "organ-pipe",
+                (0..len / 2).chain((0..len / 2).rev()).collect(),
+            ),
+            ("sawtooth", (0..len).map(|i| i % 16).collect()),
+        ];
+        let max_comparisons = 4 * len * (usize::BITS - len.leading_zeros()) as usize;
+        for (name, mut list) in inputs {
+            let mut expected = list.clone();
+            expected.sort();
+            let mut comparisons = 0;
+            sorter.sort_by(&mut list, |a, b| {
+                comparisons += 1;
+                a.cmp(b)
+            });
+            assert_eq!(list, expected, "{} input is not sorted", name);
+            assert!(
+                comparisons <= max_comparisons,
+                "{} input took {} comparisons",
+                name,
+                comparisons
+            );
+        }
+    }
 }
@@ -0,0 +1,223 @@
+use std::cmp::Ordering;
+
+use crate::sorting::{
+    instrument::{NoProbe, ObserverProbe, SortObserver},
+    permutation::apply_permutation_unchecked,
+    InsertionSort, Sorter,
+};
+
+/// Sub-lists up to this length are sorted with `InsertionSort`, since counting 257 possible bytes is wasteful on them.
+const INSERTION_SORT_CUTOFF: usize = 16;
+
+///     ## Algorithm
+///     - This sorting algorithm sorts the list L of byte strings one byte at a time, from the first byte to the last.
+///     - L is split by a stable counting sort on the first byte of each string. Strings which are too short to have
+///       such a byte go first, as an empty suffix is smaller than any non-empty one.
+///     - Each group of strings sharing the same first byte is then sorted by its second byte, and so on, until groups
+///       only contain equal strings, or have at most `INSERTION_SORT_CUTOFF` strings and are sorted with
+///       `InsertionSort`, comparing the remaining suffixes only.
+///     - Items are only moved once, at the end, following the permutation computed by the passes, so the sort is stable.
+///     ## Complexity
+///     - Each byte of the distinguishing prefixes of the strings is examined at most once, plus the cost of counting
+///       over 257 possible bytes for each group.
+///     - Therefore, Time Complexity is `O(n * w)` in the worst case, for strings of length w, and usually sub-linear in
+///       the total length of the strings.
+///     - Only the groups smaller than the largest one are sorted recursively, so Space Complexity is
+///       `O(n + 257 * log n)`.
+pub fn msd_radix_sort<T: AsRef<[u8]>>(list: &mut [T]) {
+    let order = msd_radix_argsort(list);
+    apply_permutation_unchecked(list, &order, &NoProbe);
+}
+
+/// Sorts the list like `msd_radix_sort`, and reports the swaps which rearrange it to the `observer`. See
+/// `observe_by`.
+pub fn msd_radix_sort_observed<T: AsRef<[u8]>>(list: &mut [T], observer: &mut dyn SortObserver) {
+    let order = msd_radix_argsort(list);
+    apply_permutation_unchecked(list, &order, &ObserverProbe::new(list, observer));
+}
+
+/// Returns the permutation which stably sorts the list, computed by the passes of `msd_radix_sort`.
+fn msd_radix_argsort<T: AsRef<[u8]>>(list: &[T]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..list.len()).collect();
+    let mut aux = vec![0; list.len()];
+    msd_sort(list, &mut order, &mut aux, 0);
+    order
+}
+
+/// Returns the byte of `item` at index `depth`, shifted by one, or 0 when `item` is too short to have one.
+pub(crate) fn byte_at<T: AsRef<[u8]>>(item: &T, depth: usize) -> usize {
+    match item.as_ref().get(depth) {
+        Some(&byte) => byte as usize + 1,
+        None => 0,
+    }
+}
+
+fn msd_sort<T: AsRef<[u8]>>(
+    list: &[T],
+    mut order: &mut [usize],
+    mut aux: &mut [usize],
+    mut depth: usize,
+) {
+    loop {
+        if order.len() <= INSERTION_SORT_CUTOFF {
+            InsertionSort.sort_by(order, |&a, &b| suffix_cmp(&list[a], &list[b], depth));
+            return;
+        }
+        let mut starts = [0; 258];
+        for &index in order.iter() {
+            starts[byte_at(&list[index], depth) + 1] += 1;
+        }
+        for r in 0..257 {
+            starts[r + 1] += starts[r];
+        }
+        let bounds = starts;
+        for &index in order.iter() {
+            let r = byte_at(&list[index], depth);
+            aux[starts[r]] = index;
+            starts[r] += 1;
+        }
+        order.copy_from_slice(&aux[..order.len()]);
+        // Only the groups smaller than the largest one are sorted recursively, each of them holding at most half of
+        // the strings, so that long shared prefixes are walked by this loop rather than by nested calls.
+        let largest = (1..257).max_by_key(|&r| bounds[r + 1] - bounds[r]).unwrap();
+        for r in (1..257).filter(|&r| r != largest) {
+            let (lo, hi) = (bounds[r], bounds[r + 1]);
+            if hi - lo > 1 {
+                msd_sort(list, &mut order[lo..hi], &mut aux[lo..hi], depth + 1);
+            }
+        }
+        let (lo, hi) = (bounds[largest], bounds[largest + 1]);
+        if hi - lo <= 1 {
+            return;
+        }
+        order = &mut order[lo..hi];
+        aux = &mut aux[lo..hi];
+        depth += 1;
+    }
+}
+
+fn suffix_cmp<T: AsRef<[u8]>>(a: &T, b: &T, depth: usize) -> Ordering {
+    let a = a.as_ref();
+    let b = b.as_ref();
+    a[depth.min(a.len())..].cmp(&b[depth.min(b.len())..])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sorting::{
+        msd_radix_sort::msd_radix_sort,
+        tests::{path_corpus, pseudo_random_list, url_corpus},
+        InsertionSort, Sorter,
+    };
+
+    #[test]
+    fn msd_radix_sort_test() {
+        let mut list = vec![
+            "she",
+            "sells",
+            "seashells",
+            "by",
+            "the",
+            "sea",
+            "shore",
+            "",
+            "s",
+            "sea",
+        ];
+        msd_radix_sort(&mut list);
+        assert_eq!(
+            list,
+            vec![
+                "",
+                "by",
+                "s",
+                "sea",
+                "sea",
+                "seashells",
+                "sells",
+                "she",
+                "shore",
+                "the"
+            ]
+        );
+
+        let mut empty: Vec<String> = vec![];
+        msd_radix_sort(&mut empty);
+        assert_eq!(empty, Vec::<String>::new());
+    }
+
+    #[test]
+    fn against_insertion_sort() {
+        let mut list: Vec<Vec<u8>> = pseudo_random_list(1000, 8, u64::MAX)
+            .into_iter()
+            .map(|v| {
+                v.to_le_bytes()[..(v % 9) as usize]
+                    .iter()
+                    .map(|b| b % 4)
+                    .collect()
+            })
+            .collect();
+        let mut expected = list.clone();
+        InsertionSort.sort(&mut expected);
+        msd_radix_sort(&mut list);
+        assert_eq!(list, expected);
+    }
+
+    #[test]
+    fn urls_and_paths() {
+        let mut list = url_corpus(1000);
+        let mut expected = list.clone();
+        InsertionSort.sort(&mut expected);
+        msd_radix_sort(&mut list);
+        assert_eq!(list, expected);
+
+        let corpus = path_corpus(1000);
+        let mut list: Vec<&str> = corpus.iter().map(String::as_str).collect();
+        let mut expected = list.clone();
+        InsertionSort.sort(&mut expected);
+        msd_radix_sort(&mut list);
+        assert_eq!(list, expected);
+    }
+
+    #[test]
+    fn long_shared_prefix() {
+        // One call per shared byte would overflow the stack of the test thread.
+        let prefix = "a".repeat(10_000);
+        let mut list: Vec<String> = pseudo_random_list(100, 10, 1000)
+            .into_iter()
+            .map(|v| format!("{}{}", prefix, v))
+            .collect();
+        let mut expected = list.clone();
+        expected.sort();
+        msd_radix_sort(&mut list);
+        assert_eq!(list, expected);
+    }
+
+    #[test]
+    fn stability() {
+        struct Record {
+            key: Vec<u8>,
+            index: usize,
+        }
+        impl AsRef<[u8]> for Record {
+            fn as_ref(&self) -> &[u8] {
+                &self.key
+            }
+        }
+        let mut records: Vec<Record> = pseudo_random_list(1000, 9, 5)
+            .into_iter()
+            .enumerate()
+            .map(|(index, v)| Record {
+                key: vec![b'k'; v as usize],
+                index,
+            })
+            .collect();
+        msd_radix_sort(&mut records);
+        for pair in records.windows(2) {
+            assert!(
+                pair[0].key < pair[1].key
+                    || (pair[0].key == pair[1].key && pair[0].index < pair[1].index)
+            );
+        }
+    }
+}
@@ -0,0 +1,345 @@
+use std::cmp::Ordering;
+
+use crate::sorting::{
+    permutation::{apply_permutation, argsort_by, check_permutation},
+    Sorter, TimSort,
+};
+
+/// The direction in which a `SortKey` orders the rows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Ascending,
+    Descending,
+}
+
+/// A comparison between two rows.
+type Comparator<'a, T> = dyn Fn(&T, &T) -> Ordering + 'a;
+
+/// One of the keys `sort_by_keys` sorts rows by: a comparison between rows, and the direction to order them in.
+/// Keys are usually created with `asc`, `desc`, `asc_by` and `desc_by`.
+///
+/// ```
+/// use more_rust_structures::sorting::{asc_by, desc, sort_by_keys};
+///
+/// type Row = (&'static str, u32, f64);
+/// let mut rows: Vec<Row> = vec![("ann", 30, 2.5), ("bob", 25, 1.0), ("cid", 30, 4.0), ("ann", 25, 3.0)];
+/// sort_by_keys(&mut rows, &[desc(|row: &Row| row.1), asc_by(|a: &Row, b: &Row| a.0.cmp(b.0))]);
+/// assert_eq!(rows, [("ann", 30, 2.5), ("cid", 30, 4.0), ("ann", 25, 3.0), ("bob", 25, 1.0)]);
+/// ```
+pub struct SortKey<'a, T> {
+    compare: Box<Comparator<'a, T>>,
+    direction: Direction,
+}
+
+impl<'a, T> SortKey<'a, T> {
+    pub fn new<F>(compare: F, direction: Direction) -> Self
+    where
+        F: Fn(&T, &T) -> Ordering + 'a,
+    {
+        SortKey {
+            compare: Box::new(compare),
+            direction,
+        }
+    }
+
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    /// Compares two rows by this key, in its direction.
+    pub fn compare(&self, a: &T, b: &T) -> Ordering {
+        let ordering = (self.compare)(a, b);
+        match self.direction {
+            Direction::Ascending => ordering,
+            Direction::Descending => ordering.reverse(),
+        }
+    }
+}
+
+/// A key ordering rows in ascending order of the value extracted by the `key` function.
+pub fn asc<'a, T, K, F>(key: F) -> SortKey<'a, T>
+where
+    K: Ord,
+    F: Fn(&T) -> K + 'a,
+{
+    SortKey::new(move |a, b| key(a).cmp(&key(b)), Direction::Ascending)
+}
+
+/// A key ordering rows in descending order of the value extracted by the `key` function.
+pub fn desc<'a, T, K, F>(key: F) -> SortKey<'a, T>
+where
+    K: Ord,
+    F: Fn(&T) -> K + 'a,
+{
+    SortKey::new(move |a, b| key(a).cmp(&key(b)), Direction::Descending)
+}
+
+/// A key ordering rows according to the order defined by the `compare` function. Unlike `asc`, it can compare values
+/// borrowed from the rows, such as string columns, without cloning them.
+pub fn asc_by<'a, T, F>(compare: F) -> SortKey<'a, T>
+where
+    F: Fn(&T, &T) -> Ordering + 'a,
+{
+    SortKey::new(compare, Direction::Ascending)
+}
+
+/// A key ordering rows in the reverse of the order defined by the `compare` function. See `asc_by`.
+pub fn desc_by<'a, T, F>(compare: F) -> SortKey<'a, T>
+where
+    F: Fn(&T, &T) -> Ordering + 'a,
+{
+    SortKey::new(compare, Direction::Descending)
+}
+
+/// Compares two rows lexicographically by the keys: by the first key, then, when they are equal by it, by the second
+/// key, and so on. Rows equal by all the keys are equal.
+pub fn compare_by_keys<T>(keys: &[SortKey<'_, T>], a: &T, b: &T) -> Ordering {
+    keys.iter()
+        .map(|key| key.compare(a, b))
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or(Ordering::Equal)
+}
+
+///     ## Algorithm
+///     - This sorting algorithm sorts the rows lexicographically by the keys, each in its own direction, as defined by
+///       `compare_by_keys`, with `TimSort`.
+///     - Keys are only compared until the first one which tells the rows apart, and the sort is stable, so rows equal
+///       by all the keys keep their relative order.
+///     ## Complexity
+///     - It runs `O(n log n)` comparisons of rows, each running at most one comparison per key.
+///     - Therefore, Time Complexity is `O(k n log n)`, for k keys, and Space Complexity is `O(n)`.
+pub fn sort_by_keys<T>(rows: &mut [T], keys: &[SortKey<'_, T>]) {
+    TimSort.sort_by(rows, |a, b| compare_by_keys(keys, a, b));
+}
+
+/// Returns the permutation which stably sorts the rows by the keys, as `sort_by_keys` does, without rearranging
+/// them: the indexes of the rows, in sorted order.
+pub fn argsort_by_keys<T>(rows: &[T], keys: &[SortKey<'_, T>]) -> Vec<usize> {
+    argsort_by(&TimSort, rows, |a, b| compare_by_keys(keys, a, b))
+}
+
+/// A column of a table stored column by column, as parallel vectors of the same length, which can be reordered by
+/// a permutation of its rows.
+pub trait Column {
+    /// Returns the number of rows of the column.
+    fn len(&self) -> usize;
+
+    /// Returns whether the column has no rows.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Rearranges the column so that the value at row `i` is the value which was at row `order[i]`. `permute_columns`
+    /// only calls it once it has checked that order is a permutation of the rows of every column.
+    ///     ## Panics
+    ///     - When order is not a permutation of the rows of the column.
+    fn permute(&mut self, order: &[usize]);
+}
+
+impl<T> Column for Vec<T> {
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+
+    fn permute(&mut self, order: &[usize]) {
+        apply_permutation(self, order);
+    }
+}
+
+/// Returns the permutation which stably sorts the `len` rows of a table stored column by column by the keys, which
+/// compare the indexes of the rows, usually by looking them up in the columns. The columns can then be reordered by
+/// the permutation with `permute_columns`, once the keys are dropped.
+///
+/// ```
+/// use more_rust_structures::sorting::{argsort_columns, asc, desc, permute_columns};
+///
+/// let mut names = vec!["ann", "bob", "cid", "dan"];
+/// let mut teams = vec![2, 1, 2, 1];
+/// let mut scores = vec![7.5, 9.0, 8.0, 6.5];
+/// let order = argsort_columns(names.len(), &[asc(|&row| teams[row]), desc(|&row| (scores[row] * 10.0) as i64)]);
+/// permute_columns(&order, &mut [&mut names, &mut teams, &mut scores]);
+/// assert_eq!(names, ["bob", "dan", "cid", "ann"]);
+/// assert_eq!(teams, [1, 1, 2, 2]);
+/// assert_eq!(scores, [9.0, 6.5, 8.0, 7.5]);
+/// ```
+pub fn argsort_columns(len: usize, keys: &[SortKey<'_, usize>]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..len).collect();
+    TimSort.sort_by(&mut order, |a, b| compare_by_keys(keys, a, b));
+    order
+}
+
+/// Reorders all the columns by the same permutation of their rows, as returned by `argsort_columns`. See
+/// `apply_permutation`.
+///     ## Panics
+///     - When order is not a permutation of the rows of every column, in which case no column is reordered.
+pub fn permute_columns(order: &[usize], columns: &mut [&mut dyn Column]) {
+    for column in columns.iter() {
+        if column.len() != order.len() {
+            panic!(
+                "every column should have the same length as order, {}, but one has length {}",
+                order.len(),
+                column.len()
+            );
+        }
+    }
+    check_permutation(order);
+    for column in columns {
+        column.permute(order);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        cmp::Ordering,
+        panic::{self, AssertUnwindSafe},
+    };
+
+    use crate::sorting::{
+        disorder::is_sorted_by,
+        multi_key::{
+            argsort_by_keys, argsort_columns, asc, asc_by, compare_by_keys, desc, desc_by,
+            permute_columns, sort_by_keys, Column, Direction, SortKey,
+        },
+        tests::pseudo_random_list,
+    };
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Row {
+        id: usize,
+        city: String,
+        age: u64,
+        score: u64,
+    }
+
+    fn rows(len: usize) -> Vec<Row> {
+        let cities = ["Rome", "Oslo", "Lima", "Kyiv"];
+        pseudo_random_list(len, 17, u64::MAX)
+            .into_iter()
+            .enumerate()
+            .map(|(id, v)| Row {
+                id,
+                city: cities[(v % 4) as usize].to_string(),
+                age: v / 4 % 5,
+                score: v / 20 % 3,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn sort_by_keys_test() {
+        let mut table = rows(500);
+        let keys = [
+            asc_by(|a: &Row, b: &Row| a.city.cmp(&b.city)),
+            desc(|row: &Row| row.age),
+            asc(|row: &Row| row.score),
+        ];
+        sort_by_keys(&mut table, &keys);
+        let mut expected = rows(500);
+        expected.sort_by(|a, b| {
+            a.city
+                .cmp(&b.city)
+                .then(b.age.cmp(&a.age))
+                .then(a.score.cmp(&b.score))
+        });
+        assert_eq!(table, expected);
+        assert!(is_sorted_by(&table, |a, b| compare_by_keys(&keys, a, b)));
+        // Rows equal by all the keys keep their original order.
+        assert!(table.windows(2).all(|pair| {
+            compare_by_keys(&keys, &pair[0], &pair[1]).is_ne() || pair[0].id < pair[1].id
+        }));
+    }
+
+    #[test]
+    fn keys() {
+        let key = desc_by(|a: &i32, b: &i32| a.cmp(b));
+        assert_eq!(key.direction(), Direction::Descending);
+        assert_eq!(key.compare(&1, &2), Ordering::Greater);
+        let key = SortKey::new(|a: &i32, b: &i32| a.cmp(b), Direction::Ascending);
+        assert_eq!(key.compare(&1, &2), Ordering::Less);
+        assert_eq!(compare_by_keys::<i32>(&[], &1, &2), Ordering::Equal);
+
+        let mut list = vec![3, 1, 2];
+        sort_by_keys(&mut list, &[]);
+        assert_eq!(list, [3, 1, 2]);
+    }
+
+    #[test]
+    fn argsort_by_keys_test() {
+        let table = rows(100);
+        let keys = [asc(|row: &Row| row.score), desc(|row: &Row| row.age)];
+        let order = argsort_by_keys(&table, &keys);
+        let mut sorted = table.clone();
+        sort_by_keys(&mut sorted, &keys);
+        let by_order: Vec<Row> = order.iter().map(|&i| table[i].clone()).collect();
+        assert_eq!(by_order, sorted);
+    }
+
+    #[test]
+    fn columns_test() {
+        let table = rows(300);
+        let mut ids: Vec<usize> = table.iter().map(|row| row.id).collect();
+        let mut cities: Vec<String> = table.iter().map(|row| row.city.clone()).collect();
+        let mut ages: Vec<u64> = table.iter().map(|row| row.age).collect();
+        let order = argsort_columns(
+            ids.len(),
+            &[
+                desc_by(|&a: &usize, &b: &usize| cities[a].cmp(&cities[b])),
+                asc(|&row| ages[row]),
+            ],
+        );
+        permute_columns(&order, &mut [&mut ids, &mut cities, &mut ages]);
+
+        let mut expected = table;
+        sort_by_keys(
+            &mut expected,
+            &[
+                desc_by(|a: &Row, b: &Row| a.city.cmp(&b.city)),
+                asc(|row: &Row| row.age),
+            ],
+        );
+        assert_eq!(ids, expected.iter().map(|row| row.id).collect::<Vec<_>>());
+        assert_eq!(
+            cities,
+            expected
+                .iter()
+                .map(|row| row.city.clone())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(ages, expected.iter().map(|row| row.age).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn permute_columns_of_different_lengths() {
+        let mut short = vec![1, 2];
+        let mut long = vec![1, 2, 3];
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            permute_columns(&[1, 0, 2], &mut [&mut long, &mut short])
+        }));
+        let message = *result.unwrap_err().downcast::<String>().unwrap();
+        assert_eq!(
+            message,
+            "every column should have the same length as order, 3, but one has length 2"
+        );
+        assert_eq!(long, [1, 2, 3]);
+        assert_eq!(short, [1, 2]);
+    }
+
+    #[test]
+    fn permute_columns_by_invalid_order() {
+        let mut first = vec![1, 2, 3];
+        let mut second = vec![4, 5, 6];
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            permute_columns(&[1, 1, 2], &mut [&mut first, &mut second])
+        }));
+        assert!(result.is_err());
+        assert_eq!(first, [1, 2, 3]);
+        assert_eq!(second, [4, 5, 6]);
+    }
+
+    #[test]
+    #[should_panic(expected = "[1, 1] is not a permutation of 0..2")]
+    fn permute_column_by_invalid_order() {
+        Column::permute(&mut vec![10, 20], &[1, 1]);
+    }
+}
@@ -0,0 +1,438 @@
+use std::{cmp::Ordering, mem::MaybeUninit, ptr};
+
+use rayon::prelude::*;
+
+use crate::sorting::{
+    instrument::NoProbe, merge_sort::merge, quick_sort::median_of_three, MergeSort, Sorter,
+};
+
+/// Sub-lists up to this length are sorted sequentially by default.
+const DEFAULT_CUTOFF: usize = 8192;
+
+/// The number of items each task classifies and moves during a parallel partitioning.
+const PARTITION_CHUNK: usize = 4096;
+
+///     ## Algorithm
+///     - This sorting algorithm is `MergeSort` where the two halves of each sub-list longer than `cutoff` are sorted in
+///       parallel, as separate rayon tasks, before being merged.
+///     - Sub-lists of at most `cutoff` items are sorted sequentially, with `MergeSort`.
+///     - It is stable, so its output is identical to the output of `MergeSort`, whatever the number of threads.
+///     ## Complexity
+///     - The work is `O(n log n)`, as for `MergeSort`, but merges run sequentially, so the span is `O(n)`.
+///     - Space Complexity is `O(n)`, for the merge buffers.
+pub struct ParallelMergeSort {
+    cutoff: usize,
+}
+
+impl ParallelMergeSort {
+    pub fn new(cutoff: usize) -> Self {
+        ParallelMergeSort { cutoff }
+    }
+
+    /// Sorts the provided `list` in place, in ascending order.
+    pub fn sort<T: Ord + Send>(&self, list: &mut [T]) {
+        self.sort_by(list, T::cmp);
+    }
+
+    /// Sorts the provided `list` in place, in ascending order of the key extracted by the `key` function.
+    pub fn sort_by_key<T, K, F>(&self, list: &mut [T], key: F)
+    where
+        T: Send,
+        K: Ord,
+        F: Fn(&T) -> K + Sync,
+    {
+        self.sort_by(list, |a, b| key(a).cmp(&key(b)));
+    }
+
+    /// Sorts the provided `list` in place, according to the order defined by the `compare` function, which is shared
+    /// by all threads.
+    pub fn sort_by<T, F>(&self, list: &mut [T], compare: F)
+    where
+        T: Send,
+        F: Fn(&T, &T) -> Ordering + Sync,
+    {
+        parallel_merge_sort(list, &compare, self.cutoff.max(1));
+    }
+}
+
+impl Default for ParallelMergeSort {
+    fn default() -> Self {
+        ParallelMergeSort::new(DEFAULT_CUTOFF)
+    }
+}
+
+fn parallel_merge_sort<T, F>(list: &mut [T], compare: &F, cutoff: usize)
+where
+    T: Send,
+    F: Fn(&T, &T) -> Ordering + Sync,
+{
+    if list.len() <= cutoff {
+        MergeSort.sort_by(list, compare);
+        return;
+    }
+    let mid = list.len() / 2;
+    let (left, right) = list.split_at_mut(mid);
+    rayon::join(
+        || parallel_merge_sort(left, compare, cutoff),
+        || parallel_merge_sort(right, compare, cutoff),
+    );
+    merge(
+        list,
+        mid,
+        &mut Vec::with_capacity(mid),
+        &mut |a: &T, b: &T| compare(a, b),
+        &NoProbe,
+    );
+}
+
+///     ## Algorithm
+///     - This sorting algorithm is a quicksort where each sub-list longer than `cutoff` is partitioned in parallel,
+///       and the resulting parts are sorted in parallel, as separate rayon tasks.
+///     - The pivot is chosen as in `MedianOfThreeQuickSort`. Chunks of the sub-list are then compared with the pivot
+///       in parallel, each chunk counts the items smaller than, equal to and bigger than the pivot, and a prefix sum
+///       over the counts gives each chunk the positions its items go to.
+///     - Items are moved in parallel to an auxiliary buffer, into the three parts, in their original order, and moved
+///       back. Items equal to the pivot are in their final position, and are not sorted again.
+///     - Sub-lists of at most `cutoff` items are sorted sequentially, with `MergeSort`.
+///     - As in `IntroSort`, it keeps track of the recursion depth, and when it exceeds `2 * log2(n)`, which only
+///       happens when the chosen pivots keep producing unbalanced partitions, the current sub-list is sorted with
+///       `ParallelMergeSort` instead.
+///     - Partitioning preserves the order of equal items, so the sort is stable, and its output is identical to the
+///       output of `MergeSort`, whatever the number of threads and the cutoff.
+///     ## Complexity
+///     - The work is `O(n log n)`, also in the worst case, thanks to the fallback, and the span is `O(log^2 n)` on
+///       average, above the cutoff.
+///     - Space Complexity is `O(n)`, for the auxiliary buffer, and the recursion is `O(log n)` deep.
+pub struct ParallelQuickSort {
+    cutoff: usize,
+}
+
+impl ParallelQuickSort {
+    pub fn new(cutoff: usize) -> Self {
+        ParallelQuickSort { cutoff }
+    }
+
+    /// Sorts the provided `list` in place, in ascending order.
+    pub fn sort<T: Ord + Send + Sync>(&self, list: &mut [T]) {
+        self.sort_by(list, T::cmp);
+    }
+
+    /// Sorts the provided `list` in place, in ascending order of the key extracted by the `key` function.
+    pub fn sort_by_key<T, K, F>(&self, list: &mut [T], key: F)
+    where
+        T: Send + Sync,
+        K: Ord,
+        F: Fn(&T) -> K + Sync,
+    {
+        self.sort_by(list, |a, b| key(a).cmp(&key(b)));
+    }
+
+    /// Sorts the provided `list` in place, according to the order defined by the `compare` function, which is shared
+    /// by all threads.
+    pub fn sort_by<T, F>(&self, list: &mut [T], compare: F)
+    where
+        T: Send + Sync,
+        F: Fn(&T, &T) -> Ordering + Sync,
+    {
+        let mut buffer: Vec<MaybeUninit<T>> = Vec::with_capacity(list.len());
+        buffer.resize_with(list.len(), MaybeUninit::uninit);
+        let depth_limit = 2 * list.len().max(1).ilog2();
+        parallel_quick_sort(list, &mut buffer, &compare, self.cutoff.max(1), depth_limit);
+    }
+}
+
+impl Default for ParallelQuickSort {
+    fn default() -> Self {
+        ParallelQuickSort::new(DEFAULT_CUTOFF)
+    }
+}
+
+fn parallel_quick_sort<T, F>(
+    list: &mut [T],
+    buffer: &mut [MaybeUninit<T>],
+    compare: &F,
+    cutoff: usize,
+    depth_limit: u32,
+) where
+    T: Send + Sync,
+    F: Fn(&T, &T) -> Ordering + Sync,
+{
+    if list.len() <= cutoff {
+        MergeSort.sort_by(list, compare);
+        return;
+    }
+    if depth_limit == 0 {
+        parallel_merge_sort(list, compare, cutoff);
+        return;
+    }
+    let (lt, gt) = parallel_partition(list, buffer, compare);
+    let (smaller, rest) = list.split_at_mut(lt);
+    let bigger = &mut rest[gt - lt..];
+    let (smaller_buffer, rest_buffer) = buffer.split_at_mut(lt);
+    let bigger_buffer = &mut rest_buffer[gt - lt..];
+    rayon::join(
+        || parallel_quick_sort(smaller, smaller_buffer, compare, cutoff, depth_limit - 1),
+        || parallel_quick_sort(bigger, bigger_buffer, compare, cutoff, depth_limit - 1),
+    );
+}
+
+/// Partitions the list, preserving the relative order of the items, into the items smaller than the pivot,
+/// `list[..lt]`, the items equal to it, `list[lt..gt]`, and the items bigger than it, `list[gt..]`, and returns
+/// `(lt, gt)`.
+fn parallel_partition<T, F>(
+    list: &mut [T],
+    buffer: &mut [MaybeUninit<T>],
+    compare: &F,
+) -> (usize, usize)
+where
+    T: Send + Sync,
+    F: Fn(&T, &T) -> Ordering + Sync,
+{
+    let len = list.len();
+    let pivot = median_of_three(list, len / 4, len / 2, 3 * len / 4, &mut |a: &T, b: &T| {
+        compare(a, b)
+    });
+
+    // `compare` is only called while classifying, before any item is moved, so a panic leaves the list untouched.
+    let shared: &[T] = list;
+    let classes: Vec<Ordering> = shared
+        .par_iter()
+        .map(|item| compare(item, &shared[pivot]))
+        .collect();
+    let counts: Vec<[usize; 3]> = classes
+        .par_chunks(PARTITION_CHUNK)
+        .map(|chunk| {
+            let mut counts = [0; 3];
+            for class in chunk {
+                counts[part(*class)] += 1;
+            }
+            counts
+        })
+        .collect();
+
+    let mut totals = [0; 3];
+    for chunk_counts in &counts {
+        for part in 0..3 {
+            totals[part] += chunk_counts[part];
+        }
+    }
+    let mut next = [0, totals[0], totals[0] + totals[1]];
+    let offsets: Vec<[usize; 3]> = counts
+        .iter()
+        .map(|chunk_counts| {
+            let offsets = next;
+            for part in 0..3 {
+                next[part] += chunk_counts[part];
+            }
+            offsets
+        })
+        .collect();
+
+    let source = SendPtr(list.as_mut_ptr());
+    let destination = SendPtr(buffer.as_mut_ptr() as *mut T);
+    classes
+        .par_chunks(PARTITION_CHUNK)
+        .zip(offsets)
+        .enumerate()
+        .for_each(|(chunk, (classes, mut offsets))| {
+            let start = chunk * PARTITION_CHUNK;
+            for (i, class) in classes.iter().enumerate() {
+                let part = part(*class);
+                // SAFETY: every item of the list is copied exactly once, to a distinct slot of the buffer, as the
+                // offsets of each chunk cover disjoint ranges of each part.
+                unsafe {
+                    ptr::copy_nonoverlapping(
+                        source.get().add(start + i),
+                        destination.get().add(offsets[part]),
+                        1,
+                    )
+                };
+                offsets[part] += 1;
+            }
+        });
+    list.par_chunks_mut(PARTITION_CHUNK)
+        .zip(buffer.par_chunks(PARTITION_CHUNK))
+        .for_each(|(list, buffer)| {
+            // SAFETY: the buffer now holds a bitwise copy of every item, and the list is only overwritten with them,
+            // without dropping its stale copies.
+            unsafe {
+                ptr::copy_nonoverlapping(buffer.as_ptr() as *const T, list.as_mut_ptr(), list.len())
+            };
+        });
+    (totals[0], totals[0] + totals[1])
+}
+
+fn part(class: Ordering) -> usize {
+    match class {
+        Ordering::Less => 0,
+        Ordering::Equal => 1,
+        Ordering::Greater => 2,
+    }
+}
+
+/// A raw pointer which can be shared by the tasks of a parallel partitioning, each of them accessing disjoint items.
+struct SendPtr<T>(*mut T);
+
+impl<T> Clone for SendPtr<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for SendPtr<T> {}
+
+impl<T> SendPtr<T> {
+    /// Returns the pointer. Closures have to go through this method, so that they capture the whole `SendPtr` rather
+    /// than just its raw pointer field.
+    fn get(self) -> *mut T {
+        self.0
+    }
+}
+
+// SAFETY: `SendPtr` is only used to copy items of type `T: Send` between threads, without aliasing.
+unsafe impl<T: Send> Send for SendPtr<T> {}
+unsafe impl<T: Send> Sync for SendPtr<T> {}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        panic::{catch_unwind, AssertUnwindSafe},
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Mutex,
+        },
+    };
+
+    use rayon::ThreadPoolBuilder;
+
+    use crate::sorting::{
+        parallel_sort::{ParallelMergeSort, ParallelQuickSort},
+        tests::pseudo_random_list,
+        MergeSort, Sorter,
+    };
+
+    /// Records with few distinct keys, so that equal keys tell apart stable and unstable sorts.
+    fn records(len: usize) -> Vec<(u64, usize)> {
+        pseudo_random_list(len, 21, 50)
+            .into_iter()
+            .zip(0..len)
+            .collect()
+    }
+
+    fn expected(records: &[(u64, usize)]) -> Vec<(u64, usize)> {
+        let mut expected = records.to_vec();
+        MergeSort.sort_by_key(&mut expected, |record| record.0);
+        expected
+    }
+
+    #[test]
+    fn parallel_merge_sort_test() {
+        for len in [0, 1, 2, 100, 10_000, 100_000] {
+            let mut list = pseudo_random_list(len, 3, u64::MAX);
+            let mut sorted = list.clone();
+            sorted.sort();
+            ParallelMergeSort::new(1000).sort(&mut list);
+            assert_eq!(list, sorted);
+        }
+    }
+
+    #[test]
+    fn parallel_quick_sort_test() {
+        for len in [0, 1, 2, 100, 10_000, 100_000] {
+            let mut list = pseudo_random_list(len, 3, u64::MAX);
+            let mut sorted = list.clone();
+            sorted.sort();
+            ParallelQuickSort::new(1000).sort(&mut list);
+            assert_eq!(list, sorted);
+        }
+        let mut equal = vec![7; 50_000];
+        ParallelQuickSort::new(1000).sort(&mut equal);
+        assert_eq!(equal, vec![7; 50_000]);
+    }
+
+    #[test]
+    fn identical_to_sequential() {
+        let records = records(50_000);
+        let expected = expected(&records);
+        for threads in [1, 2, 4] {
+            let pool = ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap();
+            for cutoff in [0, 100, 5000, 100_000] {
+                let mut list = records.clone();
+                pool.install(|| {
+                    ParallelMergeSort::new(cutoff).sort_by_key(&mut list, |record| record.0)
+                });
+                assert_eq!(list, expected);
+
+                let mut list = records.clone();
+                pool.install(|| {
+                    ParallelQuickSort::new(cutoff).sort_by_key(&mut list, |record| record.0)
+                });
+                assert_eq!(list, expected);
+            }
+        }
+    }
+
+    /// Sorts the indices `0..len` with McIlroy's killer adversary, which only decides the order of two items when it
+    /// has to, so that each pivot ends up next to the smallest items of its sub-list. Returns the input this run
+    /// turned out to be: the value each index was given.
+    fn killer_adversary(sorter: &ParallelQuickSort, len: usize) -> Vec<usize> {
+        let gas = len;
+        let state = Mutex::new((vec![gas; len], 0, 0));
+        let mut list: Vec<usize> = (0..len).collect();
+        sorter.sort_by(&mut list, |&x, &y| {
+            let (values, solid, candidate) = &mut *state.lock().unwrap();
+            if values[x] == gas && values[y] == gas {
+                let frozen = if x == *candidate { x } else { y };
+                values[frozen] = *solid;
+                *solid += 1;
+            }
+            if values[x] == gas {
+                *candidate = x;
+            } else if values[y] == gas {
+                *candidate = y;
+            }
+            values[x].cmp(&values[y])
+        });
+        let (values, _, _) = state.into_inner().unwrap();
+        assert!(list
+            .windows(2)
+            .all(|pair| values[pair[0]] <= values[pair[1]]));
+        values
+    }
+
+    #[test]
+    fn adversarial_input() {
+        let pool = ThreadPoolBuilder::new().num_threads(1).build().unwrap();
+        let sorter = ParallelQuickSort::new(100);
+        let mut list = pool.install(|| killer_adversary(&sorter, 20_000));
+        let mut expected = list.clone();
+        expected.sort();
+        sorter.sort(&mut list);
+        assert_eq!(list, expected);
+    }
+
+    #[test]
+    fn panic_safety() {
+        let mut list: Vec<String> = pseudo_random_list(20_000, 7, 1000)
+            .into_iter()
+            .map(|v| v.to_string())
+            .collect();
+        let mut expected = list.clone();
+        expected.sort();
+        let comparisons = AtomicUsize::new(0);
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            ParallelQuickSort::new(100).sort_by(&mut list, |a, b| {
+                if comparisons.fetch_add(1, Ordering::Relaxed) == 50_000 {
+                    panic!("compare failed");
+                }
+                a.cmp(b)
+            })
+        }));
+        assert!(result.is_err());
+        list.sort();
+        assert_eq!(list, expected);
+    }
+}
@@ -0,0 +1,414 @@
+use std::cmp::Ordering;
+
+use crate::sorting::{
+    heap_sort::heap_sort,
+    insertion_sort::insertion_sort,
+    instrument::{reverse, swap, NoProbe, ObserverProbe, Probe},
+    SortObserver, Sorter,
+};
+
+/// Sub-lists up to this length are sorted with `InsertionSort`.
+const INSERTION_SORT_CUTOFF: usize = 20;
+
+/// The number of items scanned at once on each side of the list by the block partitioning.
+const BLOCK: usize = 128;
+
+///     ## Algorithm
+///     - This sorting algorithm, by Orson Peters, is a pattern-defeating quicksort: an introsort which recognises the
+///       patterns in the list L that would make quicksort slow, and takes advantage of the ones that make it fast.
+///     - The pivot is the median of three items, or of three medians of three on longer lists. Pivots are picked so
+///       that, when L looks already sorted or reverse-sorted, a partial insertion sort is attempted first, and gives up
+///       after fixing a handful of misplaced items.
+///     - Partitioning works in blocks of `BLOCK` items: the positions of the misplaced items of a block on each side are
+///       collected first, without branching on comparisons, and then swapped in pairs.
+///     - When the pivot is equal to the pivot of the parent partition, which is the smallest item of L, L is
+///       partitioned into the items equal to the pivot and the rest, and the former are never looked at again. This
+///       makes lists with many duplicates linear to sort.
+///     - An unbalanced partition shuffles a few items around the middle of the next sub-lists, to break patterns
+///       which keep producing bad pivots. After `log2(n)` unbalanced partitions, the current sub-list is sorted with
+///       `HeapSort`, and sub-lists of at most `INSERTION_SORT_CUTOFF` items are sorted with `InsertionSort`.
+///     ## Complexity
+///     - Time Complexity is `O(n log n)` in the worst case and `O(n)` on sorted lists, reverse-sorted lists and lists
+///       with few distinct keys.
+///     - Space Complexity is `O(log n)`, for the recursion. The algorithm never allocates and is not stable.
+pub struct PdqSort;
+
+impl Sorter for PdqSort {
+    fn sort_by<T, F>(&self, list: &mut [T], mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let limit = usize::BITS - list.len().leading_zeros();
+        recurse(list, &mut compare, None, limit, &NoProbe);
+    }
+
+    fn sort_observed_by<T, F>(&self, list: &mut [T], compare: F, observer: &mut dyn SortObserver)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let probe = ObserverProbe::new(list, observer);
+        let limit = usize::BITS - list.len().leading_zeros();
+        recurse(list, &mut probe.comparing(compare), None, limit, &probe);
+    }
+}
+
+fn recurse<'a, T, F, P>(
+    mut list: &'a mut [T],
+    compare: &mut F,
+    mut predecessor: Option<&'a T>,
+    mut limit: u32,
+    probe: &P,
+) where
+    F: FnMut(&T, &T) -> Ordering,
+    P: Probe,
+{
+    let mut was_balanced = true;
+    let mut was_partitioned = true;
+    loop {
+        let len = list.len();
+        if len <= INSERTION_SORT_CUTOFF {
+            insertion_sort(list, compare, probe);
+            return;
+        }
+        if limit == 0 {
+            heap_sort(list, compare, probe);
+            return;
+        }
+        if !was_balanced {
+            break_patterns(list, probe);
+            limit -= 1;
+        }
+
+        let (pivot, likely_sorted) = choose_pivot(list, compare, probe);
+        if was_balanced
+            && was_partitioned
+            && likely_sorted
+            && partial_insertion_sort(list, compare, probe)
+        {
+            return;
+        }
+        if let Some(predecessor) = predecessor {
+            if compare(predecessor, &list[pivot]) != Ordering::Less {
+                let mid = partition_equal(list, pivot, compare, probe);
+                list = &mut std::mem::take(&mut list)[mid..];
+                continue;
+            }
+        }
+
+        let (mid, already_partitioned) = partition(list, pivot, compare, probe);
+        was_balanced = std::cmp::min(mid, len - mid) >= len / 8;
+        was_partitioned = already_partitioned;
+        let (left, right) = std::mem::take(&mut list).split_at_mut(mid);
+        let (pivot, right) = right.split_at_mut(1);
+        let pivot = &pivot[0];
+        if left.len() < right.len() {
+            recurse(left, compare, predecessor, limit, probe);
+            list = right;
+            predecessor = Some(pivot);
+        } else {
+            recurse(right, compare, Some(pivot), limit, probe);
+            list = left;
+        }
+    }
+}
+
+/// Returns the index of the chosen pivot, and whether the list is likely already sorted.
+/// When the samples look reverse-sorted, the list is reversed, so that it becomes likely sorted.
+fn choose_pivot<T, F, P>(list: &mut [T], compare: &mut F, probe: &P) -> (usize, bool)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    P: Probe,
+{
+    const SHORTEST_MEDIAN_OF_MEDIANS: usize = 50;
+    const MAX_SWAPS: usize = 4 * 3;
+
+    let len = list.len();
+    let mut a = len / 4;
+    let mut b = len / 4 * 2;
+    let mut c = len / 4 * 3;
+    let mut swaps = 0;
+    {
+        let list: &[T] = list;
+        let mut sort2 = |a: &mut usize, b: &mut usize| {
+            if compare(&list[*b], &list[*a]) == Ordering::Less {
+                std::mem::swap(a, b);
+                swaps += 1;
+            }
+        };
+        let mut sort3 = |a: &mut usize, b: &mut usize, c: &mut usize| {
+            sort2(a, b);
+            sort2(b, c);
+            sort2(a, b);
+        };
+        if len >= SHORTEST_MEDIAN_OF_MEDIANS {
+            for index in [&mut a, &mut b, &mut c] {
+                let middle = *index;
+                sort3(&mut (middle - 1), index, &mut (middle + 1));
+            }
+        }
+        sort3(&mut a, &mut b, &mut c);
+    }
+    if swaps < MAX_SWAPS {
+        (b, swaps == 0)
+    } else {
+        reverse(list, probe);
+        (len - 1 - b, true)
+    }
+}
+
+/// Sorts a list which is likely already sorted by fixing at most a few misplaced items, and returns whether it
+/// succeeded. Gives up straight away on short lists, which are cheap to partition anyway.
+fn partial_insertion_sort<T, F, P>(list: &mut [T], compare: &mut F, probe: &P) -> bool
+where
+    F: FnMut(&T, &T) -> Ordering,
+    P: Probe,
+{
+    const MAX_STEPS: usize = 5;
+    const SHORTEST_SHIFTING: usize = 50;
+
+    let len = list.len();
+    let mut i = 1;
+    for _ in 0..MAX_STEPS {
+        while i < len && compare(&list[i], &list[i - 1]) != Ordering::Less {
+            i += 1;
+        }
+        if i == len {
+            return true;
+        }
+        if len < SHORTEST_SHIFTING {
+            return false;
+        }
+        swap(list, i - 1, i, probe);
+        let mut j = i - 1;
+        while j > 0 && compare(&list[j], &list[j - 1]) == Ordering::Less {
+            swap(list, j, j - 1, probe);
+            j -= 1;
+        }
+        let mut j = i;
+        while j + 1 < len && compare(&list[j + 1], &list[j]) == Ordering::Less {
+            swap(list, j, j + 1, probe);
+            j += 1;
+        }
+    }
+    false
+}
+
+/// Partitions the list around the item at index `pivot`, and returns the final index of the pivot, along with
+/// whether the list was already partitioned.
+/// Items before the returned index are smaller than the pivot, items after it are not smaller than the pivot.
+fn partition<T, F, P>(list: &mut [T], pivot: usize, compare: &mut F, probe: &P) -> (usize, bool)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    P: Probe,
+{
+    swap(list, 0, pivot, probe);
+    let (pivot, rest) = list.split_at_mut(1);
+    let pivot = &pivot[0];
+    let mut l = 0;
+    let mut r = rest.len();
+    while l < r && compare(&rest[l], pivot) == Ordering::Less {
+        l += 1;
+    }
+    while l < r && compare(&rest[r - 1], pivot) != Ordering::Less {
+        r -= 1;
+    }
+    let mid = l + partition_in_blocks(&mut rest[l..r], pivot, compare, probe);
+    swap(list, 0, mid, probe);
+    (mid, l >= r)
+}
+
+/// Partitions the list into the items smaller than the pivot, followed by the others, and returns the number of the
+/// former. Misplaced items are looked for a block at a time on both sides, recording their offsets first, so that
+/// the comparisons don't drive any branch, and then swapped in pairs.
+fn partition_in_blocks<T, F, P>(list: &mut [T], pivot: &T, compare: &mut F, probe: &P) -> usize
+where
+    F: FnMut(&T, &T) -> Ordering,
+    P: Probe,
+{
+    let mut l = 0;
+    let mut block_l = BLOCK;
+    let mut offsets_l = [0u8; BLOCK];
+    let mut start_l = 0;
+    let mut end_l = 0;
+
+    let mut r = list.len();
+    let mut block_r = BLOCK;
+    let mut offsets_r = [0u8; BLOCK];
+    let mut start_r = 0;
+    let mut end_r = 0;
+
+    loop {
+        let is_done = r - l <= 2 * BLOCK;
+        if is_done {
+            // The remaining items are split between the two sides, minus a block which still has offsets to swap.
+            let mut remaining = r - l;
+            if start_l < end_l || start_r < end_r {
+                remaining -= BLOCK;
+            }
+            if start_l < end_l {
+                block_r = remaining;
+            } else if start_r < end_r {
+                block_l = remaining;
+            } else {
+                block_l = remaining / 2;
+                block_r = remaining - block_l;
+            }
+        }
+
+        if start_l == end_l {
+            start_l = 0;
+            end_l = 0;
+            for i in 0..block_l {
+                offsets_l[end_l] = i as u8;
+                end_l += (compare(&list[l + i], pivot) != Ordering::Less) as usize;
+            }
+        }
+        if start_r == end_r {
+            start_r = 0;
+            end_r = 0;
+            for i in 0..block_r {
+                offsets_r[end_r] = i as u8;
+                end_r += (compare(&list[r - 1 - i], pivot) == Ordering::Less) as usize;
+            }
+        }
+
+        let count = std::cmp::min(end_l - start_l, end_r - start_r);
+        for k in 0..count {
+            swap(
+                list,
+                l + offsets_l[start_l + k] as usize,
+                r - 1 - offsets_r[start_r + k] as usize,
+                probe,
+            );
+        }
+        start_l += count;
+        start_r += count;
+
+        if start_l == end_l {
+            l += block_l;
+        }
+        if start_r == end_r {
+            r -= block_r;
+        }
+        if is_done {
+            break;
+        }
+    }
+
+    if start_l < end_l {
+        // Items not smaller than the pivot are left in the last left block: move them to the right end.
+        while start_l < end_l {
+            end_l -= 1;
+            swap(list, l + offsets_l[end_l] as usize, r - 1, probe);
+            r -= 1;
+        }
+        r
+    } else {
+        // Items smaller than the pivot are left in the last right block: move them to the left end.
+        while start_r < end_r {
+            end_r -= 1;
+            swap(list, l, r - 1 - offsets_r[end_r] as usize, probe);
+            l += 1;
+        }
+        l
+    }
+}
+
+/// Partitions the list into the items equal to the item at index `pivot`, followed by the items bigger than it, and
+/// returns the number of the former. Every item of the list must be at least as big as the pivot.
+fn partition_equal<T, F, P>(list: &mut [T], pivot: usize, compare: &mut F, probe: &P) -> usize
+where
+    F: FnMut(&T, &T) -> Ordering,
+    P: Probe,
+{
+    swap(list, 0, pivot, probe);
+    let (pivot, rest) = list.split_at_mut(1);
+    let pivot = &pivot[0];
+    let mut l = 0;
+    let mut r = rest.len();
+    loop {
+        while l < r && compare(pivot, &rest[l]) != Ordering::Less {
+            l += 1;
+        }
+        while l < r && compare(pivot, &rest[r - 1]) == Ordering::Less {
+            r -= 1;
+        }
+        if l >= r {
+            break;
+        }
+        r -= 1;
+        swap(rest, l, r, probe);
+        l += 1;
+    }
+    l + 1
+}
+
+/// Swaps a few items around the middle of the list with pseudo-random positions, to break the patterns which caused
+/// an unbalanced partition.
+fn break_patterns<T, P: Probe>(list: &mut [T], probe: &P) {
+    let len = list.len();
+    if len < 8 {
+        return;
+    }
+    let mut random = len as u32;
+    let modulus = len.next_power_of_two();
+    let middle = len / 4 * 2;
+    for i in 0..3 {
+        random ^= random << 13;
+        random ^= random >> 17;
+        random ^= random << 5;
+        let mut other = random as usize & (modulus - 1);
+        if other >= len {
+            other -= len;
+        }
+        swap(list, middle - 1 + i, other, probe);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sorting::{
+        disorder::is_sorted,
+        instrument,
+        pdq_sort::PdqSort,
+        sort_unstable,
+        tests::{
+            adversarial_test, panic_safety_test, pseudo_random_list, pseudo_random_test,
+            sorting_test,
+        },
+    };
+
+    #[test]
+    fn pdq_sort_test() {
+        sorting_test(PdqSort);
+        pseudo_random_test(PdqSort);
+        adversarial_test(PdqSort);
+    }
+
+    #[test]
+    fn pdq_sort_panic_safety() {
+        panic_safety_test(PdqSort);
+    }
+
+    #[test]
+    fn linear_on_patterns() {
+        let len = 10_000;
+        let mut sorted: Vec<usize> = (0..len).collect();
+        assert!(instrument(&PdqSort, &mut sorted).comparisons < 2 * len);
+        let mut reverse_sorted: Vec<usize> = (0..len).rev().collect();
+        assert!(instrument(&PdqSort, &mut reverse_sorted).comparisons < 2 * len);
+        assert_eq!(reverse_sorted, sorted);
+        let mut few_keys = pseudo_random_list(len, 4, 3);
+        assert!(instrument(&PdqSort, &mut few_keys).comparisons < 4 * len);
+        assert!(is_sorted(&few_keys));
+    }
+
+    #[test]
+    fn large_lists() {
+        let mut list = pseudo_random_list(100_000, 5, u64::MAX);
+        let mut expected = list.clone();
+        expected.sort_unstable();
+        sort_unstable(&mut list);
+        assert_eq!(list, expected);
+    }
+}
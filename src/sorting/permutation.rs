@@ -0,0 +1,445 @@
+use std::{cmp::Ordering, collections::HashMap};
+
+use crate::sorting::{
+    instrument::{swap, NoProbe, Probe},
+    random::Rng,
+    Sorter, TimSort,
+};
+
+///     ## Algorithm
+///     - This shuffling algorithm, by Fisher and Yates, popularized by Knuth as Algorithm P, permutes the list L
+///       uniformly at random: each of the `n!` permutations is equally likely, provided the `rng` is uniform.
+///     - For each index i, from `n - 1` down to 1, `L[i]` is swapped with `L[j]`, for a random j in `0..=i`, so that
+///       `L[i..]` holds a uniform random sample of the items, in uniform random order.
+///     ## Complexity
+///     - It draws `n - 1` random numbers and runs at most `n - 1` swaps.
+///     - Therefore, Time Complexity is `O(n)` and Space Complexity is `O(1)`, since the algorithm runs in place.
+pub fn shuffle<T, R: Rng + ?Sized>(list: &mut [T], rng: &mut R) {
+    shuffle_probed(list, rng, &NoProbe);
+}
+
+/// Shuffles the list like `shuffle`, and reports its swaps to the `probe`.
+pub(crate) fn shuffle_probed<T, R, P>(list: &mut [T], rng: &mut R, probe: &P)
+where
+    R: Rng + ?Sized,
+    P: Probe,
+{
+    for i in (1..list.len()).rev() {
+        let j = rng.below(i + 1);
+        if j != i {
+            swap(list, i, j, probe);
+        }
+    }
+}
+
+///     ## Algorithm
+///     - This sampling algorithm picks k distinct items of the list L, uniformly at random, and returns them in random
+///       order: each of the `n! / (n - k)!` ordered samples is equally likely.
+///     - It runs the first k iterations of a Fisher-Yates shuffle on the indexes of L, from the front, without
+///       materializing them: only the indexes displaced by a swap are stored, in a map.
+///     ## Complexity
+///     - Time Complexity is `O(k)` and Space Complexity is `O(k)`, regardless of n.
+///     ## Panics
+///     - When k is bigger than the length of the list.
+pub fn sample_without_replacement<'a, T, R>(list: &'a [T], k: usize, rng: &mut R) -> Vec<&'a T>
+where
+    R: Rng + ?Sized,
+{
+    let len = list.len();
+    if k > len {
+        panic!("cannot sample {} items out of {}", k, len);
+    }
+    let mut displaced: HashMap<usize, usize> = HashMap::with_capacity(k);
+    (0..k)
+        .map(|i| {
+            let j = i + rng.below(len - i);
+            let at_i = displaced.get(&i).copied().unwrap_or(i);
+            let at_j = displaced.insert(j, at_i).unwrap_or(j);
+            &list[at_j]
+        })
+        .collect()
+}
+
+/// Returns a permutation of `0..n`, chosen uniformly at random with `shuffle`.
+pub fn random_permutation<R: Rng + ?Sized>(n: usize, rng: &mut R) -> Vec<usize> {
+    let mut permutation: Vec<usize> = (0..n).collect();
+    shuffle(&mut permutation, rng);
+    permutation
+}
+
+/// Rearranges the list into the next permutation in lexicographic order, and returns whether there was one.
+/// See `next_permutation_by`.
+pub fn next_permutation<T: Ord>(list: &mut [T]) -> bool {
+    next_permutation_by(list, T::cmp)
+}
+
+///     ## Algorithm
+///     - This algorithm, by Narayana Pandita, rearranges the list L into the smallest permutation of its items which
+///       is bigger than L in lexicographic order, according to the order defined by the `compare` function.
+///     - It looks for the last index i such that `L[i]` is smaller than `L[i + 1]`: `L[i + 1..]` is then in descending
+///       order, and is the biggest permutation of its items. When there is no such index, L is the last permutation:
+///       it is reversed into the first one, sorted, and false is returned.
+///     - Otherwise `L[i]` is swapped with the last item of `L[i + 1..]` bigger than it, and `L[i + 1..]` is reversed,
+///       into ascending order.
+///     - Each call moves to a strictly bigger permutation, so, starting from a sorted list, it visits each distinct
+///       permutation of a list with duplicates exactly once.
+///     ## Complexity
+///     - Time Complexity is `O(n)` in the worst case and `O(1)` amortized over all the permutations, and Space
+///       Complexity is `O(1)`.
+pub fn next_permutation_by<T, F>(list: &mut [T], mut compare: F) -> bool
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let Some(i) = (1..list.len())
+        .rev()
+        .find(|&i| compare(&list[i - 1], &list[i]) == Ordering::Less)
+        .map(|i| i - 1)
+    else {
+        list.reverse();
+        return false;
+    };
+    let j = (i + 1..list.len())
+        .rev()
+        .find(|&j| compare(&list[i], &list[j]) == Ordering::Less)
+        .unwrap();
+    list.swap(i, j);
+    list[i + 1..].reverse();
+    true
+}
+
+/// Returns the inverse of the permutation `order` of `0..n`: the permutation which maps `order[i]` back to i. Sorting
+/// a list with `apply_permutation` and the order returned by `argsort`, and then with its inverse, restores it.
+///     ## Panics
+///     - When order is not a permutation of `0..n`.
+pub fn inverse_permutation(order: &[usize]) -> Vec<usize> {
+    check_permutation(order);
+    let mut inverse = vec![0; order.len()];
+    for (i, &source) in order.iter().enumerate() {
+        inverse[source] = i;
+    }
+    inverse
+}
+
+/// Rearranges the list so that the item at index `i` is the item which was at index `order[i]`, following the cycles
+/// of the permutation, so that each item is moved into its final position by a single swap.
+///     ## Panics
+///     - When order is not a permutation of `0..n`, where n is the length of the list.
+pub fn apply_permutation<T>(list: &mut [T], order: &[usize]) {
+    if order.len() != list.len() {
+        panic!(
+            "order should have the same length as the list, {}, but has length {}",
+            list.len(),
+            order.len()
+        );
+    }
+    check_permutation(order);
+    apply_permutation_unchecked(list, order, &NoProbe);
+}
+
+/// Rearranges the list like `apply_permutation`, without checking that order is a permutation of `0..n`, for the
+/// sorts which compute it themselves, and reports its swaps to the `probe`. Any other order still returns, with the
+/// items of the list in an unspecified order, unless it holds an index out of bounds, which panics.
+pub(crate) fn apply_permutation_unchecked<T, P: Probe>(list: &mut [T], order: &[usize], probe: &P) {
+    let mut placed = vec![false; list.len()];
+    for start in 0..list.len() {
+        if placed[start] {
+            continue;
+        }
+        let mut i = start;
+        loop {
+            placed[i] = true;
+            let source = order[i];
+            // In a permutation, the only placed index a cycle can lead to is its start. Stopping at any placed index
+            // also ends the cycles of an order which repeats indexes.
+            if placed[source] {
+                break;
+            }
+            swap(list, i, source, probe);
+            i = source;
+        }
+    }
+}
+
+/// Returns the permutation which sorts the list in ascending order with the given `sorter`. See `argsort_by`.
+pub fn argsort<S: Sorter, T: Ord>(sorter: &S, list: &[T]) -> Vec<usize> {
+    argsort_by(sorter, list, T::cmp)
+}
+
+/// Returns the permutation which sorts the list with the given `sorter`, according to the order defined by the
+/// `compare` function, without rearranging the list: the indexes of its items, in sorted order, so that the
+/// i-th smallest item is `list[order[i]]`. The list can then be sorted with `apply_permutation`.
+/// Indexes of equal items are in their original order when the sorter is stable.
+///
+/// ```
+/// use more_rust_structures::sorting::{apply_permutation, argsort_by, MergeSort};
+///
+/// let mut list = ["pear", "fig", "apple"];
+/// let order = argsort_by(&MergeSort, &list, |a, b| a.len().cmp(&b.len()));
+/// assert_eq!(order, [1, 0, 2]);
+/// apply_permutation(&mut list, &order);
+/// assert_eq!(list, ["fig", "pear", "apple"]);
+/// ```
+pub fn argsort_by<S, T, F>(sorter: &S, list: &[T], mut compare: F) -> Vec<usize>
+where
+    S: Sorter,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let mut order: Vec<usize> = (0..list.len()).collect();
+    sorter.sort_by(&mut order, |&a, &b| compare(&list[a], &list[b]));
+    order
+}
+
+/// Returns the permutation which sorts the list with the given `sorter`, in ascending order of the key extracted by
+/// the `key` function. See `argsort_by`.
+pub fn argsort_by_key<S, T, K, F>(sorter: &S, list: &[T], mut key: F) -> Vec<usize>
+where
+    S: Sorter,
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    argsort_by(sorter, list, |a, b| key(a).cmp(&key(b)))
+}
+
+/// Returns the permutation which stably sorts the list in ascending order with `TimSort`: indexes of equal items are
+/// in ascending order. See `argsort_by`.
+pub fn stable_argsort<T: Ord>(list: &[T]) -> Vec<usize> {
+    argsort(&TimSort, list)
+}
+
+/// Returns the permutation which stably sorts the list with `TimSort`, according to the order defined by the
+/// `compare` function. See `argsort_by`.
+pub fn stable_argsort_by<T, F>(list: &[T], compare: F) -> Vec<usize>
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    argsort_by(&TimSort, list, compare)
+}
+
+/// Returns the permutation which stably sorts the list with `TimSort`, in ascending order of the key extracted by the
+/// `key` function. See `argsort_by`.
+pub fn stable_argsort_by_key<T, K, F>(list: &[T], key: F) -> Vec<usize>
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    argsort_by_key(&TimSort, list, key)
+}
+
+/// Panics when `permutation` is not a permutation of `0..n`, where n is its length.
+pub(crate) fn check_permutation(permutation: &[usize]) {
+    let mut seen = vec![false; permutation.len()];
+    for &position in permutation {
+        if position >= seen.len() || seen[position] {
+            panic!(
+                "{:?} is not a permutation of 0..{}",
+                permutation,
+                seen.len()
+            );
+        }
+        seen[position] = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::sorting::{
+        disorder::is_sorted,
+        instrument::NoProbe,
+        permutation::{
+            apply_permutation, apply_permutation_unchecked, argsort, argsort_by, argsort_by_key,
+            inverse_permutation, next_permutation, random_permutation, sample_without_replacement,
+            shuffle, stable_argsort, stable_argsort_by, stable_argsort_by_key,
+        },
+        random::{SeedableRng, XorShift64},
+        tests::pseudo_random_list,
+        BottomUpMergeSort, HeapSort, InsertionSort, MergeSort, PdqSort, ShellSort, TimSort,
+    };
+
+    #[test]
+    fn shuffle_is_uniform() {
+        let mut rng = XorShift64::seed_from_u64(3);
+        let mut counts: HashMap<Vec<u8>, usize> = HashMap::new();
+        for _ in 0..24_000 {
+            let mut list = [0, 1, 2, 3];
+            shuffle(&mut list, &mut rng);
+            *counts.entry(list.to_vec()).or_default() += 1;
+        }
+        assert_eq!(counts.len(), 24);
+        assert!(
+            counts.values().all(|&count| (850..1150).contains(&count)),
+            "{:?}",
+            counts
+        );
+    }
+
+    #[test]
+    fn shuffle_is_reproducible() {
+        let list = pseudo_random_list(100, 1, 1000);
+        let mut a = list.clone();
+        let mut b = list.clone();
+        shuffle(&mut a, &mut XorShift64::seed_from_u64(9));
+        shuffle(&mut b, &mut XorShift64::seed_from_u64(9));
+        assert_eq!(a, b);
+        assert_ne!(a, list);
+        a.sort();
+        let mut sorted = list;
+        sorted.sort();
+        assert_eq!(a, sorted);
+        shuffle::<u8, _>(&mut [], &mut XorShift64::seed_from_u64(9));
+    }
+
+    #[test]
+    fn sample_without_replacement_test() {
+        let mut rng = XorShift64::seed_from_u64(5);
+        let list: Vec<usize> = (0..10).collect();
+        let mut counts = [[0; 10]; 3];
+        for _ in 0..30_000 {
+            let sample = sample_without_replacement(&list, 3, &mut rng);
+            assert!(sample[0] != sample[1] && sample[1] != sample[2] && sample[0] != sample[2]);
+            for (position, &&item) in sample.iter().enumerate() {
+                counts[position][item] += 1;
+            }
+        }
+        for position in counts {
+            assert!(
+                position
+                    .iter()
+                    .all(|&count| (2_700..3_300).contains(&count)),
+                "{:?}",
+                position
+            );
+        }
+        assert!(sample_without_replacement(&list, 0, &mut rng).is_empty());
+        let mut everything: Vec<usize> = sample_without_replacement(&list, 10, &mut rng)
+            .into_iter()
+            .copied()
+            .collect();
+        everything.sort();
+        assert_eq!(everything, list);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot sample 4 items out of 3")]
+    fn sample_too_many() {
+        sample_without_replacement(&[1, 2, 3], 4, &mut XorShift64::seed_from_u64(1));
+    }
+
+    #[test]
+    fn next_permutation_test() {
+        let mut list = [1, 2, 3, 4];
+        let mut permutations = vec![list];
+        while next_permutation(&mut list) {
+            permutations.push(list);
+        }
+        assert_eq!(permutations.len(), 24);
+        assert!(permutations.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(list, [1, 2, 3, 4]);
+
+        let mut duplicates = [1, 1, 2, 2];
+        let mut count = 1;
+        while next_permutation(&mut duplicates) {
+            count += 1;
+        }
+        assert_eq!(count, 6);
+
+        let mut empty: [u8; 0] = [];
+        assert!(!next_permutation(&mut empty));
+        assert!(!next_permutation(&mut [1]));
+    }
+
+    #[test]
+    fn inverse_and_apply_permutation() {
+        let mut rng = XorShift64::seed_from_u64(11);
+        for n in [0, 1, 2, 10, 100] {
+            let order = random_permutation(n, &mut rng);
+            let inverse = inverse_permutation(&order);
+            assert_eq!(inverse_permutation(&inverse), order);
+            let original: Vec<usize> = (100..100 + n).collect();
+            let mut list = original.clone();
+            apply_permutation(&mut list, &order);
+            for i in 0..n {
+                assert_eq!(list[i], original[order[i]]);
+            }
+            apply_permutation(&mut list, &inverse);
+            assert_eq!(list, original);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "[1, 1, 0] is not a permutation of 0..3")]
+    fn apply_non_permutation() {
+        apply_permutation(&mut [4, 5, 6], &[1, 1, 0]);
+    }
+
+    #[test]
+    fn apply_non_permutation_unchecked() {
+        let mut list = [4, 5, 6];
+        apply_permutation_unchecked(&mut list, &[1, 1, 0], &NoProbe);
+        list.sort();
+        assert_eq!(list, [4, 5, 6]);
+    }
+
+    #[test]
+    #[should_panic(expected = "order should have the same length as the list")]
+    fn apply_permutation_of_other_length() {
+        apply_permutation(&mut [4, 5, 6], &[1, 0]);
+    }
+
+    #[test]
+    fn argsort_test() {
+        let list = pseudo_random_list(500, 2, 50);
+        let expected = argsort(&MergeSort, &list);
+        for order in [
+            argsort(&InsertionSort, &list),
+            argsort(&BottomUpMergeSort, &list),
+            argsort(&TimSort, &list),
+        ] {
+            assert_eq!(order, expected);
+        }
+        for order in [
+            expected.clone(),
+            argsort(&HeapSort, &list),
+            argsort(&ShellSort::default(), &list),
+            argsort(&PdqSort, &list),
+        ] {
+            let mut sorted = list.clone();
+            apply_permutation(&mut sorted, &order);
+            assert!(is_sorted(&sorted));
+        }
+        // Stable sorters keep the indexes of equal items in ascending order.
+        assert!((1..list.len()).all(|i| {
+            list[expected[i - 1]] < list[expected[i]] || expected[i - 1] < expected[i]
+        }));
+
+        let words = ["ccc", "a", "bb", "dd"];
+        assert_eq!(
+            argsort_by_key(&MergeSort, &words, |word| word.len()),
+            [1, 2, 3, 0]
+        );
+        assert_eq!(
+            argsort_by(&MergeSort, &words, |a, b| b.cmp(a)),
+            [3, 0, 2, 1]
+        );
+    }
+
+    #[test]
+    fn stable_argsort_test() {
+        let list = pseudo_random_list(300, 8, 10);
+        let order = stable_argsort(&list);
+        assert!((1..order.len()).all(|i| {
+            list[order[i - 1]] < list[order[i]]
+                || (list[order[i - 1]] == list[order[i]] && order[i - 1] < order[i])
+        }));
+        let words = ["bb", "a", "cc", "d"];
+        assert_eq!(
+            stable_argsort_by_key(&words, |word| word.len()),
+            [1, 3, 0, 2]
+        );
+        assert_eq!(
+            stable_argsort_by(&words, |a, b| b.len().cmp(&a.len())),
+            [0, 2, 1, 3]
+        );
+    }
+}
@@ -0,0 +1,370 @@
+use std::cmp::Ordering;
+
+use crate::sorting::{
+    insertion_sort::insertion_sort,
+    instrument::{swap, NoProbe, ObserverProbe, Probe},
+    permutation::shuffle_probed,
+    random::XorShift64,
+    SortObserver, Sorter,
+};
+
+/// Sub-lists up to this length are sorted with `InsertionSort`, which is faster than partitioning on small inputs.
+const INSERTION_SORT_CUTOFF: usize = 10;
+
+///     ## Algorithm
+///     - This sorting algorithm first shuffles the list L uniformly at random, so that no input order, such as an already
+///       sorted list, can consistently produce bad partitions.
+///     - It then picks the first item of L as pivot and partitions L so that items before the pivot are not bigger than
+///       it and items after the pivot are not smaller than it. Both scans stop on items equal to the pivot, so lists
+///       with many duplicates are still split in balanced halves.
+///     - The two sides of the pivot are then sorted the same way.
+///     ## Complexity
+///     - The expected number of comparisons is around `1.39 * n * log2(n)`, whatever the input.
+///     - Therefore, expected Time Complexity is `O(n log n)`. The worst case is `O(n^2)`, but it is extremely unlikely.
+///     - Recursion always goes into the smaller side first, so Space Complexity is `O(log n)`.
+pub struct RandomizedQuickSort;
+
+impl Sorter for RandomizedQuickSort {
+    fn sort_by<T, F>(&self, list: &mut [T], mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        shuffle_probed(list, &mut XorShift64::from_entropy(), &NoProbe);
+        randomized_sort(list, &mut compare, &NoProbe);
+    }
+
+    fn sort_observed_by<T, F>(&self, list: &mut [T], compare: F, observer: &mut dyn SortObserver)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let probe = ObserverProbe::new(list, observer);
+        shuffle_probed(list, &mut XorShift64::from_entropy(), &probe);
+        randomized_sort(list, &mut probe.comparing(compare), &probe);
+    }
+}
+
+fn randomized_sort<T, F, P>(mut list: &mut [T], compare: &mut F, probe: &P)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    P: Probe,
+{
+    while list.len() > 1 {
+        let pivot = partition(list, compare, probe);
+        let (left, right) = list.split_at_mut(pivot);
+        let right = &mut right[1..];
+        if left.len() < right.len() {
+            randomized_sort(left, compare, probe);
+            list = right;
+        } else {
+            randomized_sort(right, compare, probe);
+            list = left;
+        }
+    }
+}
+
+///     ## Algorithm
+///     - This sorting algorithm works like `RandomizedQuickSort`, without shuffling the list L.
+///     - The pivot is the median of the items at one quarter, half and three quarters of L, which gives good partitions
+///       on sorted, reverse-sorted and most real-world lists, and avoids the cost of the shuffle.
+///     - Sub-lists of at most `INSERTION_SORT_CUTOFF` items are sorted with `InsertionSort`.
+///     ## Complexity
+///     - Time Complexity is `O(n log n)` on average. Specifically crafted inputs can still lead to `O(n^2)`.
+///     - Recursion always goes into the smaller side first, so Space Complexity is `O(log n)`.
+pub struct MedianOfThreeQuickSort;
+
+impl Sorter for MedianOfThreeQuickSort {
+    fn sort_by<T, F>(&self, list: &mut [T], mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        median_of_three_sort(list, &mut compare, &NoProbe);
+    }
+
+    fn sort_observed_by<T, F>(&self, list: &mut [T], compare: F, observer: &mut dyn SortObserver)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let probe = ObserverProbe::new(list, observer);
+        median_of_three_sort(list, &mut probe.comparing(compare), &probe);
+    }
+}
+
+fn median_of_three_sort<T, F, P>(mut list: &mut [T], compare: &mut F, probe: &P)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    P: Probe,
+{
+    while list.len() > INSERTION_SORT_CUTOFF {
+        let len = list.len();
+        let median = median_of_three(list, len / 4, len / 2, 3 * len / 4, compare);
+        swap(list, 0, median, probe);
+        let pivot = partition(list, compare, probe);
+        let (left, right) = list.split_at_mut(pivot);
+        let right = &mut right[1..];
+        if left.len() < right.len() {
+            median_of_three_sort(left, compare, probe);
+            list = right;
+        } else {
+            median_of_three_sort(right, compare, probe);
+            list = left;
+        }
+    }
+    insertion_sort(list, compare, probe);
+}
+
+///     ## Algorithm
+///     - This sorting algorithm uses Dijkstra's 3-way partitioning: the list L is split in the items smaller than the
+///       pivot, `L[..lt]`, the items equal to the pivot, `L[lt..gt]`, and the items bigger than the pivot, `L[gt..]`.
+///     - L is scanned once, from left to right, moving each item to the part it belongs to.
+///     - Only `L[..lt]` and `L[gt..]` are sorted recursively, so items equal to the pivot are never compared again.
+///     - The pivot is chosen as in `MedianOfThreeQuickSort`, and sub-lists of at most
+///       `INSERTION_SORT_CUTOFF` items are sorted with `InsertionSort`.
+///     ## Complexity
+///     - Time Complexity is `O(n log n)` on average, and becomes linear when the list only contains a constant number
+///       of distinct keys.
+///     - Recursion always goes into the smaller side first, so Space Complexity is `O(log n)`.
+pub struct ThreeWayQuickSort;
+
+impl Sorter for ThreeWayQuickSort {
+    fn sort_by<T, F>(&self, list: &mut [T], mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        three_way_sort(list, &mut compare, &NoProbe);
+    }
+
+    fn sort_observed_by<T, F>(&self, list: &mut [T], compare: F, observer: &mut dyn SortObserver)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let probe = ObserverProbe::new(list, observer);
+        three_way_sort(list, &mut probe.comparing(compare), &probe);
+    }
+}
+
+fn three_way_sort<T, F, P>(mut list: &mut [T], compare: &mut F, probe: &P)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    P: Probe,
+{
+    while list.len() > INSERTION_SORT_CUTOFF {
+        let len = list.len();
+        let median = median_of_three(list, len / 4, len / 2, 3 * len / 4, compare);
+        swap(list, 0, median, probe);
+        let (lt, gt) = three_way_partition(list, compare, probe);
+        let (left, rest) = list.split_at_mut(lt);
+        let right = &mut rest[gt - lt..];
+        if left.len() < right.len() {
+            three_way_sort(left, compare, probe);
+            list = right;
+        } else {
+            three_way_sort(right, compare, probe);
+            list = left;
+        }
+    }
+    insertion_sort(list, compare, probe);
+}
+
+///     ## Algorithm
+///     - This sorting algorithm, by Vladimir Yaroslavskiy, partitions the list L around two pivots `p <= q`, into the
+///       items smaller than `p`, the items between `p` and `q` and the items bigger than `q`, and sorts the three
+///       parts recursively.
+///     - The pivots are taken at one third and two thirds of L, which gives balanced partitions on sorted and
+///       reverse-sorted lists.
+///     - When `p` and `q` are equal, the middle part only contains items equal to them and is not sorted again.
+///     - Sub-lists of at most `INSERTION_SORT_CUTOFF` items are sorted with `InsertionSort`.
+///     ## Complexity
+///     - Time Complexity is `O(n log n)` on average, with fewer memory accesses than single-pivot quicksort.
+///     - Recursion only goes into the two smaller parts, so Space Complexity is `O(log n)`.
+pub struct DualPivotQuickSort;
+
+impl Sorter for DualPivotQuickSort {
+    fn sort_by<T, F>(&self, list: &mut [T], mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        dual_pivot_sort(list, &mut compare, &NoProbe);
+    }
+
+    fn sort_observed_by<T, F>(&self, list: &mut [T], compare: F, observer: &mut dyn SortObserver)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let probe = ObserverProbe::new(list, observer);
+        dual_pivot_sort(list, &mut probe.comparing(compare), &probe);
+    }
+}
+
+fn dual_pivot_sort<T, F, P>(mut list: &mut [T], compare: &mut F, probe: &P)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    P: Probe,
+{
+    while list.len() > INSERTION_SORT_CUTOFF {
+        let len = list.len();
+        swap(list, 0, len / 3, probe);
+        swap(list, len - 1, 2 * len / 3, probe);
+        if compare(&list[len - 1], &list[0]) == Ordering::Less {
+            swap(list, 0, len - 1, probe);
+        }
+        let mut lt = 1;
+        let mut gt = len - 2;
+        let mut i = 1;
+        while i <= gt {
+            if compare(&list[i], &list[0]) == Ordering::Less {
+                swap(list, i, lt, probe);
+                lt += 1;
+                i += 1;
+            } else if compare(&list[len - 1], &list[i]) == Ordering::Less {
+                swap(list, i, gt, probe);
+                gt -= 1;
+            } else {
+                i += 1;
+            }
+        }
+        lt -= 1;
+        gt += 1;
+        swap(list, 0, lt, probe);
+        swap(list, len - 1, gt, probe);
+        let sort_middle = compare(&list[lt], &list[gt]) == Ordering::Less;
+        let (left, rest) = list.split_at_mut(lt);
+        let (middle, right) = rest[1..].split_at_mut(gt - lt - 1);
+        let right = &mut right[1..];
+        let middle: &mut [T] = if sort_middle { middle } else { &mut [] };
+        // The two smaller parts are sorted recursively, each of them holding at most half of the list, and the
+        // largest one is sorted by the next iteration.
+        let mut parts = [left, middle, right];
+        parts.sort_by_key(|part| part.len());
+        let [smallest, middle, largest] = parts;
+        dual_pivot_sort(smallest, compare, probe);
+        dual_pivot_sort(middle, compare, probe);
+        list = largest;
+    }
+    insertion_sort(list, compare, probe);
+}
+
+/// Partitions the list around its first item, and returns the final index of the pivot.
+/// Items before the returned index are not bigger than the pivot, items after it are not smaller than the pivot.
+pub(crate) fn partition<T, F, P>(list: &mut [T], compare: &mut F, probe: &P) -> usize
+where
+    F: FnMut(&T, &T) -> Ordering,
+    P: Probe,
+{
+    let last = list.len() - 1;
+    let mut i = 0;
+    let mut j = last + 1;
+    loop {
+        i += 1;
+        while i < last && compare(&list[i], &list[0]) == Ordering::Less {
+            i += 1;
+        }
+        j -= 1;
+        while j > 0 && compare(&list[0], &list[j]) == Ordering::Less {
+            j -= 1;
+        }
+        if i >= j {
+            break;
+        }
+        swap(list, i, j, probe);
+    }
+    swap(list, 0, j, probe);
+    j
+}
+
+/// Partitions the list around its first item, into the items smaller than the pivot, `list[..lt]`, the items equal to
+/// it, `list[lt..gt]`, and the items bigger than it, `list[gt..]`, and returns `(lt, gt)`.
+pub(crate) fn three_way_partition<T, F, P>(
+    list: &mut [T],
+    compare: &mut F,
+    probe: &P,
+) -> (usize, usize)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    P: Probe,
+{
+    let mut lt = 0;
+    let mut gt = list.len();
+    let mut i = 1;
+    while i < gt {
+        match compare(&list[i], &list[lt]) {
+            Ordering::Less => {
+                swap(list, lt, i, probe);
+                lt += 1;
+                i += 1;
+            }
+            Ordering::Greater => {
+                gt -= 1;
+                swap(list, i, gt, probe);
+            }
+            Ordering::Equal => i += 1,
+        }
+    }
+    (lt, gt)
+}
+
+/// Returns whichever of the indexes `a`, `b` and `c` holds the median of the three items.
+pub(crate) fn median_of_three<T, F>(
+    list: &[T],
+    a: usize,
+    b: usize,
+    c: usize,
+    compare: &mut F,
+) -> usize
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let less = |compare: &mut F, x: usize, y: usize| compare(&list[x], &list[y]) == Ordering::Less;
+    if less(compare, a, b) {
+        if less(compare, b, c) {
+            b
+        } else if less(compare, a, c) {
+            c
+        } else {
+            a
+        }
+    } else if less(compare, a, c) {
+        a
+    } else if less(compare, b, c) {
+        c
+    } else {
+        b
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sorting::{
+        quick_sort::{
+            DualPivotQuickSort, MedianOfThreeQuickSort, RandomizedQuickSort, ThreeWayQuickSort,
+        },
+        tests::{adversarial_test, pseudo_random_test, sorting_test},
+    };
+
+    #[test]
+    fn randomized_quick_sort_test() {
+        sorting_test(RandomizedQuickSort);
+        pseudo_random_test(RandomizedQuickSort);
+        adversarial_test(RandomizedQuickSort);
+    }
+
+    #[test]
+    fn median_of_three_quick_sort_test() {
+        sorting_test(MedianOfThreeQuickSort);
+        pseudo_random_test(MedianOfThreeQuickSort);
+        adversarial_test(MedianOfThreeQuickSort);
+    }
+
+    #[test]
+    fn three_way_quick_sort_test() {
+        sorting_test(ThreeWayQuickSort);
+        pseudo_random_test(ThreeWayQuickSort);
+        adversarial_test(ThreeWayQuickSort);
+    }
+
+    #[test]
+    fn dual_pivot_quick_sort_test() {
+        sorting_test(DualPivotQuickSort);
+        pseudo_random_test(DualPivotQuickSort);
+        adversarial_test(DualPivotQuickSort);
+    }
+}
@@ -0,0 +1,154 @@
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+};
+
+/// A source of uniformly distributed random numbers, as used by `shuffle`, `RandomizedQuickSort` and the other
+/// randomized algorithms of this module.
+/// With the `rand` feature, every `rand_core::RngCore` generator is an `Rng`.
+pub trait Rng {
+    /// Returns the next random number, uniformly distributed over all the values of u64.
+    fn next_u64(&mut self) -> u64;
+
+    /// Returns a random number uniformly distributed in `0..bound`.
+    /// It uses Lemire's multiply-and-shift, taking the high half of the product of a random u64 and `bound`, and
+    /// rejects the rare low halves which would make some results more likely than others.
+    ///     ## Panics
+    ///     - When bound is 0.
+    fn below(&mut self, bound: usize) -> usize {
+        if bound == 0 {
+            panic!("bound should be positive");
+        }
+        let bound = bound as u64;
+        let threshold = bound.wrapping_neg() % bound;
+        loop {
+            let product = self.next_u64() as u128 * bound as u128;
+            if product as u64 >= threshold {
+                return (product >> 64) as usize;
+            }
+        }
+    }
+}
+
+/// A random number generator which produces the same sequence of numbers every time it is created from the same
+/// seed, to make randomized tests and benchmarks reproducible.
+pub trait SeedableRng: Rng + Sized {
+    fn seed_from_u64(seed: u64) -> Self;
+}
+
+#[cfg(feature = "rand")]
+impl<R: rand_core::RngCore> Rng for R {
+    fn next_u64(&mut self) -> u64 {
+        rand_core::RngCore::next_u64(self)
+    }
+}
+
+/// Marsaglia's xorshift generator, with 64 bits of state and a period of `2^64 - 1`.
+/// It is fast and good enough to shuffle lists and pick pivots, but not for cryptography.
+///
+/// ```
+/// use more_rust_structures::sorting::{Rng, SeedableRng, XorShift64};
+///
+/// let mut a = XorShift64::seed_from_u64(42);
+/// let mut b = XorShift64::seed_from_u64(42);
+/// assert_eq!(a.next_u64(), b.next_u64());
+/// assert!(a.below(6) < 6);
+/// ```
+#[derive(Debug, Clone)]
+pub struct XorShift64 {
+    state: u64,
+}
+
+impl XorShift64 {
+    /// Creates a generator seeded from the random keys of the standard library's hasher, which differ on every run.
+    pub fn from_entropy() -> Self {
+        XorShift64::seed_from_u64(RandomState::new().build_hasher().finish())
+    }
+}
+
+impl SeedableRng for XorShift64 {
+    /// Scrambles the seed with the SplitMix64 finalizer, so that close seeds start far apart, and the state is never
+    /// 0, which xorshift could never leave.
+    fn seed_from_u64(seed: u64) -> Self {
+        let mut z = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        XorShift64 {
+            state: (z ^ (z >> 31)).max(1),
+        }
+    }
+}
+
+impl Rng for XorShift64 {
+    fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sorting::random::{Rng, SeedableRng, XorShift64};
+
+    #[test]
+    fn seeds_are_reproducible() {
+        let first: Vec<u64> = {
+            let mut rng = XorShift64::seed_from_u64(7);
+            (0..10).map(|_| rng.next_u64()).collect()
+        };
+        let mut rng = XorShift64::seed_from_u64(7);
+        assert_eq!((0..10).map(|_| rng.next_u64()).collect::<Vec<_>>(), first);
+        let mut other = XorShift64::seed_from_u64(8);
+        assert_ne!(other.next_u64(), first[0]);
+        assert_ne!(XorShift64::seed_from_u64(0).next_u64(), 0);
+    }
+
+    #[test]
+    fn below_is_uniform() {
+        let mut rng = XorShift64::seed_from_u64(1);
+        let mut counts = [0; 6];
+        for _ in 0..60_000 {
+            counts[rng.below(6)] += 1;
+        }
+        assert!(
+            counts.iter().all(|&count| (9_500..10_500).contains(&count)),
+            "{:?}",
+            counts
+        );
+        assert_eq!(rng.below(1), 0);
+        assert!(rng.below(usize::MAX) < usize::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "bound should be positive")]
+    fn below_zero() {
+        XorShift64::from_entropy().below(0);
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn rand_generators_are_rngs() {
+        struct Counter(u64);
+
+        impl rand_core::RngCore for Counter {
+            fn next_u32(&mut self) -> u32 {
+                rand_core::RngCore::next_u64(self) as u32
+            }
+
+            fn next_u64(&mut self) -> u64 {
+                self.0 += 1;
+                self.0
+            }
+
+            fn fill_bytes(&mut self, dest: &mut [u8]) {
+                rand_core::impls::fill_bytes_via_next(self, dest)
+            }
+        }
+
+        let mut counter = Counter(0);
+        assert_eq!(Rng::next_u64(&mut counter), 1);
+        assert_eq!(counter.below(10), 0);
+    }
+}
@@ -0,0 +1,574 @@
+use std::{cmp::Ordering, ops::Range};
+
+use crate::sorting::lsd_radix_sort::RadixKey;
+
+/// Returns the index of the first item of the sorted list not smaller than `value`, which is where `value` would be
+/// inserted before the items equal to it, or the length of the list when all the items are smaller.
+/// See `lower_bound_by`.
+pub fn lower_bound<T: Ord>(list: &[T], value: &T) -> usize {
+    lower_bound_by(list, value, T::cmp)
+}
+
+///     ## Algorithm
+///     - This search algorithm finds the index of the first item of the list L not smaller than `value`, according to
+///       the order defined by the `compare` function, which L has to be sorted by.
+///     - It keeps a range of L known to hold that index, starting from the whole of L and an extra slot past its end,
+///       and compares `value` with the middle item of the range, halving it at each step.
+///     - When L is not sorted, the returned index is unspecified, but within `0..=n`.
+///     ## Complexity
+///     - It runs at most `ceil(log2(n + 1))` comparisons.
+///     - Therefore, Time Complexity is `O(log n)` and Space Complexity is `O(1)`.
+pub fn lower_bound_by<T, F>(list: &[T], value: &T, mut compare: F) -> usize
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    partition_point(list, |item| compare(item, value) == Ordering::Less)
+}
+
+/// Returns the index of the first item of the list, sorted in ascending order of the key extracted by the `key`
+/// function, whose key is not smaller than `target`. See `lower_bound_by`.
+pub fn lower_bound_by_key<T, K, F>(list: &[T], target: &K, mut key: F) -> usize
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    partition_point(list, |item| key(item) < *target)
+}
+
+/// Returns the index of the first item of the sorted list bigger than `value`, which is where `value` would be
+/// inserted after the items equal to it, or the length of the list when no item is bigger. See `upper_bound_by`.
+pub fn upper_bound<T: Ord>(list: &[T], value: &T) -> usize {
+    upper_bound_by(list, value, T::cmp)
+}
+
+/// Returns the index of the first item of the list, sorted according to the order defined by the `compare` function,
+/// bigger than `value`. It is the binary search of `lower_bound_by`, with `value` moving past the items equal to it,
+/// and runs at most `ceil(log2(n + 1))` comparisons, each with `value` as first argument.
+pub fn upper_bound_by<T, F>(list: &[T], value: &T, mut compare: F) -> usize
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    partition_point(list, |item| compare(value, item) != Ordering::Less)
+}
+
+/// Returns the index of the first item of the list, sorted in ascending order of the key extracted by the `key`
+/// function, whose key is bigger than `target`. See `upper_bound_by`.
+pub fn upper_bound_by_key<T, K, F>(list: &[T], target: &K, mut key: F) -> usize
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    partition_point(list, |item| key(item) <= *target)
+}
+
+/// Returns the range of the indexes of the items of the sorted list equal to `value`, which is empty, and starts
+/// where `value` would be inserted, when there are none. See `equal_range_by`.
+pub fn equal_range<T: Ord>(list: &[T], value: &T) -> Range<usize> {
+    equal_range_by(list, value, T::cmp)
+}
+
+/// Returns the range of the indexes of the items of the list, sorted according to the order defined by the `compare`
+/// function, equal to `value`: from its `lower_bound_by` to its `upper_bound_by`. The upper bound is only searched
+/// for from the lower bound on, so it runs at most `2 * ceil(log2(n + 1))` comparisons.
+pub fn equal_range_by<T, F>(list: &[T], value: &T, mut compare: F) -> Range<usize>
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let start = lower_bound_by(list, value, &mut compare);
+    start..start + upper_bound_by(&list[start..], value, compare)
+}
+
+/// Returns the range of the indexes of the items of the list, sorted in ascending order of the key extracted by the
+/// `key` function, whose key is equal to `target`. See `equal_range_by`.
+pub fn equal_range_by_key<T, K, F>(list: &[T], target: &K, mut key: F) -> Range<usize>
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    let start = lower_bound_by_key(list, target, &mut key);
+    start..start + upper_bound_by_key(&list[start..], target, key)
+}
+
+/// Searches the sorted list for `value`, returning `Ok` with the index of the first item equal to it, or `Err` with
+/// the index where it would be inserted when there is none. See `exponential_search_by`.
+pub fn exponential_search<T: Ord>(list: &[T], value: &T) -> Result<usize, usize> {
+    exponential_search_by(list, value, T::cmp)
+}
+
+///     ## Algorithm
+///     - This search algorithm, also known as galloping search, by Bentley and Yao, looks for `value` in the list L,
+///       sorted according to the order defined by the `compare` function, starting from the head of L.
+///     - It compares `value` with the items at indexes 0, 1, 3, 7, ..., `2^k - 1`, until it finds one not smaller
+///       than it, and then runs a binary search between that item and the previous one probed.
+///     - It returns `Ok` with the index of the first item equal to `value`, as `lower_bound_by` does, or `Err` with
+///       the index where `value` would be inserted. Unlike `slice::binary_search`, the index is always the first one
+///       when several items are equal to `value`.
+///     ## Complexity
+///     - It runs at most around `2 * log2(i)` comparisons, where i is the returned index, so it beats a binary search
+///       when `value` is close to the head of L, or when the length of L is unknown or unbounded.
+///     - Therefore, Time Complexity is `O(log i)` and Space Complexity is `O(1)`.
+pub fn exponential_search_by<T, F>(list: &[T], value: &T, mut compare: F) -> Result<usize, usize>
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let index = gallop(list, |item| compare(item, value) == Ordering::Less);
+    match list.get(index) {
+        Some(item) if compare(item, value) == Ordering::Equal => Ok(index),
+        _ => Err(index),
+    }
+}
+
+/// Searches the list, sorted in ascending order of the key extracted by the `key` function, for an item whose key is
+/// `target`. See `exponential_search_by`.
+pub fn exponential_search_by_key<T, K, F>(
+    list: &[T],
+    target: &K,
+    mut key: F,
+) -> Result<usize, usize>
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    let index = gallop(list, |item| key(item) < *target);
+    match list.get(index) {
+        Some(item) if key(item) == *target => Ok(index),
+        _ => Err(index),
+    }
+}
+
+/// Searches the sorted list of numeric keys for `value`, returning `Ok` with the index of the first key equal to it,
+/// or `Err` with the index where it would be inserted when there is none. See `interpolation_search_by_key`.
+///
+/// ```
+/// use more_rust_structures::sorting::interpolation_search;
+///
+/// let timestamps: Vec<u64> = (0..1000).map(|i| 1_700_000_000 + 60 * i).collect();
+/// assert_eq!(interpolation_search(&timestamps, 1_700_000_600), Ok(10));
+/// assert_eq!(interpolation_search(&timestamps, 1_700_000_601), Err(11));
+/// ```
+pub fn interpolation_search<K: RadixKey>(list: &[K], value: K) -> Result<usize, usize> {
+    interpolation_search_by_key(list, value, |&key| key)
+}
+
+///     ## Algorithm
+///     - This search algorithm, by Peterson, looks for `target` in the list L, sorted in ascending order of the
+///       numeric key extracted by the `key` function, guessing where it is from the values of the keys, as one looks
+///       up a word in a dictionary.
+///     - It keeps a range of L known to hold the first key not smaller than `target`, and probes the index where
+///       `target` would be if the keys were evenly spread between the first and the last key of the range.
+///     - Keys are compared, and interpolated, by their `RadixKey::to_radix` mapping, which preserves their order, so
+///       it works on signed and floating point keys too, although their spread in the mapping may be less even.
+///     - When a probe does not at least halve the range, which happens when keys are unevenly spread, it is followed
+///       by the probe of a binary search, so it never needs more than about twice the probes of a binary search.
+///     - It returns `Ok` with the index of the first item whose key is `target`, or `Err` with the index where an item
+///       with key `target` would be inserted.
+///     - There is no variant taking a `compare` function, since interpolating needs the distance between keys, not
+///       just their order.
+///     ## Complexity
+///     - It runs on average `O(log log n)` probes, when the keys are uniformly distributed, and at most around
+///       `2 * log2(n)` probes otherwise, each extracting at most 3 keys.
+///     - Therefore, Time Complexity is `O(log log n)` on average, and `O(log n)` in the worst case, and Space
+///       Complexity is `O(1)`.
+pub fn interpolation_search_by_key<T, K, F>(
+    list: &[T],
+    target: K,
+    mut key: F,
+) -> Result<usize, usize>
+where
+    K: RadixKey,
+    F: FnMut(&T) -> K,
+{
+    let target = target.to_radix();
+    let mut radix = |index: usize| key(&list[index]).to_radix();
+    let mut lo = 0;
+    let mut hi = list.len();
+    while lo < hi {
+        let first = radix(lo);
+        if first >= target {
+            break;
+        }
+        let last = radix(hi - 1);
+        if last < target {
+            lo = hi;
+            break;
+        }
+        // first < target <= last, so the probe is within lo..hi.
+        let len = hi - lo;
+        let offset = (target - first) as u128 * (len - 1) as u128 / (last - first) as u128;
+        let probe = lo + offset as usize;
+        if radix(probe) < target {
+            lo = probe + 1;
+        } else {
+            hi = probe;
+        }
+        if lo < hi && hi - lo > len / 2 {
+            let mid = lo + (hi - lo) / 2;
+            if radix(mid) < target {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+    }
+    if lo < list.len() && radix(lo) == target {
+        Ok(lo)
+    } else {
+        Err(lo)
+    }
+}
+
+/// Returns the `lower_bound` of `value` in each of the sorted lists. See `multi_lower_bound_by`.
+pub fn multi_lower_bound<T: Ord>(lists: &[&[T]], value: &T) -> Vec<usize> {
+    multi_lower_bound_by(lists, value, T::cmp)
+}
+
+///     ## Algorithm
+///     - This search algorithm returns the index of the first item not smaller than `value` in each of the lists, all
+///       sorted according to the order defined by the `compare` function, in the order of the lists.
+///     - Each list is searched on its own, with `lower_bound_by`. Fractional cascading would save all the searches
+///       but the first, but it needs the lists to be merged into a linked structure beforehand, and rebuilt whenever
+///       any of them changes, while these searches work on the lists as they are.
+///     ## Complexity
+///     - It runs at most `ceil(log2(n + 1))` comparisons for each list of n items.
+///     - Therefore, Time Complexity is `O(k log n)`, for k lists of at most n items, and Space Complexity is `O(k)`, for
+///       the returned indexes.
+pub fn multi_lower_bound_by<T, F>(lists: &[&[T]], value: &T, mut compare: F) -> Vec<usize>
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    lists
+        .iter()
+        .map(|list| lower_bound_by(list, value, &mut compare))
+        .collect()
+}
+
+/// Returns the `lower_bound_by_key` of `target` in each of the lists, sorted in ascending order of the key extracted
+/// by the `key` function. See `multi_lower_bound_by`.
+pub fn multi_lower_bound_by_key<T, K, F>(lists: &[&[T]], target: &K, mut key: F) -> Vec<usize>
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    lists
+        .iter()
+        .map(|list| lower_bound_by_key(list, target, &mut key))
+        .collect()
+}
+
+/// Returns the `equal_range` of `value` in each of the sorted lists. See `multi_lower_bound_by`.
+pub fn multi_equal_range<T: Ord>(lists: &[&[T]], value: &T) -> Vec<Range<usize>> {
+    multi_equal_range_by(lists, value, T::cmp)
+}
+
+/// Returns the `equal_range_by` of `value` in each of the lists, sorted according to the order defined by the
+/// `compare` function, each searched on its own. See `multi_lower_bound_by`.
+pub fn multi_equal_range_by<T, F>(lists: &[&[T]], value: &T, mut compare: F) -> Vec<Range<usize>>
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    lists
+        .iter()
+        .map(|list| equal_range_by(list, value, &mut compare))
+        .collect()
+}
+
+/// Returns the `equal_range_by_key` of `target` in each of the lists, sorted in ascending order of the key extracted
+/// by the `key` function. See `multi_lower_bound_by`.
+pub fn multi_equal_range_by_key<T, K, F>(
+    lists: &[&[T]],
+    target: &K,
+    mut key: F,
+) -> Vec<Range<usize>>
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    lists
+        .iter()
+        .map(|list| equal_range_by_key(list, target, &mut key))
+        .collect()
+}
+
+/// Returns the number of leading items of the list for which `is_before` holds, with a binary search. `is_before`
+/// must hold for a prefix of the list, and not hold for the rest of it.
+fn partition_point<T, P>(list: &[T], mut is_before: P) -> usize
+where
+    P: FnMut(&T) -> bool,
+{
+    let mut lo = 0;
+    let mut hi = list.len();
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if is_before(&list[mid]) {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+    lo
+}
+
+/// Returns the number of leading items of the sorted `run` for which `is_before` holds, with an exponential search
+/// followed by a binary search. `is_before` must hold for a prefix of the run, and not hold for the rest of it.
+/// Runs in `O(log k)` comparisons, where k is the returned count.
+pub(crate) fn gallop<T, P>(run: &[T], mut is_before: P) -> usize
+where
+    P: FnMut(&T) -> bool,
+{
+    let mut bound = 1;
+    while bound <= run.len() && is_before(&run[bound - 1]) {
+        bound *= 2;
+    }
+    let lo = bound / 2;
+    let hi = std::cmp::min(bound - 1, run.len());
+    lo + partition_point(&run[lo..hi], is_before)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use crate::sorting::search::{
+        equal_range, equal_range_by, equal_range_by_key, exponential_search, exponential_search_by,
+        exponential_search_by_key, gallop, interpolation_search, interpolation_search_by_key,
+        lower_bound, lower_bound_by, lower_bound_by_key, multi_equal_range, multi_equal_range_by,
+        multi_equal_range_by_key, multi_lower_bound, multi_lower_bound_by,
+        multi_lower_bound_by_key, upper_bound, upper_bound_by, upper_bound_by_key,
+    };
+
+    /// All the sorted lists of up to `max_len` items, with values in `0..values`.
+    fn sorted_lists(max_len: usize, values: i32) -> Vec<Vec<i32>> {
+        let mut lists = vec![vec![]];
+        let mut last = vec![vec![]];
+        for _ in 0..max_len {
+            last = last
+                .into_iter()
+                .flat_map(|list: Vec<i32>| {
+                    let from = list.last().copied().unwrap_or(0);
+                    (from..values).map(move |value| {
+                        let mut longer = list.clone();
+                        longer.push(value);
+                        longer
+                    })
+                })
+                .collect();
+            lists.extend(last.iter().cloned());
+        }
+        lists
+    }
+
+    fn linear_lower_bound(list: &[i32], value: i32) -> usize {
+        list.iter()
+            .position(|&item| item >= value)
+            .unwrap_or(list.len())
+    }
+
+    fn linear_upper_bound(list: &[i32], value: i32) -> usize {
+        list.iter()
+            .position(|&item| item > value)
+            .unwrap_or(list.len())
+    }
+
+    fn linear_search(list: &[i32], value: i32) -> Result<usize, usize> {
+        let index = linear_lower_bound(list, value);
+        if list.get(index) == Some(&value) {
+            Ok(index)
+        } else {
+            Err(index)
+        }
+    }
+
+    #[test]
+    fn bounds_match_linear_search() {
+        for list in sorted_lists(7, 4) {
+            for value in -1..5 {
+                let lower = linear_lower_bound(&list, value);
+                let upper = linear_upper_bound(&list, value);
+                assert_eq!(lower_bound(&list, &value), lower, "{:?} {}", list, value);
+                assert_eq!(upper_bound(&list, &value), upper, "{:?} {}", list, value);
+                assert_eq!(equal_range(&list, &value), lower..upper);
+
+                let reversed: Vec<i32> = list.iter().rev().copied().collect();
+                let (rev_lower, rev_upper) = (list.len() - upper, list.len() - lower);
+                assert_eq!(
+                    lower_bound_by(&reversed, &value, |a, b| b.cmp(a)),
+                    rev_lower
+                );
+                assert_eq!(
+                    upper_bound_by(&reversed, &value, |a, b| b.cmp(a)),
+                    rev_upper
+                );
+                assert_eq!(
+                    equal_range_by(&reversed, &value, |a, b| b.cmp(a)),
+                    rev_lower..rev_upper
+                );
+
+                let pairs: Vec<(i32, usize)> = list.iter().map(|&item| (item, 0)).collect();
+                assert_eq!(lower_bound_by_key(&pairs, &value, |pair| pair.0), lower);
+                assert_eq!(upper_bound_by_key(&pairs, &value, |pair| pair.0), upper);
+                assert_eq!(
+                    equal_range_by_key(&pairs, &value, |pair| pair.0),
+                    lower..upper
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn searches_match_linear_search() {
+        for list in sorted_lists(7, 4) {
+            for value in -1..5 {
+                let expected = linear_search(&list, value);
+                assert_eq!(
+                    exponential_search(&list, &value),
+                    expected,
+                    "{:?} {}",
+                    list,
+                    value
+                );
+                assert_eq!(
+                    interpolation_search(&list, value),
+                    expected,
+                    "{:?} {}",
+                    list,
+                    value
+                );
+
+                let reversed: Vec<i32> = list.iter().rev().copied().collect();
+                let rev_expected = list.len() - linear_upper_bound(&list, value);
+                let found = exponential_search_by(&reversed, &value, |a, b| b.cmp(a));
+                assert_eq!(found.unwrap_or_else(|index| index), rev_expected);
+                assert_eq!(found.is_ok(), expected.is_ok());
+
+                let pairs: Vec<(usize, i32)> = list.iter().map(|&item| (0, item)).collect();
+                assert_eq!(
+                    exponential_search_by_key(&pairs, &value, |pair| pair.1),
+                    expected
+                );
+                assert_eq!(
+                    interpolation_search_by_key(&pairs, value, |pair| pair.1),
+                    expected
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn interpolation_search_on_skewed_keys() {
+        let lists: [Vec<i64>; 4] = [
+            (0..1000).map(|i| i * i * i - 1_000_000).collect(),
+            (0..62).map(|i| 1 << i).collect(),
+            [i64::MIN, -1, 0, 0, 0, 1, i64::MAX].to_vec(),
+            (0..500).map(|i| i / 7).collect(),
+        ];
+        for list in lists {
+            for &item in &list {
+                for value in [item.saturating_sub(1), item, item.saturating_add(1)] {
+                    assert_eq!(
+                        interpolation_search(&list, value),
+                        linear_search_i64(&list, value),
+                        "{}",
+                        value
+                    );
+                }
+            }
+        }
+        let floats = [-3.5, -0.25, 0.0, 1.0, 1.0, 2.5, f64::INFINITY];
+        assert_eq!(interpolation_search(&floats, 1.0), Ok(3));
+        assert_eq!(interpolation_search(&floats, 2.0), Err(5));
+        assert_eq!(interpolation_search(&floats, -4.0), Err(0));
+        assert_eq!(interpolation_search(&[u64::MAX; 3], u64::MAX), Ok(0));
+        assert_eq!(interpolation_search(&[0, u64::MAX], 1), Err(1));
+    }
+
+    fn linear_search_i64(list: &[i64], value: i64) -> Result<usize, usize> {
+        let index = list
+            .iter()
+            .position(|&item| item >= value)
+            .unwrap_or(list.len());
+        if list.get(index) == Some(&value) {
+            Ok(index)
+        } else {
+            Err(index)
+        }
+    }
+
+    #[test]
+    fn probe_counts() {
+        let list: Vec<u32> = (0..1_000_000).map(|i| 3 * i).collect();
+        let probes = Cell::new(0);
+        for value in [0, 2, 1_234_567, 2_999_997, 3_000_000] {
+            probes.set(0);
+            let found = interpolation_search_by_key(&list, value, |&key| {
+                probes.set(probes.get() + 1);
+                key
+            });
+            assert_eq!(found, list.binary_search(&value));
+            assert!(probes.get() <= 12, "{} probes", probes.get());
+        }
+
+        let comparisons = Cell::new(0);
+        let found = exponential_search_by(&list, &27, |a, b| {
+            comparisons.set(comparisons.get() + 1);
+            a.cmp(b)
+        });
+        assert_eq!(found, Ok(9));
+        // 5 probes, 3 binary search steps and the final equality check, where a binary search would run 20.
+        assert!(comparisons.get() <= 9, "{} comparisons", comparisons.get());
+    }
+
+    #[test]
+    fn multi_array_search() {
+        let lists = sorted_lists(4, 3);
+        let slices: Vec<&[i32]> = lists.iter().map(|list| list.as_slice()).collect();
+        for value in -1..4 {
+            let lower: Vec<usize> = lists
+                .iter()
+                .map(|list| linear_lower_bound(list, value))
+                .collect();
+            let ranges: Vec<_> = lists
+                .iter()
+                .map(|list| linear_lower_bound(list, value)..linear_upper_bound(list, value))
+                .collect();
+            assert_eq!(multi_lower_bound(&slices, &value), lower);
+            assert_eq!(multi_lower_bound_by(&slices, &value, i32::cmp), lower);
+            assert_eq!(
+                multi_lower_bound_by_key(&slices, &value, |&item| item),
+                lower
+            );
+            assert_eq!(multi_equal_range(&slices, &value), ranges);
+            assert_eq!(multi_equal_range_by(&slices, &value, i32::cmp), ranges);
+            assert_eq!(
+                multi_equal_range_by_key(&slices, &value, |&item| item),
+                ranges
+            );
+        }
+        assert!(multi_lower_bound::<i32>(&[], &0).is_empty());
+    }
+
+    #[test]
+    fn upper_bound_compares_value_first() {
+        let list = [1, 2, 2, 3];
+        let found = upper_bound_by(&list, &2, |value, item| {
+            assert_eq!(*value, 2);
+            value.cmp(item)
+        });
+        assert_eq!(found, 3);
+    }
+
+    #[test]
+    fn gallop_test() {
+        let run = [1, 2, 2, 3, 5, 8, 13, 21, 34];
+        for key in 0..40 {
+            assert_eq!(
+                gallop(&run, |&item| item < key),
+                run.iter().filter(|&&item| item < key).count()
+            );
+            assert_eq!(
+                gallop(&run, |&item| item <= key),
+                run.iter().filter(|&&item| item <= key).count()
+            );
+        }
+        assert_eq!(gallop(&[] as &[i32], |_| true), 0);
+    }
+}
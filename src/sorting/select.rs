@@ -0,0 +1,382 @@
+use std::cmp::Ordering;
+
+use crate::sorting::{
+    heap_sort::sink,
+    instrument::NoProbe,
+    quick_sort::{median_of_three, three_way_partition},
+    InsertionSort, PdqSort, Sorter,
+};
+
+/// Sub-lists up to this length are sorted with `InsertionSort` rather than partitioned.
+const INSERTION_SORT_CUTOFF: usize = 10;
+
+/// Returns the `k`-th smallest item of the list, counting from 0, after rearranging the list in place.
+/// See `select_nth_by`.
+pub fn select_nth<T: Ord>(list: &mut [T], k: usize) -> &mut T {
+    select_nth_by(list, k, T::cmp)
+}
+
+///     ## Algorithm
+///     - This selection algorithm, also known as introselect, rearranges the list L so that `L[k]` is the item which
+///       would be at index k if L were sorted, the items before it are not bigger than it, and the items after it are
+///       not smaller than it.
+///     - It starts as quickselect: L is split with a 3-way partition around the median of three items, and only the
+///       part holding index k is partitioned further, until index k falls among the items equal to the pivot.
+///     - When quickselect has partitioned more than `2 * log2(n)` times, which only happens when the chosen pivots
+///       keep producing unbalanced partitions, it switches to median-of-medians pivots: the median of the medians of
+///       groups of 5 items, found recursively, which is guaranteed to leave out at least 30% of the items.
+///     ## Complexity
+///     - Quickselect runs on average `O(n)` comparisons, and median-of-medians selection is `O(n)` in the worst case.
+///     - Therefore, Time Complexity is `O(n)`, also in the worst case, and Space Complexity is `O(log n)`, for the
+///       recursion of median-of-medians. The algorithm never allocates.
+///     ## Panics
+///     - When k is not smaller than the length of the list.
+pub fn select_nth_by<T, F>(list: &mut [T], k: usize, mut compare: F) -> &mut T
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    if k >= list.len() {
+        panic!("index {:?} out of bounds for length {:?}", k, list.len());
+    }
+    let limit = 2 * list.len().ilog2() as usize;
+    introselect(list, k, limit, &mut compare);
+    &mut list[k]
+}
+
+/// Returns the `k`-th smallest item of the list, in ascending order of the key extracted by the `key` function, after
+/// rearranging the list in place. See `select_nth_by`.
+pub fn select_nth_by_key<T, K, F>(list: &mut [T], k: usize, mut key: F) -> &mut T
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    select_nth_by(list, k, |a, b| key(a).cmp(&key(b)))
+}
+
+fn introselect<T, F>(mut list: &mut [T], mut k: usize, mut limit: usize, compare: &mut F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    while list.len() > INSERTION_SORT_CUTOFF {
+        let len = list.len();
+        let pivot = if limit == 0 {
+            median_of_medians(list, compare)
+        } else {
+            limit -= 1;
+            median_of_three(list, len / 4, len / 2, 3 * len / 4, compare)
+        };
+        list.swap(0, pivot);
+        let (lt, gt) = three_way_partition(list, compare, &NoProbe);
+        if k < lt {
+            list = &mut list[..lt];
+        } else if k >= gt {
+            list = &mut list[gt..];
+            k -= gt;
+        } else {
+            return;
+        }
+    }
+    InsertionSort.sort_by(list, compare);
+}
+
+/// Moves the medians of the groups of 5 items of the list to its front, and returns the index of their median.
+fn median_of_medians<T, F>(list: &mut [T], compare: &mut F) -> usize
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let groups = list.len() / 5;
+    for group in 0..groups {
+        InsertionSort.sort_by(&mut list[5 * group..5 * group + 5], &mut *compare);
+        list.swap(group, 5 * group + 2);
+    }
+    introselect(&mut list[..groups], groups / 2, 0, compare);
+    groups / 2
+}
+
+/// Rearranges the list so that `list[..k]` holds its `k` smallest items, in ascending order. The order of the other
+/// items is unspecified.
+pub fn partial_sort<T: Ord>(list: &mut [T], k: usize) {
+    partial_sort_by(list, k, T::cmp);
+}
+
+///     ## Algorithm
+///     - This sorting algorithm only orders the k smallest items of the list L, according to the order defined by the
+///       `compare` function, into `L[..k]`.
+///     - It selects the item that would be at index k with `select_nth_by`, which leaves the k smallest items before
+///       it, and sorts `L[..k]` with `PdqSort`.
+///     - When k is not smaller than n, the whole list is sorted.
+///     ## Complexity
+///     - Time Complexity is `O(n + k log k)` and Space Complexity is `O(log n)`.
+pub fn partial_sort_by<T, F>(list: &mut [T], k: usize, mut compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let k = k.min(list.len());
+    if k < list.len() {
+        select_nth_by(list, k, &mut compare);
+    }
+    PdqSort.sort_by(&mut list[..k], compare);
+}
+
+/// Rearranges the list so that `list[..k]` holds its `k` smallest items, in ascending order of the key extracted by
+/// the `key` function. See `partial_sort_by`.
+pub fn partial_sort_by_key<T, K, F>(list: &mut [T], k: usize, mut key: F)
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    partial_sort_by(list, k, |a, b| key(a).cmp(&key(b)));
+}
+
+/// Returns the `k` biggest items yielded by `items`, in descending order. See `top_k_by`.
+pub fn top_k<T, I>(items: I, k: usize) -> Vec<T>
+where
+    T: Ord,
+    I: IntoIterator<Item = T>,
+{
+    top_k_by(items, k, T::cmp)
+}
+
+///     ## Algorithm
+///     - This selection algorithm consumes the iterator once, and keeps the k biggest items seen so far, according to
+///       the order defined by the `compare` function, in a binary min-heap.
+///     - Each new item bigger than the root of the heap, which is the smallest of the k items kept, replaces it, and
+///       is then sunk down to restore the heap order.
+///     - Once the iterator is exhausted, the k items kept are sorted in descending order. Fewer items are returned when
+///       the iterator yields fewer than k.
+///     ## Complexity
+///     - Each item runs at most `1 + 2 * log2(k)` comparisons.
+///     - Therefore, Time Complexity is `O(n log k)` and Space Complexity is `O(k)`, regardless of n.
+pub fn top_k_by<T, I, F>(items: I, k: usize, mut compare: F) -> Vec<T>
+where
+    I: IntoIterator<Item = T>,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    if k == 0 {
+        return vec![];
+    }
+    let mut reversed = |a: &T, b: &T| compare(b, a);
+    let mut items = items.into_iter();
+    let mut heap: Vec<T> = items.by_ref().take(k).collect();
+    let len = heap.len();
+    for parent in (0..len / 2).rev() {
+        sink(&mut heap, parent, len, &mut reversed, &NoProbe);
+    }
+    for item in items {
+        if reversed(&heap[0], &item) == Ordering::Greater {
+            heap[0] = item;
+            sink(&mut heap, 0, k, &mut reversed, &NoProbe);
+        }
+    }
+    PdqSort.sort_by(&mut heap, reversed);
+    heap
+}
+
+/// Returns the `k` biggest items yielded by `items`, in descending order of the key extracted by the `key` function.
+/// See `top_k_by`.
+pub fn top_k_by_key<T, I, K, F>(items: I, k: usize, mut key: F) -> Vec<T>
+where
+    I: IntoIterator<Item = T>,
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    top_k_by(items, k, |a, b| key(a).cmp(&key(b)))
+}
+
+/// Returns the lower median of the list, the item at index `(n - 1) / 2` of the sorted list, after rearranging the
+/// list in place with `select_nth`, or `None` when the list is empty.
+pub fn median<T: Ord>(list: &mut [T]) -> Option<&mut T> {
+    median_by(list, T::cmp)
+}
+
+/// Returns the lower median of the list, according to the order defined by the `compare` function. See `median`.
+pub fn median_by<T, F>(list: &mut [T], compare: F) -> Option<&mut T>
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    if list.is_empty() {
+        return None;
+    }
+    let k = (list.len() - 1) / 2;
+    Some(select_nth_by(list, k, compare))
+}
+
+/// Returns the `p`-th percentile of the list, after rearranging the list in place with `select_nth`, or `None` when the
+/// list is empty. See `percentile_by`.
+pub fn percentile<T: Ord>(list: &mut [T], p: f64) -> Option<&mut T> {
+    percentile_by(list, p, T::cmp)
+}
+
+/// Returns the `p`-th percentile of the list, according to the order defined by the `compare` function, or `None`
+/// when the list is empty.
+/// It uses the nearest-rank method: the result is the smallest item such that at least `p` percent of the items are
+/// not bigger than it, so it's always an item of the list. The 0th percentile is the smallest item.
+///     ## Panics
+///     - When `p` is not within `0.0..=100.0`.
+pub fn percentile_by<T, F>(list: &mut [T], p: f64, compare: F) -> Option<&mut T>
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    if !(0.0..=100.0).contains(&p) {
+        panic!("percentile should be within 0 and 100, got {:?}", p);
+    }
+    if list.is_empty() {
+        return None;
+    }
+    let rank = (p / 100.0 * list.len() as f64).ceil() as usize;
+    let k = rank.max(1) - 1;
+    Some(select_nth_by(list, k, compare))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Reverse;
+
+    use crate::sorting::{
+        select::{
+            introselect, median, partial_sort, partial_sort_by_key, percentile, select_nth,
+            select_nth_by, select_nth_by_key, top_k, top_k_by_key,
+        },
+        tests::pseudo_random_list,
+    };
+
+    fn assert_selected(list: &[u64], k: usize, sorted: &[u64]) {
+        assert_eq!(list[k], sorted[k]);
+        assert!(list[..k].iter().all(|item| *item <= list[k]));
+        assert!(list[k + 1..].iter().all(|item| *item >= list[k]));
+    }
+
+    #[test]
+    fn select_nth_test() {
+        for len in [1, 2, 3, 10, 11, 57, 200] {
+            for modulo in [2, 10, u64::MAX] {
+                let list = pseudo_random_list(len, len as u64 ^ modulo, modulo);
+                let mut sorted = list.clone();
+                sorted.sort();
+                for k in 0..len {
+                    let mut selected = list.clone();
+                    assert_eq!(*select_nth(&mut selected, k), sorted[k]);
+                    assert_selected(&selected, k, &sorted);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn select_nth_by_comparator_and_key() {
+        let mut list = vec!["banana", "fig", "apple", "cherry", "kiwi"];
+        assert_eq!(*select_nth_by(&mut list, 0, |a, b| b.cmp(a)), "kiwi");
+        assert_eq!(*select_nth_by_key(&mut list, 1, |s| s.len()), "kiwi");
+    }
+
+    #[test]
+    #[should_panic(expected = "index 3 out of bounds for length 3")]
+    fn select_nth_out_of_bounds() {
+        select_nth(&mut [1, 2, 3], 3);
+    }
+
+    #[test]
+    fn median_of_medians_is_linear() {
+        for modulo in [2, 10, u64::MAX] {
+            let list = pseudo_random_list(10_000, modulo, modulo);
+            let mut sorted = list.clone();
+            sorted.sort();
+            for k in [0, 1, 4_999, 5_000, 9_999] {
+                let mut selected = list.clone();
+                let mut comparisons = 0;
+                // A limit of 0 makes introselect use median-of-medians pivots only.
+                introselect(&mut selected, k, 0, &mut |a: &u64, b: &u64| {
+                    comparisons += 1;
+                    a.cmp(b)
+                });
+                assert_selected(&selected, k, &sorted);
+                assert!(
+                    comparisons <= 30 * list.len(),
+                    "{} comparisons",
+                    comparisons
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn select_nth_adversarial() {
+        let len = 2000;
+        let inputs: Vec<Vec<u64>> = vec![
+            (0..len).collect(),
+            (0..len).rev().collect(),
+            vec![7; len as usize],
+            (0..len).map(|i| i.min(len - i)).collect(),
+            (0..len).map(|i| i % 50).collect(),
+        ];
+        for list in inputs {
+            let mut sorted = list.clone();
+            sorted.sort();
+            let mut selected = list.clone();
+            let mut comparisons = 0;
+            select_nth_by(&mut selected, 1000, |a, b| {
+                comparisons += 1;
+                a.cmp(b)
+            });
+            assert_selected(&selected, 1000, &sorted);
+            assert!(
+                comparisons <= 30 * list.len(),
+                "{} comparisons",
+                comparisons
+            );
+        }
+    }
+
+    #[test]
+    fn partial_sort_test() {
+        let list = pseudo_random_list(500, 11, 100);
+        let mut sorted = list.clone();
+        sorted.sort();
+        for k in [0, 1, 10, 250, 499, 500, 600] {
+            let mut partial = list.clone();
+            partial_sort(&mut partial, k);
+            let k = k.min(list.len());
+            assert_eq!(partial[..k], sorted[..k]);
+            let mut rest = partial[k..].to_vec();
+            rest.sort();
+            assert_eq!(rest, sorted[k..]);
+        }
+
+        let mut words = vec!["ccc", "a", "bb", "dddd"];
+        partial_sort_by_key(&mut words, 2, |s| Reverse(s.len()));
+        assert_eq!(words[..2], ["dddd", "ccc"]);
+    }
+
+    #[test]
+    fn top_k_test() {
+        let list = pseudo_random_list(1000, 13, 1000);
+        let mut sorted = list.clone();
+        sorted.sort_by(|a, b| b.cmp(a));
+        for k in [0, 1, 5, 100, 1000, 2000] {
+            assert_eq!(top_k(list.iter().copied(), k), sorted[..k.min(list.len())]);
+        }
+        assert_eq!(top_k_by_key(0..10, 3, |i| Reverse(*i)), vec![0, 1, 2]);
+        assert_eq!(top_k(Vec::<u64>::new(), 3), Vec::<u64>::new());
+    }
+
+    #[test]
+    fn median_and_percentile() {
+        assert_eq!(median(&mut Vec::<u64>::new()), None);
+        assert_eq!(median(&mut [3, 1, 2]), Some(&mut 2));
+        assert_eq!(median(&mut [4, 1, 3, 2]), Some(&mut 2));
+
+        let mut list: Vec<u64> = (1..=20).rev().collect();
+        assert_eq!(percentile(&mut list, 0.0), Some(&mut 1));
+        assert_eq!(percentile(&mut list, 5.0), Some(&mut 1));
+        assert_eq!(percentile(&mut list, 30.0), Some(&mut 6));
+        assert_eq!(percentile(&mut list, 50.0), Some(&mut 10));
+        assert_eq!(percentile(&mut list, 100.0), Some(&mut 20));
+        assert_eq!(percentile(&mut Vec::<u64>::new(), 50.0), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "percentile should be within 0 and 100")]
+    fn percentile_out_of_range() {
+        percentile(&mut [1, 2, 3], 101.0);
+    }
+}
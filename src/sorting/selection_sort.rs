@@ -0,0 +1,268 @@
+use std::cmp::Ordering;
+
+use crate::sorting::{
+    instrument::{rotate_right_by_one, swap, NoProbe, ObserverProbe, Probe},
+    SortObserver, Sorter,
+};
+
+///     ## Advantages and Disadvantages
+///     - The algorithm performs sorting in place and is online.
+///     - It is not stable in its basic form and requires additional space or specific assumptions on the type of list
+///       being sorted (such as it being a linked list).
+///     - Compared to other quadratic comparison-based algorithms, such as <see cref="InsertionSort"/>, it is generally
+///       simpler but requires in average an higher number of comparisons, therefore yielding worse performance.
+///     - Compared to linearithmic comparison-based algorithms, such as <see cref="HeapSort"/>, it is way simpler to
+///       understand and predict in exact number of operations executed. However, the performance is sensibly worse.
+///     - Compared to non-comparison-based algorithms, such as counting sort, it doesn't require any assumption on the
+///       type or values of the items in the input, the only requirement being their total comparability and the
+///       comparison behaving according to total order operators rules.
+///     ## Algorithm
+///     - This sorting algorithm split the list L being sorted in two parts: the sorted part, located at the beginning
+///       of the list (`L[..i]`), and the unsorted part, located at the end of the list (`L[i..]`).
+///     - At the beginning the sorted part is empty (i.e. length 0) and the unsorted part covers the entire list (i.e.
+///       length n).
+///     - The algorithm runs n iterations, where n is the number of items in the list.
+///     - At the beginning of iteration `i`, the sorted sub-list is `L[..i]` and the unsorted sub-list is `L[i..]`.
+///     - The unsorted sub-list `L[i..]` is scanned linearly, looking for the index `j`, between `i` and `n - 1`, of the item
+///       of `L[i..]` with minimum value.
+///     - `L[i]` is swapped with `L[j]`, unless j is i, and the iteration `i` terminates.
+///     - Now `L[..(i + 1)]` is the new sorted sub-list, and `L[(i + 1)..]` is the new unsorted sub-list.
+///     ## Complexity
+///     - Each of the n iterations runs `n - i - 1` comparisons, to identify the index of the item with the minimum value
+///       in the sub-list `L[i..]`.
+///     - The total number of comparisons, over the n iterations, is around `n * n / 2`.
+///     - Each iteration runs at most one swap, and the last one never does, so there are at most `n - 1` swaps.
+///     - Therefore, Time Complexity is `O(n^2)` and Space Complexity is `O(1)`, since the algorithm runs in place.
+pub struct SelectionSort;
+
+impl Sorter for SelectionSort {
+    fn sort_by<T, F>(&self, list: &mut [T], mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        selection_sort(list, &mut compare, &NoProbe);
+    }
+
+    fn sort_observed_by<T, F>(&self, list: &mut [T], compare: F, observer: &mut dyn SortObserver)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let probe = ObserverProbe::new(list, observer);
+        selection_sort(list, &mut probe.comparing(compare), &probe);
+    }
+}
+
+/// Sorts the `list` with `SelectionSort`, and reports its swaps to the `probe`.
+fn selection_sort<T, F, P>(list: &mut [T], compare: &mut F, probe: &P)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    P: Probe,
+{
+    for i in 0..list.len() {
+        let j = min_index(list, i, compare);
+        if j != i {
+            swap(list, i, j, probe);
+        }
+    }
+}
+
+///     ## Algorithm
+///     - This sorting algorithm is a stable variant of `SelectionSort`: at iteration `i` it finds the index `j` of the
+///       first item of `L[i..]` with minimum value, like `SelectionSort` does.
+///     - Instead of swapping `L[i]` with `L[j]`, which could move `L[i]` after items equal to it, `L[j]` is inserted at
+///       index i, and `L[i..j]` is shifted right by one position, preserving the relative order of the unsorted items.
+///     ## Complexity
+///     - It runs the same `n * (n - 1) / 2` comparisons as `SelectionSort`, and no swaps, but up to `n * n / 2` moves,
+///       when shifting.
+///     - Therefore, Time Complexity is `O(n^2)` and Space Complexity is `O(1)`, since the algorithm runs in place.
+pub struct StableSelectionSort;
+
+impl Sorter for StableSelectionSort {
+    fn sort_by<T, F>(&self, list: &mut [T], mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        stable_selection_sort(list, &mut compare, &NoProbe);
+    }
+
+    fn sort_observed_by<T, F>(&self, list: &mut [T], compare: F, observer: &mut dyn SortObserver)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let probe = ObserverProbe::new(list, observer);
+        stable_selection_sort(list, &mut probe.comparing(compare), &probe);
+    }
+}
+
+/// Sorts the `list` with `StableSelectionSort`, and reports its moves to the `probe`.
+fn stable_selection_sort<T, F, P>(list: &mut [T], compare: &mut F, probe: &P)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    P: Probe,
+{
+    for i in 0..list.len() {
+        let j = min_index(list, i, compare);
+        if j != i {
+            rotate_right_by_one(&mut list[i..=j], probe);
+        }
+    }
+}
+
+///     ## Algorithm
+///     - This sorting algorithm is a variant of `SelectionSort` which grows a sorted part at both ends of the list L:
+///       at the beginning of each pass the unsorted part is `L[lo..hi]`, with `L[..lo]` holding the smallest items and
+///       `L[hi..]` the biggest ones, both sorted.
+///     - Each pass scans `L[lo..hi]` once, looking for both the index of the minimum and the index of the maximum,
+///       comparing each item with the maximum only when it is not smaller than the current minimum.
+///     - The minimum is swapped into `L[lo]` and the maximum into `L[hi - 1]`, keeping track of the maximum when it was
+///       at index lo, and the unsorted part shrinks by two items.
+///     ## Complexity
+///     - There are `n / 2` passes, each running at most `2 * (hi - lo - 1)` comparisons, for a total of at most around
+///       `n * n / 2` comparisons, like `SelectionSort`, but half the passes over the list.
+///     - Each pass runs at most two swaps, and the last one at most one, so there are at most `n - 1` swaps.
+///     - Therefore, Time Complexity is `O(n^2)` and Space Complexity is `O(1)`, since the algorithm runs in place.
+pub struct DoubleEndedSelectionSort;
+
+impl Sorter for DoubleEndedSelectionSort {
+    fn sort_by<T, F>(&self, list: &mut [T], mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        double_ended_selection_sort(list, &mut compare, &NoProbe);
+    }
+
+    fn sort_observed_by<T, F>(&self, list: &mut [T], compare: F, observer: &mut dyn SortObserver)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let probe = ObserverProbe::new(list, observer);
+        double_ended_selection_sort(list, &mut probe.comparing(compare), &probe);
+    }
+}
+
+/// Sorts the `list` with `DoubleEndedSelectionSort`, and reports its swaps to the `probe`.
+fn double_ended_selection_sort<T, F, P>(list: &mut [T], compare: &mut F, probe: &P)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    P: Probe,
+{
+    let mut lo = 0;
+    let mut hi = list.len();
+    while hi - lo > 1 {
+        let mut min = lo;
+        let mut max = lo;
+        for j in lo + 1..hi {
+            if compare(&list[j], &list[min]) == Ordering::Less {
+                min = j;
+            } else if compare(&list[j], &list[max]) != Ordering::Less {
+                max = j;
+            }
+        }
+        if min != lo {
+            swap(list, lo, min, probe);
+            if max == lo {
+                max = min;
+            }
+        }
+        if max != hi - 1 {
+            swap(list, max, hi - 1, probe);
+        }
+        lo += 1;
+        hi -= 1;
+    }
+}
+
+/// Returns the index of the first item of `list[from..]` with minimum value, running `list.len() - from - 1`
+/// comparisons.
+fn min_index<T, F>(list: &[T], from: usize, compare: &mut F) -> usize
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let mut min = from;
+    for j in from + 1..list.len() {
+        if compare(&list[j], &list[min]) == Ordering::Less {
+            min = j;
+        }
+    }
+    min
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sorting::{
+        disorder::is_sorted,
+        instrument::instrument,
+        selection_sort::{DoubleEndedSelectionSort, SelectionSort, StableSelectionSort},
+        tests::{pseudo_random_list, pseudo_random_test, sorting_test, stability_test},
+        Sorter,
+    };
+
+    /// Lists of many shapes and lengths.
+    fn inputs() -> Vec<Vec<u64>> {
+        let mut inputs = vec![];
+        for len in [0, 1, 2, 3, 10, 101, 500] {
+            inputs.push((0..len).collect());
+            inputs.push((0..len).rev().collect());
+            inputs.push(vec![4; len as usize]);
+            inputs.push((0..len).map(|i| i.min(len - i)).collect());
+            inputs.push(pseudo_random_list(len as usize, len + 1, 5));
+            inputs.push(pseudo_random_list(len as usize, len + 2, u64::MAX));
+        }
+        inputs
+    }
+
+    fn swap_bound_test<S: Sorter>(sorter: S) {
+        for mut list in inputs() {
+            let n = list.len();
+            let counts = instrument(&sorter, &mut list);
+            assert!(is_sorted(&list));
+            assert!(
+                counts.swaps <= n.saturating_sub(1),
+                "{} swaps for {} items",
+                counts.swaps,
+                n
+            );
+            assert!(
+                counts.comparisons <= n * n / 2,
+                "{} comparisons for {} items",
+                counts.comparisons,
+                n
+            );
+        }
+    }
+
+    #[test]
+    fn selection_sort_test() {
+        sorting_test(SelectionSort);
+        pseudo_random_test(SelectionSort);
+    }
+
+    #[test]
+    fn selection_sort_swaps() {
+        swap_bound_test(SelectionSort);
+        for mut list in inputs() {
+            let n = list.len();
+            let counts = instrument(&SelectionSort, &mut list);
+            assert_eq!(counts.comparisons, n * n.saturating_sub(1) / 2);
+        }
+        let mut sorted: Vec<u64> = (0..100).collect();
+        assert_eq!(instrument(&SelectionSort, &mut sorted).swaps, 0);
+    }
+
+    #[test]
+    fn stable_selection_sort_test() {
+        sorting_test(StableSelectionSort);
+        pseudo_random_test(StableSelectionSort);
+        stability_test(StableSelectionSort);
+        for mut list in inputs() {
+            assert_eq!(instrument(&StableSelectionSort, &mut list).swaps, 0);
+        }
+    }
+
+    #[test]
+    fn double_ended_selection_sort_test() {
+        sorting_test(DoubleEndedSelectionSort);
+        pseudo_random_test(DoubleEndedSelectionSort);
+        swap_bound_test(DoubleEndedSelectionSort);
+    }
+}
@@ -0,0 +1,220 @@
+use std::cmp::Ordering;
+
+use crate::sorting::{
+    instrument::{swap, NoProbe, ObserverProbe, Probe},
+    SortObserver, Sorter,
+};
+
+/// The decreasing sequences of gaps `ShellSort` can h-sort the list with.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GapSequence {
+    /// Knuth's `(3^k - 1) / 2` sequence: 1, 4, 13, 40, 121, ...
+    Knuth,
+    /// Sedgewick's `4^k + 3 * 2^(k - 1) + 1` sequence: 1, 8, 23, 77, 281, ...
+    Sedgewick,
+    /// Tokuda's `ceil((9^k - 4^k) / (5 * 4^(k - 1)))` sequence: 1, 4, 9, 20, 46, 103, ...
+    Tokuda,
+    /// Ciura's empirically found sequence, 1, 4, 10, 23, 57, 132, 301, 701, extended by multiplying by 2.25.
+    Ciura,
+    /// Pratt's `2^p * 3^q` sequence: 1, 2, 3, 4, 6, 8, 9, 12, ...
+    Pratt,
+    /// Any sequence of gaps. Zeros and duplicates are ignored, and a final gap of 1 is added when missing, so that the
+    /// last pass is a plain insertion sort.
+    Custom(Vec<usize>),
+}
+
+impl GapSequence {
+    /// The gaps smaller than `len`, in decreasing order, always ending with 1.
+    pub fn gaps(&self, len: usize) -> Vec<usize> {
+        let mut gaps = match self {
+            GapSequence::Knuth => generate(|h| 3 * h + 1, len),
+            GapSequence::Sedgewick => {
+                let mut gaps = vec![1];
+                for k in 1.. {
+                    let gap = 4usize.pow(k) + 3 * 2usize.pow(k - 1) + 1;
+                    if gap >= len {
+                        break;
+                    }
+                    gaps.push(gap);
+                }
+                gaps
+            }
+            GapSequence::Tokuda => {
+                let mut gaps = vec![];
+                let mut h = 1.0f64;
+                while (h.ceil() as usize) < len.max(2) {
+                    gaps.push(h.ceil() as usize);
+                    h = 2.25 * h + 1.0;
+                }
+                gaps
+            }
+            GapSequence::Ciura => {
+                let mut gaps: Vec<usize> = [1, 4, 10, 23, 57, 132, 301, 701].into_iter().collect();
+                while gaps[gaps.len() - 1] < len {
+                    gaps.push(gaps[gaps.len() - 1] * 9 / 4);
+                }
+                gaps
+            }
+            GapSequence::Pratt => {
+                let mut gaps = vec![];
+                let mut power_of_two = 1;
+                while power_of_two < len.max(2) {
+                    let mut gap = power_of_two;
+                    while gap < len.max(2) {
+                        gaps.push(gap);
+                        gap *= 3;
+                    }
+                    power_of_two *= 2;
+                }
+                gaps
+            }
+            GapSequence::Custom(gaps) => gaps.clone(),
+        };
+        gaps.retain(|&gap| gap > 0 && (gap < len || gap == 1));
+        gaps.push(1);
+        gaps.sort_unstable_by(|a, b| b.cmp(a));
+        gaps.dedup();
+        gaps
+    }
+}
+
+/// Generates the gaps `1, next(1), next(next(1)), ...` smaller than `len`.
+fn generate<F: Fn(usize) -> usize>(next: F, len: usize) -> Vec<usize> {
+    let mut gaps = vec![1];
+    let mut h = next(1);
+    while h < len {
+        gaps.push(h);
+        h = next(h);
+    }
+    gaps
+}
+
+///     ## Algorithm
+///     - This sorting algorithm, by Donald Shell, generalises `InsertionSort`, which only ever moves items by one
+///       position, to move items by `h` positions at a time.
+///     - A list L is h-sorted when every sub-list of items `h` positions apart, `L[k], L[k + h], L[k + 2h], ...`, is
+///       sorted. h-sorting runs insertion sort on each of these sub-lists, comparing and swapping `L[j]` with `L[j - h]`.
+///     - L is h-sorted for each `h` of a decreasing `GapSequence`, ending with `h = 1`, which is a plain insertion sort.
+///     - Passes with big gaps move items a long way with few swaps, so that each following pass finds L almost sorted,
+///       which is the best case for insertion sort.
+///     ## Complexity
+///     - The exact number of comparisons depends on the gap sequence and is not known in general for most sequences.
+///     - Time Complexity is `O(n^(3/2))` with `GapSequence::Knuth`, `O(n^(4/3))` with `GapSequence::Sedgewick` and
+///       `O(n log^2 n)` with `GapSequence::Pratt`. Space Complexity is `O(1)`, since the algorithm runs in place.
+pub struct ShellSort {
+    gap_sequence: GapSequence,
+}
+
+impl ShellSort {
+    pub fn new(gap_sequence: GapSequence) -> Self {
+        ShellSort { gap_sequence }
+    }
+
+    pub fn gap_sequence(&self) -> &GapSequence {
+        &self.gap_sequence
+    }
+}
+
+impl Default for ShellSort {
+    /// Uses `GapSequence::Ciura`, which performs best in practice.
+    fn default() -> Self {
+        ShellSort::new(GapSequence::Ciura)
+    }
+}
+
+impl ShellSort {
+    /// Sorts the `list`, and reports its swaps to the `probe`.
+    fn shell_sort<T, F, P>(&self, list: &mut [T], compare: &mut F, probe: &P)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+        P: Probe,
+    {
+        for h in self.gap_sequence.gaps(list.len()) {
+            for i in h..list.len() {
+                let mut j = i;
+                while j >= h && compare(&list[j], &list[j - h]) == Ordering::Less {
+                    swap(list, j, j - h, probe);
+                    j -= h;
+                }
+            }
+        }
+    }
+}
+
+impl Sorter for ShellSort {
+    fn sort_by<T, F>(&self, list: &mut [T], mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        self.shell_sort(list, &mut compare, &NoProbe);
+    }
+
+    fn sort_observed_by<T, F>(&self, list: &mut [T], compare: F, observer: &mut dyn SortObserver)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let probe = ObserverProbe::new(list, observer);
+        self.shell_sort(list, &mut probe.comparing(compare), &probe);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sorting::{
+        instrument,
+        shell_sort::{GapSequence, ShellSort},
+        tests::{pseudo_random_list, pseudo_random_test, sorting_test},
+        InsertionSort,
+    };
+
+    fn gap_sequences() -> Vec<GapSequence> {
+        vec![
+            GapSequence::Knuth,
+            GapSequence::Sedgewick,
+            GapSequence::Tokuda,
+            GapSequence::Ciura,
+            GapSequence::Pratt,
+            GapSequence::Custom(vec![1, 5, 19, 41, 109, 209, 505, 929]),
+        ]
+    }
+
+    #[test]
+    fn shell_sort_test() {
+        for gap_sequence in gap_sequences() {
+            sorting_test(ShellSort::new(gap_sequence.clone()));
+            pseudo_random_test(ShellSort::new(gap_sequence));
+        }
+    }
+
+    #[test]
+    fn gaps() {
+        assert_eq!(GapSequence::Knuth.gaps(100), vec![40, 13, 4, 1]);
+        assert_eq!(GapSequence::Sedgewick.gaps(100), vec![77, 23, 8, 1]);
+        assert_eq!(GapSequence::Tokuda.gaps(100), vec![46, 20, 9, 4, 1]);
+        assert_eq!(
+            GapSequence::Ciura.gaps(1000),
+            vec![701, 301, 132, 57, 23, 10, 4, 1]
+        );
+        assert_eq!(
+            GapSequence::Ciura.gaps(2000),
+            vec![1577, 701, 301, 132, 57, 23, 10, 4, 1]
+        );
+        assert_eq!(GapSequence::Pratt.gaps(10), vec![9, 8, 6, 4, 3, 2, 1]);
+        assert_eq!(
+            GapSequence::Custom(vec![0, 5, 200, 5, 3]).gaps(100),
+            vec![5, 3, 1]
+        );
+        assert_eq!(GapSequence::Knuth.gaps(0), vec![1]);
+    }
+
+    #[test]
+    fn fewer_comparisons_than_insertion_sort() {
+        let list = pseudo_random_list(2000, 1, 1_000_000);
+        let insertion_sort_comparisons = instrument(&InsertionSort, &mut list.clone()).comparisons;
+        for gap_sequence in gap_sequences() {
+            let comparisons =
+                instrument(&ShellSort::new(gap_sequence), &mut list.clone()).comparisons;
+            assert!(comparisons < insertion_sort_comparisons / 10);
+        }
+    }
+}
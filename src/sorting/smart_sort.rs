@@ -0,0 +1,481 @@
+use std::{cmp::Ordering, fmt};
+
+use crate::sorting::{
+    disorder::count_runs_by, instrument::ObserverProbe, lsd_radix_sort_by_key, InsertionSort,
+    PdqSort, RadixKey, SortObserver, Sorter, ThreeWayQuickSort, TimSort,
+};
+
+/// The maximum number of items inspected to profile the list.
+const SAMPLE_LEN: usize = 256;
+
+/// The length of the blocks of adjacent items inspected to estimate the runs of lists longer than `SAMPLE_LEN`.
+const SAMPLE_BLOCK_LEN: usize = 32;
+
+/// The algorithms `SmartSort` can dispatch to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    /// `InsertionSort`, for short lists.
+    Insertion,
+    /// `TimSort`, an adaptive merge sort, for lists made of long runs.
+    Merge,
+    /// `ThreeWayQuickSort`, for lists with many duplicates, and the general case.
+    ThreeWayQuick,
+    /// `lsd_radix_sort_by_key`, for long lists of narrow keys. Only available when sorting by a `RadixKey`.
+    Radix,
+}
+
+impl fmt::Display for Strategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Strategy::Insertion => write!(f, "insertion sort"),
+            Strategy::Merge => write!(f, "adaptive merge sort"),
+            Strategy::ThreeWayQuick => write!(f, "3-way quicksort"),
+            Strategy::Radix => write!(f, "radix sort"),
+        }
+    }
+}
+
+/// The characteristics of a list, estimated from a sample of at most 256 of its items.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Profile {
+    /// The number of items of the list.
+    pub len: usize,
+    /// The estimated number of ascending or descending runs, whichever are fewer: 1 for sorted and reverse sorted
+    /// lists, around `n / 2` for random lists.
+    pub estimated_runs: usize,
+    /// The fraction of the sampled items equal to another sampled item, from 0 when they are all distinct, to almost
+    /// 1 when they are all equal.
+    pub duplicate_ratio: f64,
+    /// The number of bytes of the keys, when sorting by a `RadixKey`.
+    pub key_bytes: Option<usize>,
+}
+
+impl Profile {
+    ///     ## Algorithm
+    ///     - Lists of at most `SAMPLE_LEN` items are inspected entirely. Otherwise, runs are estimated from
+    ///       `SAMPLE_LEN / SAMPLE_BLOCK_LEN` blocks of adjacent items, evenly spread over the list, by extrapolating the
+    ///       number of items smaller than the one before them, and duplicates from `SAMPLE_LEN` evenly spread items.
+    ///     - The sampled items are sorted by index with `PdqSort`, and the duplicates are the ones equal to the previous.
+    ///     ## Complexity
+    ///     - It runs `O(SAMPLE_LEN log SAMPLE_LEN)` comparisons, regardless of n.
+    fn sample_by<T, F>(list: &[T], compare: &mut F, key_bytes: Option<usize>) -> Self
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let len = list.len();
+        let blocks: Vec<&[T]> = if len <= SAMPLE_LEN {
+            vec![list]
+        } else {
+            let count = SAMPLE_LEN / SAMPLE_BLOCK_LEN;
+            (0..count)
+                .map(|block| {
+                    let start = block * (len - SAMPLE_BLOCK_LEN) / (count - 1);
+                    &list[start..start + SAMPLE_BLOCK_LEN]
+                })
+                .collect()
+        };
+        let (mut pairs, mut ascending, mut descending) = (0, 0, 0);
+        for block in blocks.into_iter().filter(|block| !block.is_empty()) {
+            pairs += block.len() - 1;
+            ascending += count_runs_by(block, &mut *compare) - 1;
+            descending += count_runs_by(block, |a, b| compare(b, a)) - 1;
+        }
+        let estimated_runs = match pairs {
+            0 => len.min(1),
+            _ => 1 + ascending.min(descending) * (len - 1) / pairs,
+        };
+
+        let sample_len = len.min(SAMPLE_LEN);
+        let mut sample: Vec<usize> = (0..sample_len).map(|i| i * len / sample_len).collect();
+        PdqSort.sort_by(&mut sample, |&a, &b| compare(&list[a], &list[b]));
+        let duplicates = sample
+            .windows(2)
+            .filter(|pair| compare(&list[pair[0]], &list[pair[1]]) == Ordering::Equal)
+            .count();
+        let duplicate_ratio = match sample_len {
+            0 => 0.0,
+            _ => duplicates as f64 / sample_len as f64,
+        };
+
+        Profile {
+            len,
+            estimated_runs,
+            duplicate_ratio,
+            key_bytes,
+        }
+    }
+}
+
+/// The strategy chosen to sort a list, and the profile of the list it was chosen from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Decision {
+    pub strategy: Strategy,
+    pub profile: Profile,
+}
+
+impl fmt::Display for Decision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} for {} items: about {} runs, {:.0}% duplicates",
+            self.strategy,
+            self.profile.len,
+            self.profile.estimated_runs,
+            100.0 * self.profile.duplicate_ratio
+        )?;
+        if let Some(bytes) = self.profile.key_bytes {
+            write!(f, ", {}-byte keys", bytes)?;
+        }
+        Ok(())
+    }
+}
+
+/// Chooses the strategy to sort a list with, from its profile.
+/// Any `Fn(&Profile) -> Strategy` closure is a policy.
+pub trait SortPolicy {
+    fn choose(&self, profile: &Profile) -> Strategy;
+}
+
+impl<F: Fn(&Profile) -> Strategy> SortPolicy for F {
+    fn choose(&self, profile: &Profile) -> Strategy {
+        self(profile)
+    }
+}
+
+/// The policy of `SmartSort`, with configurable thresholds. The first matching rule wins:
+/// - lists of at most `insertion_cutoff` items are sorted with `Strategy::Insertion`;
+/// - lists whose runs are at least `min_average_run_len` items long on average with `Strategy::Merge`;
+/// - lists with a `duplicate_ratio` of at least `min_duplicate_ratio` with `Strategy::ThreeWayQuick`;
+/// - lists of keys of b bytes with `Strategy::Radix`, when the b linear passes of radix sort are cheaper than the
+///   `log2(n)` levels of quicksort, that is when `2 * b <= log2(n)`;
+/// - all the other lists with `Strategy::ThreeWayQuick`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DefaultPolicy {
+    pub insertion_cutoff: usize,
+    pub min_average_run_len: usize,
+    pub min_duplicate_ratio: f64,
+}
+
+impl Default for DefaultPolicy {
+    fn default() -> Self {
+        DefaultPolicy {
+            insertion_cutoff: 24,
+            min_average_run_len: 32,
+            min_duplicate_ratio: 0.5,
+        }
+    }
+}
+
+impl SortPolicy for DefaultPolicy {
+    fn choose(&self, profile: &Profile) -> Strategy {
+        let len = profile.len;
+        if len <= self.insertion_cutoff {
+            Strategy::Insertion
+        } else if profile.estimated_runs * self.min_average_run_len <= len {
+            Strategy::Merge
+        } else if profile.duplicate_ratio >= self.min_duplicate_ratio {
+            Strategy::ThreeWayQuick
+        } else if profile
+            .key_bytes
+            .is_some_and(|bytes| 2 * bytes <= len.ilog2() as usize)
+        {
+            Strategy::Radix
+        } else {
+            Strategy::ThreeWayQuick
+        }
+    }
+}
+
+///     ## Algorithm
+///     - This sorting algorithm profiles the list L from a sample of its items, estimating its runs and duplicates,
+///       and lets its `SortPolicy` choose the algorithm to sort it with. See `Profile` and `DefaultPolicy`.
+///     - `Strategy::Radix` is only available when sorting by a `RadixKey`, with `sort_by_radix_key`. Otherwise, the
+///       list is sorted with `Strategy::ThreeWayQuick` when the policy chooses it.
+///     - `sort_by_with_decision` returns the decision taken, so that it can be logged. The sort is not stable, since
+///       some of the strategies are not.
+///     ## Complexity
+///     - Profiling runs `O(1)` comparisons, and each strategy is `O(n log n)` or better on the lists it is chosen
+///       for, except insertion sort, which is only chosen for short lists.
+pub struct SmartSort<P = DefaultPolicy> {
+    policy: P,
+}
+
+impl Default for SmartSort {
+    fn default() -> Self {
+        SmartSort::with_policy(DefaultPolicy::default())
+    }
+}
+
+impl<P: SortPolicy> SmartSort<P> {
+    /// Creates a sorter which chooses how to sort each list with the given policy.
+    ///
+    /// ```
+    /// use more_rust_structures::sorting::{SmartSort, Sorter, Strategy};
+    ///
+    /// let sorter = SmartSort::default();
+    /// let mut list: Vec<u32> = (0..1000).collect();
+    /// assert_eq!(sorter.sort_by_with_decision(&mut list, u32::cmp).strategy, Strategy::Merge);
+    ///
+    /// let always_insertion = SmartSort::with_policy(|_: &_| Strategy::Insertion);
+    /// assert_eq!(always_insertion.sort_by_radix_key(&mut list, |&x| x).strategy, Strategy::Insertion);
+    /// ```
+    pub fn with_policy(policy: P) -> Self {
+        SmartSort { policy }
+    }
+
+    /// Sorts the list according to the order defined by the `compare` function, as `sort_by` does, and returns the
+    /// decision taken.
+    pub fn sort_by_with_decision<T, F>(&self, list: &mut [T], mut compare: F) -> Decision
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let decision = self.decide(list, &mut compare, None);
+        run(decision.strategy, list, compare, None);
+        decision
+    }
+
+    /// Returns the decision this sorter would take for the list, according to the order defined by the `compare`
+    /// function, without sorting it.
+    pub fn decide_by<T, F>(&self, list: &[T], mut compare: F) -> Decision
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        self.decide(list, &mut compare, None)
+    }
+
+    /// Sorts the list in ascending order of the radix key extracted by the `key` function, which makes
+    /// `Strategy::Radix` available, and returns the decision taken.
+    pub fn sort_by_radix_key<T, K, F>(&self, list: &mut [T], mut key: F) -> Decision
+    where
+        K: RadixKey,
+        F: FnMut(&T) -> K,
+    {
+        let mut compare = |a: &T, b: &T| key(a).to_radix().cmp(&key(b).to_radix());
+        let decision = self.decide(list, &mut compare, Some(K::BYTES));
+        match decision.strategy {
+            Strategy::Radix => lsd_radix_sort_by_key(list, key),
+            strategy => run(strategy, list, compare, None),
+        }
+        decision
+    }
+
+    fn decide<T, F>(&self, list: &[T], compare: &mut F, key_bytes: Option<usize>) -> Decision
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let profile = Profile::sample_by(list, compare, key_bytes);
+        let strategy = match self.policy.choose(&profile) {
+            Strategy::Radix if key_bytes.is_none() => Strategy::ThreeWayQuick,
+            strategy => strategy,
+        };
+        Decision { strategy, profile }
+    }
+}
+
+impl<P: SortPolicy> Sorter for SmartSort<P> {
+    fn sort_by<T, F>(&self, list: &mut [T], compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        self.sort_by_with_decision(list, compare);
+    }
+
+    fn sort_observed_by<T, F>(
+        &self,
+        list: &mut [T],
+        mut compare: F,
+        observer: &mut dyn SortObserver,
+    ) where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let probe = ObserverProbe::new(list, &mut *observer);
+        let decision = self.decide(list, &mut probe.comparing(&mut compare), None);
+        run(decision.strategy, list, compare, Some(observer));
+    }
+}
+
+/// Sorts the list with any strategy but `Strategy::Radix`, reporting its operations to the `observer`, if any.
+fn run<T, F>(
+    strategy: Strategy,
+    list: &mut [T],
+    compare: F,
+    observer: Option<&mut dyn SortObserver>,
+) where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    match strategy {
+        Strategy::Insertion => run_with(&InsertionSort, list, compare, observer),
+        Strategy::Merge => run_with(&TimSort, list, compare, observer),
+        Strategy::ThreeWayQuick | Strategy::Radix => {
+            run_with(&ThreeWayQuickSort, list, compare, observer)
+        }
+    }
+}
+
+fn run_with<S, T, F>(
+    sorter: &S,
+    list: &mut [T],
+    compare: F,
+    observer: Option<&mut dyn SortObserver>,
+) where
+    S: Sorter,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    match observer {
+        Some(observer) => sorter.sort_observed_by(list, compare, observer),
+        None => sorter.sort_by(list, compare),
+    }
+}
+
+/// Sorts the provided `list` in place, in ascending order, with `SmartSort`, and returns the decision taken.
+pub fn smart_sort<T: Ord>(list: &mut [T]) -> Decision {
+    smart_sort_by(list, T::cmp)
+}
+
+/// Sorts the provided `list` in place, according to the order defined by the `compare` function, with `SmartSort`,
+/// and returns the decision taken.
+pub fn smart_sort_by<T, F>(list: &mut [T], compare: F) -> Decision
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    SmartSort::default().sort_by_with_decision(list, compare)
+}
+
+/// Sorts the provided `list` in place, in ascending order of the radix key extracted by the `key` function, with
+/// `SmartSort`, and returns the decision taken. Lists of integers and floats can be sorted by themselves, with
+/// `|&item| item`.
+pub fn smart_sort_by_radix_key<T, K, F>(list: &mut [T], key: F) -> Decision
+where
+    K: RadixKey,
+    F: FnMut(&T) -> K,
+{
+    SmartSort::default().sort_by_radix_key(list, key)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sorting::{
+        disorder::is_sorted,
+        smart_sort::{
+            smart_sort, smart_sort_by, smart_sort_by_radix_key, DefaultPolicy, Profile, SmartSort,
+            Strategy,
+        },
+        tests::{pseudo_random_list, pseudo_random_test, sorting_test},
+        Sorter,
+    };
+
+    #[test]
+    fn smart_sort_test() {
+        sorting_test(SmartSort::default());
+        pseudo_random_test(SmartSort::default());
+        pseudo_random_test(SmartSort::with_policy(|_: &Profile| Strategy::Radix));
+    }
+
+    #[test]
+    fn decisions() {
+        let mut short = pseudo_random_list(20, 1, 1000);
+        assert_eq!(smart_sort(&mut short).strategy, Strategy::Insertion);
+        assert!(is_sorted(&short));
+
+        for mut list in [
+            (0..10_000).collect::<Vec<u64>>(),
+            (0..10_000).rev().collect(),
+            (0..10_000).map(|i| (i + 5000) % 10_000).collect(),
+        ] {
+            let decision = smart_sort(&mut list);
+            assert_eq!(decision.strategy, Strategy::Merge, "{}", decision);
+            assert!(decision.profile.estimated_runs <= 10);
+            assert!(is_sorted(&list));
+        }
+
+        let mut few_keys = pseudo_random_list(10_000, 2, 8);
+        let decision = smart_sort(&mut few_keys);
+        assert_eq!(decision.strategy, Strategy::ThreeWayQuick);
+        assert!(decision.profile.duplicate_ratio > 0.9);
+        assert!(is_sorted(&few_keys));
+
+        let random = pseudo_random_list(10_000, 3, u64::MAX);
+        let decision = smart_sort(&mut random.clone());
+        assert_eq!(decision.strategy, Strategy::ThreeWayQuick);
+        assert_eq!(decision.profile.duplicate_ratio, 0.0);
+        assert!(decision.profile.estimated_runs > 2000);
+
+        let mut narrow: Vec<u16> = random.iter().map(|&x| x as u16).collect();
+        let decision = smart_sort_by_radix_key(&mut narrow, |&x| x);
+        assert_eq!(decision.strategy, Strategy::Radix);
+        assert_eq!(decision.profile.key_bytes, Some(2));
+        assert!(is_sorted(&narrow));
+
+        let mut wide = random.clone();
+        assert_eq!(
+            smart_sort_by_radix_key(&mut wide, |&x| x).strategy,
+            Strategy::ThreeWayQuick
+        );
+        assert!(is_sorted(&wide));
+    }
+
+    #[test]
+    fn policies() {
+        let sorter = SmartSort::with_policy(DefaultPolicy {
+            insertion_cutoff: 100,
+            ..DefaultPolicy::default()
+        });
+        let mut list = pseudo_random_list(100, 4, u64::MAX);
+        assert_eq!(
+            sorter.sort_by_with_decision(&mut list, u64::cmp).strategy,
+            Strategy::Insertion
+        );
+        assert!(is_sorted(&list));
+
+        let merge_everything = SmartSort::with_policy(|_: &Profile| Strategy::Merge);
+        let mut records = vec![(2, 'a'), (1, 'b'), (2, 'c'), (1, 'd')];
+        merge_everything.sort_by_key(&mut records, |record| record.0);
+        assert_eq!(records, [(1, 'b'), (1, 'd'), (2, 'a'), (2, 'c')]);
+
+        // Radix sort needs a radix key, so comparison sorts fall back to quicksort.
+        let radix = SmartSort::with_policy(|_: &Profile| Strategy::Radix);
+        let mut list = pseudo_random_list(100, 5, 10);
+        assert_eq!(
+            radix.sort_by_with_decision(&mut list, u64::cmp).strategy,
+            Strategy::ThreeWayQuick
+        );
+        assert_eq!(
+            radix.sort_by_radix_key(&mut list, |&x| x).strategy,
+            Strategy::Radix
+        );
+
+        let decision = SmartSort::default().decide_by(&[3, 1, 2], i32::cmp);
+        assert_eq!(decision.profile.len, 3);
+        assert_eq!(decision.profile.estimated_runs, 2);
+    }
+
+    #[test]
+    fn sorters_can_be_shared_between_threads() {
+        fn assert_sync<S: Sync>(_: &S) {}
+        let sorter = SmartSort::default();
+        assert_sync(&sorter);
+        let mut lists = [pseudo_random_list(1000, 6, 10), (0..1000).collect()];
+        std::thread::scope(|scope| {
+            for list in &mut lists {
+                scope.spawn(|| sorter.sort(list));
+            }
+        });
+        assert!(lists.iter().all(|list| is_sorted(list)));
+    }
+
+    #[test]
+    fn decisions_can_be_logged() {
+        let mut list: Vec<i32> = (0..1000).collect();
+        assert_eq!(
+            smart_sort_by(&mut list, |a, b| b.cmp(a)).to_string(),
+            "adaptive merge sort for 1000 items: about 1 runs, 0% duplicates"
+        );
+        let mut list = vec![0u8; 1000];
+        assert_eq!(
+            smart_sort_by_radix_key(&mut list, |&x| x).to_string(),
+            "adaptive merge sort for 1000 items: about 1 runs, 100% duplicates, 1-byte keys"
+        );
+        let mut empty: [u8; 0] = [];
+        assert_eq!(smart_sort(&mut empty).profile.estimated_runs, 0);
+    }
+}
@@ -0,0 +1,532 @@
+use std::{cmp::Ordering, iter::Peekable};
+
+/// Merges sorted iterators into a single sorted iterator, in ascending order. See `merge_k_by`.
+pub fn merge_k<I>(
+    iters: I,
+) -> MergeK<<I::Item as IntoIterator>::IntoIter, Compare<<I::Item as IntoIterator>::Item>>
+where
+    I: IntoIterator,
+    I::Item: IntoIterator,
+    <I::Item as IntoIterator>::Item: Ord,
+{
+    merge_k_by(iters, Ord::cmp as Compare<_>)
+}
+
+///     ## Algorithm
+///     - This merging algorithm lazily merges k iterators, each sorted according to the order defined by the
+///       `compare` function, into a single sorted iterator.
+///     - It keeps the next item of each iterator in a binary min-heap. Each call to `next` takes the root of the heap,
+///       and replaces it with the next item of the same iterator, which is then sunk down to restore the heap order.
+///     - Equal items are ordered by the position of their iterator in `iters`, and each iterator is consumed in order,
+///       so the merge is stable.
+///     - The output is only sorted when all the iterators are: unsorted iterators produce an unspecified order.
+///     ## Complexity
+///     - Each item runs at most `2 * log2(k)` comparisons.
+///     - Therefore, Time Complexity is `O(n log k)`, for n items overall, and Space Complexity is `O(k)`.
+pub fn merge_k_by<I, F>(iters: I, compare: F) -> MergeK<<I::Item as IntoIterator>::IntoIter, F>
+where
+    I: IntoIterator,
+    I::Item: IntoIterator,
+    F: FnMut(&<I::Item as IntoIterator>::Item, &<I::Item as IntoIterator>::Item) -> Ordering,
+{
+    MergeK {
+        sources: iters.into_iter().map(IntoIterator::into_iter).collect(),
+        heap: vec![],
+        is_started: false,
+        compare,
+    }
+}
+
+/// The function comparing items by their `Ord` implementation, used by the iterators which don't take a `compare`
+/// function.
+pub type Compare<T> = fn(&T, &T) -> Ordering;
+
+/// The iterator returned by `merge_k` and `merge_k_by`.
+///
+/// ```
+/// use more_rust_structures::sorting::merge_k_by;
+///
+/// let logs = [vec![(1, "boot"), (7, "stop")], vec![(3, "login")], vec![(1, "ping"), (5, "logout")]];
+/// let merged: Vec<_> = merge_k_by(logs, |a, b| a.0.cmp(&b.0)).map(|entry| entry.1).collect();
+/// assert_eq!(merged, ["boot", "ping", "login", "logout", "stop"]);
+/// ```
+pub struct MergeK<I: Iterator, F> {
+    sources: Vec<I>,
+    /// The next item of each source which isn't exhausted, with the index of the source, as a min-heap.
+    heap: Vec<(I::Item, usize)>,
+    is_started: bool,
+    compare: F,
+}
+
+impl<I, F> MergeK<I, F>
+where
+    I: Iterator,
+    F: FnMut(&I::Item, &I::Item) -> Ordering,
+{
+    /// Sinks the item at index `node` down the min-heap, where items compare by `compare` and then by the index of
+    /// their source. It moves items with plain swaps, since the heap is private to the iterator.
+    fn sink(&mut self, mut node: usize) {
+        let compare = &mut self.compare;
+        let mut is_less = |a: &(I::Item, usize), b: &(I::Item, usize)| {
+            compare(&a.0, &b.0).then(a.1.cmp(&b.1)) == Ordering::Less
+        };
+        let heap = &mut self.heap;
+        loop {
+            let mut child = 2 * node + 1;
+            if child >= heap.len() {
+                break;
+            }
+            if child + 1 < heap.len() && is_less(&heap[child + 1], &heap[child]) {
+                child += 1;
+            }
+            if !is_less(&heap[child], &heap[node]) {
+                break;
+            }
+            heap.swap(node, child);
+            node = child;
+        }
+    }
+}
+
+impl<I, F> Iterator for MergeK<I, F>
+where
+    I: Iterator,
+    F: FnMut(&I::Item, &I::Item) -> Ordering,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        if !self.is_started {
+            self.is_started = true;
+            self.heap = self
+                .sources
+                .iter_mut()
+                .enumerate()
+                .filter_map(|(source, iter)| iter.next().map(|item| (item, source)))
+                .collect();
+            for node in (0..self.heap.len() / 2).rev() {
+                self.sink(node);
+            }
+        }
+        let source = self.heap.first()?.1;
+        let item = match self.sources[source].next() {
+            Some(next) => std::mem::replace(&mut self.heap[0], (next, source)).0,
+            None => self.heap.swap_remove(0).0,
+        };
+        self.sink(0);
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.sources.iter().map(Iterator::size_hint).fold(
+            (self.heap.len(), Some(self.heap.len())),
+            |(low, high), (l, h)| {
+                (
+                    low.saturating_add(l),
+                    high.zip(h).and_then(|(high, h)| high.checked_add(h)),
+                )
+            },
+        )
+    }
+}
+
+/// The operations `SetOperation` can combine two sorted iterators with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SetKind {
+    Union,
+    Intersection,
+    Difference,
+    SymmetricDifference,
+}
+
+impl SetKind {
+    /// Whether items only found in the first iterator, only found in the second one, and found in both, are yielded.
+    fn keeps(self) -> (bool, bool, bool) {
+        match self {
+            SetKind::Union => (true, true, true),
+            SetKind::Intersection => (false, false, true),
+            SetKind::Difference => (true, false, false),
+            SetKind::SymmetricDifference => (true, true, false),
+        }
+    }
+}
+
+/// The iterator returned by `union`, `intersection`, `difference`, `symmetric_difference` and their `_by` variants.
+///     ## Algorithm
+///     - The two iterators, each sorted according to the order defined by the `compare` function, are walked in
+///       lockstep, like in the merge step of merge sort, comparing their next items.
+///     - The smaller item is only found in its own iterator, so far, while equal items are found in both: in that case
+///       both are consumed, and only the one from the first iterator may be yielded.
+///     - Duplicates are matched one by one, so an item found `a` times in the first iterator and `b` times in the
+///       second one is yielded `max(a, b)` times by the union, `min(a, b)` times by the intersection, `a - b` times by
+///       the difference, and `|a - b|` times by the symmetric difference. Iterators without duplicates give the usual
+///       set operations.
+///     ## Complexity
+///     - Each item runs at most one comparison, so Time Complexity is `O(n + m)` and Space Complexity is `O(1)`.
+pub struct SetOperation<A: Iterator, B: Iterator, F> {
+    a: Peekable<A>,
+    b: Peekable<B>,
+    compare: F,
+    kind: SetKind,
+}
+
+impl<T, A, B, F> Iterator for SetOperation<A, B, F>
+where
+    A: Iterator<Item = T>,
+    B: Iterator<Item = T>,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let (only_a, only_b, both) = self.kind.keeps();
+        loop {
+            let ordering = match (self.a.peek(), self.b.peek()) {
+                (None, None) => return None,
+                (Some(_), None) if !only_a => return None,
+                (None, Some(_)) if !only_b => return None,
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (Some(a), Some(b)) => (self.compare)(a, b),
+            };
+            match ordering {
+                Ordering::Less => {
+                    let item = self.a.next();
+                    if only_a {
+                        return item;
+                    }
+                }
+                Ordering::Greater => {
+                    let item = self.b.next();
+                    if only_b {
+                        return item;
+                    }
+                }
+                Ordering::Equal => {
+                    let item = self.a.next();
+                    self.b.next();
+                    if both {
+                        return item;
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn set_operation<A, B, F>(
+    a: A,
+    b: B,
+    compare: F,
+    kind: SetKind,
+) -> SetOperation<A::IntoIter, B::IntoIter, F>
+where
+    A: IntoIterator,
+    B: IntoIterator<Item = A::Item>,
+    F: FnMut(&A::Item, &A::Item) -> Ordering,
+{
+    SetOperation {
+        a: a.into_iter().peekable(),
+        b: b.into_iter().peekable(),
+        compare,
+        kind,
+    }
+}
+
+/// Returns the items of either of the two sorted iterators, in ascending order. See `SetOperation`.
+pub fn union<A, B>(a: A, b: B) -> SetOperation<A::IntoIter, B::IntoIter, Compare<A::Item>>
+where
+    A: IntoIterator,
+    A::Item: Ord,
+    B: IntoIterator<Item = A::Item>,
+{
+    union_by(a, b, Ord::cmp as Compare<_>)
+}
+
+/// Returns the items of either of the two iterators sorted according to the order defined by the `compare` function,
+/// in the same order. See `SetOperation`.
+pub fn union_by<A, B, F>(a: A, b: B, compare: F) -> SetOperation<A::IntoIter, B::IntoIter, F>
+where
+    A: IntoIterator,
+    B: IntoIterator<Item = A::Item>,
+    F: FnMut(&A::Item, &A::Item) -> Ordering,
+{
+    set_operation(a, b, compare, SetKind::Union)
+}
+
+/// Returns the items of both the two sorted iterators, in ascending order. See `SetOperation`.
+pub fn intersection<A, B>(a: A, b: B) -> SetOperation<A::IntoIter, B::IntoIter, Compare<A::Item>>
+where
+    A: IntoIterator,
+    A::Item: Ord,
+    B: IntoIterator<Item = A::Item>,
+{
+    intersection_by(a, b, Ord::cmp as Compare<_>)
+}
+
+/// Returns the items of both the two iterators sorted according to the order defined by the `compare` function, in
+/// the same order. See `SetOperation`.
+pub fn intersection_by<A, B, F>(a: A, b: B, compare: F) -> SetOperation<A::IntoIter, B::IntoIter, F>
+where
+    A: IntoIterator,
+    B: IntoIterator<Item = A::Item>,
+    F: FnMut(&A::Item, &A::Item) -> Ordering,
+{
+    set_operation(a, b, compare, SetKind::Intersection)
+}
+
+/// Returns the items of the first sorted iterator which are not in the second one, in ascending order. See
+/// `SetOperation`.
+pub fn difference<A, B>(a: A, b: B) -> SetOperation<A::IntoIter, B::IntoIter, Compare<A::Item>>
+where
+    A: IntoIterator,
+    A::Item: Ord,
+    B: IntoIterator<Item = A::Item>,
+{
+    difference_by(a, b, Ord::cmp as Compare<_>)
+}
+
+/// Returns the items of the first iterator which are not in the second one, both sorted according to the order
+/// defined by the `compare` function, in the same order. See `SetOperation`.
+pub fn difference_by<A, B, F>(a: A, b: B, compare: F) -> SetOperation<A::IntoIter, B::IntoIter, F>
+where
+    A: IntoIterator,
+    B: IntoIterator<Item = A::Item>,
+    F: FnMut(&A::Item, &A::Item) -> Ordering,
+{
+    set_operation(a, b, compare, SetKind::Difference)
+}
+
+/// Returns the items of exactly one of the two sorted iterators, in ascending order. See `SetOperation`.
+pub fn symmetric_difference<A, B>(
+    a: A,
+    b: B,
+) -> SetOperation<A::IntoIter, B::IntoIter, Compare<A::Item>>
+where
+    A: IntoIterator,
+    A::Item: Ord,
+    B: IntoIterator<Item = A::Item>,
+{
+    symmetric_difference_by(a, b, Ord::cmp as Compare<_>)
+}
+
+/// Returns the items of exactly one of the two iterators sorted according to the order defined by the `compare`
+/// function, in the same order. See `SetOperation`.
+pub fn symmetric_difference_by<A, B, F>(
+    a: A,
+    b: B,
+    compare: F,
+) -> SetOperation<A::IntoIter, B::IntoIter, F>
+where
+    A: IntoIterator,
+    B: IntoIterator<Item = A::Item>,
+    F: FnMut(&A::Item, &A::Item) -> Ordering,
+{
+    set_operation(a, b, compare, SetKind::SymmetricDifference)
+}
+
+/// Returns the items of the sorted iterator without duplicates. See `dedup_by`.
+pub fn dedup<I>(iter: I) -> Dedup<I::IntoIter, Compare<I::Item>>
+where
+    I: IntoIterator,
+    I::Item: Ord,
+{
+    dedup_by(iter, Ord::cmp as Compare<_>)
+}
+
+/// Returns the items of the iterator, sorted according to the order defined by the `compare` function, skipping the
+/// items equal to the one before them: only the first of each group of equal items is yielded. Unlike
+/// `Vec::dedup`, it is lazy, and runs one comparison per item.
+pub fn dedup_by<I, F>(iter: I, compare: F) -> Dedup<I::IntoIter, F>
+where
+    I: IntoIterator,
+    F: FnMut(&I::Item, &I::Item) -> Ordering,
+{
+    Dedup {
+        iter: iter.into_iter().peekable(),
+        compare,
+    }
+}
+
+/// The iterator returned by `dedup` and `dedup_by`.
+pub struct Dedup<I: Iterator, F> {
+    iter: Peekable<I>,
+    compare: F,
+}
+
+impl<I, F> Iterator for Dedup<I, F>
+where
+    I: Iterator,
+    F: FnMut(&I::Item, &I::Item) -> Ordering,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        let item = self.iter.next()?;
+        while self
+            .iter
+            .next_if(|next| (self.compare)(&item, next) == Ordering::Equal)
+            .is_some()
+        {}
+        Some(item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::sorting::{
+        sorted_iter::{
+            dedup, dedup_by, difference, intersection, intersection_by, merge_k, merge_k_by,
+            symmetric_difference, union, union_by,
+        },
+        tests::pseudo_random_list,
+    };
+
+    fn sorted_list(len: usize, seed: u64, modulo: u64) -> Vec<u64> {
+        let mut list = pseudo_random_list(len, seed, modulo);
+        list.sort();
+        list
+    }
+
+    fn counts(list: &[u64]) -> BTreeMap<u64, usize> {
+        let mut counts = BTreeMap::new();
+        for &item in list {
+            *counts.entry(item).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Expands the counts of each item into a sorted list.
+    fn expand(counts: impl Iterator<Item = (u64, usize)>) -> Vec<u64> {
+        counts
+            .flat_map(|(item, count)| std::iter::repeat_n(item, count))
+            .collect()
+    }
+
+    #[test]
+    fn merge_k_test() {
+        let lists: Vec<Vec<u64>> = (0..10)
+            .map(|i| sorted_list(i * 37 % 100, i as u64 + 1, 50))
+            .collect();
+        let merged: Vec<u64> = merge_k(lists.clone()).collect();
+        let mut expected: Vec<u64> = lists.concat();
+        expected.sort();
+        assert_eq!(merged, expected);
+
+        let merge = merge_k(lists.iter().map(|list| list.iter()));
+        assert_eq!(merge.size_hint(), (expected.len(), Some(expected.len())));
+        assert_eq!(merge.count(), expected.len());
+
+        assert_eq!(merge_k(Vec::<Vec<u64>>::new()).next(), None);
+        assert_eq!(merge_k([vec![], vec![1], vec![]]).collect::<Vec<_>>(), [1]);
+    }
+
+    #[test]
+    fn merge_k_is_stable() {
+        let sources: Vec<Vec<(u64, usize)>> = (0..5)
+            .map(|source| {
+                sorted_list(200, source as u64 + 7, 10)
+                    .into_iter()
+                    .map(|key| (key, source))
+                    .collect()
+            })
+            .collect();
+        let merged: Vec<(u64, usize)> = merge_k_by(sources, |a, b| a.0.cmp(&b.0)).collect();
+        assert_eq!(merged.len(), 1000);
+        assert!(merged.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+
+    #[test]
+    fn merge_k_is_lazy() {
+        let mut merge = merge_k([0.., 5..]);
+        assert_eq!(merge.size_hint(), (usize::MAX, None));
+        assert_eq!(
+            merge.by_ref().take(7).collect::<Vec<_>>(),
+            [0, 1, 2, 3, 4, 5, 5]
+        );
+        assert_eq!(merge.next(), Some(6));
+    }
+
+    #[test]
+    fn set_operations_follow_multiplicities() {
+        for seed in 1..20 {
+            let a = sorted_list(seed as usize * 5, seed, 20);
+            let b = sorted_list(seed as usize * 3, seed + 100, 20);
+            let (count_a, count_b) = (counts(&a), counts(&b));
+            let count =
+                |counts: &BTreeMap<u64, usize>, item| counts.get(&item).copied().unwrap_or(0);
+            let items = || {
+                count_a
+                    .keys()
+                    .chain(count_b.keys())
+                    .copied()
+                    .collect::<std::collections::BTreeSet<u64>>()
+            };
+            let expected = |f: fn(usize, usize) -> usize| {
+                expand(
+                    items()
+                        .into_iter()
+                        .map(|item| (item, f(count(&count_a, item), count(&count_b, item)))),
+                )
+            };
+            assert_eq!(
+                union(a.clone(), b.clone()).collect::<Vec<_>>(),
+                expected(usize::max)
+            );
+            assert_eq!(
+                intersection(&a, &b).copied().collect::<Vec<_>>(),
+                expected(usize::min)
+            );
+            assert_eq!(
+                difference(&a, &b).copied().collect::<Vec<_>>(),
+                expected(usize::saturating_sub)
+            );
+            assert_eq!(
+                symmetric_difference(&a, &b).copied().collect::<Vec<_>>(),
+                expected(usize::abs_diff)
+            );
+        }
+    }
+
+    #[test]
+    fn set_operations_by() {
+        let a = ["apple", "Banana", "cherry"];
+        let b = ["APPLE", "date"];
+        let case_insensitive = |x: &&str, y: &&str| x.to_lowercase().cmp(&y.to_lowercase());
+        assert_eq!(
+            union_by(a, b, case_insensitive).collect::<Vec<_>>(),
+            ["apple", "Banana", "cherry", "date"]
+        );
+        assert_eq!(
+            intersection_by(a, b, case_insensitive).collect::<Vec<_>>(),
+            ["apple"]
+        );
+        assert_eq!(union(Vec::<u8>::new(), vec![]).next(), None);
+        assert_eq!(intersection(vec![1, 2], vec![]).next(), None);
+        assert_eq!(difference(vec![], vec![1, 2]).next(), None);
+    }
+
+    #[test]
+    fn dedup_test() {
+        let list = sorted_list(500, 3, 30);
+        let mut expected = list.clone();
+        expected.dedup();
+        assert_eq!(dedup(list).collect::<Vec<_>>(), expected);
+        assert_eq!(
+            dedup_by([(1, 'a'), (1, 'b'), (2, 'c')], |a, b| a.0.cmp(&b.0)).collect::<Vec<_>>(),
+            [(1, 'a'), (2, 'c')]
+        );
+        assert_eq!(dedup(Vec::<u8>::new()).next(), None);
+    }
+
+    #[test]
+    fn combined_pipeline() {
+        // Three sorted logs of user ids, merged, deduplicated, without the blocked users.
+        let logs = [vec![1, 3, 5, 5], vec![2, 3, 8], vec![5, 9]];
+        let blocked = [3, 9];
+        let active: Vec<u32> = difference(dedup(merge_k(logs)), blocked).collect();
+        assert_eq!(active, [1, 2, 5, 8]);
+    }
+}
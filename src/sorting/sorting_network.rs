@@ -0,0 +1,576 @@
+use std::cmp::Ordering;
+
+use crate::sorting::{
+    instrument::{swap, NoProbe, ObserverProbe, Probe},
+    SortObserver, Sorter,
+};
+
+/// The biggest number of items a sorting network is available for.
+pub(crate) const MAX_NETWORK_LEN: usize = 16;
+
+/// The comparators of the smallest known sorting network for each number of items up to 16, layer by layer.
+#[rustfmt::skip]
+const NETWORKS: [&[(usize, usize)]; 17] = [
+    // 0 and 1 items are already sorted.
+    &[],
+    &[],
+    // 2 items: 1 comparator in 1 layer.
+    &[
+        (0, 1),
+    ],
+    // 3 items: 3 comparators in 3 layers.
+    &[
+        (0, 2),
+        (0, 1),
+        (1, 2),
+    ],
+    // 4 items: 5 comparators in 3 layers.
+    &[
+        (0, 1), (2, 3),
+        (0, 2), (1, 3),
+        (1, 2),
+    ],
+    // 5 items: 9 comparators in 5 layers.
+    &[
+        (0, 3), (1, 4),
+        (0, 2), (1, 3),
+        (0, 1), (2, 4),
+        (1, 2), (3, 4),
+        (2, 3),
+    ],
+    // 6 items: 12 comparators in 5 layers.
+    &[
+        (0, 5), (1, 3), (2, 4),
+        (1, 2), (3, 4),
+        (0, 3), (2, 5),
+        (0, 1), (2, 3), (4, 5),
+        (1, 2), (3, 4),
+    ],
+    // 7 items: 16 comparators in 6 layers.
+    &[
+        (0, 6), (2, 3), (4, 5),
+        (0, 2), (1, 4), (3, 6),
+        (0, 1), (2, 5), (3, 4),
+        (1, 2), (4, 6),
+        (2, 3), (4, 5),
+        (1, 2), (3, 4), (5, 6),
+    ],
+    // 8 items: 19 comparators in 6 layers.
+    &[
+        (0, 2), (1, 3), (4, 6), (5, 7),
+        (0, 4), (1, 5), (2, 6), (3, 7),
+        (0, 1), (2, 3), (4, 5), (6, 7),
+        (2, 4), (3, 5),
+        (1, 4), (3, 6),
+        (1, 2), (3, 4), (5, 6),
+    ],
+    // 9 items: 25 comparators in 7 layers.
+    &[
+        (0, 3), (1, 7), (2, 5), (4, 8),
+        (0, 7), (2, 4), (3, 8), (5, 6),
+        (0, 2), (1, 3), (4, 5), (7, 8),
+        (1, 4), (3, 6), (5, 7),
+        (0, 1), (2, 4), (3, 5), (6, 8),
+        (2, 3), (4, 5), (6, 7),
+        (1, 2), (3, 4), (5, 6),
+    ],
+    // 10 items: 29 comparators in 8 layers.
+    &[
+        (0, 8), (1, 9), (2, 7), (3, 5), (4, 6),
+        (0, 2), (1, 4), (5, 8), (7, 9),
+        (0, 3), (2, 4), (5, 7), (6, 9),
+        (0, 1), (3, 6), (8, 9),
+        (1, 5), (2, 3), (4, 8), (6, 7),
+        (1, 2), (3, 5), (4, 6), (7, 8),
+        (2, 3), (4, 5), (6, 7),
+        (3, 4), (5, 6),
+    ],
+    // 11 items: 35 comparators in 8 layers.
+    &[
+        (0, 9), (1, 6), (2, 4), (3, 7), (5, 8),
+        (0, 1), (3, 5), (4, 10), (6, 9), (7, 8),
+        (1, 3), (2, 5), (4, 7), (8, 10),
+        (0, 4), (1, 2), (3, 7), (5, 9), (6, 8),
+        (0, 1), (2, 6), (4, 5), (7, 8), (9, 10),
+        (2, 4), (3, 6), (5, 7), (8, 9),
+        (1, 2), (3, 4), (5, 6), (7, 8),
+        (2, 3), (4, 5), (6, 7),
+    ],
+    // 12 items: 39 comparators in 9 layers.
+    &[
+        (0, 8), (1, 7), (2, 6), (3, 11), (4, 10), (5, 9),
+        (0, 1), (2, 5), (3, 4), (6, 9), (7, 8), (10, 11),
+        (0, 2), (1, 6), (5, 10), (9, 11),
+        (0, 3), (1, 2), (4, 6), (5, 7), (8, 11), (9, 10),
+        (1, 4), (3, 5), (6, 8), (7, 10),
+        (1, 3), (2, 5), (6, 9), (8, 10),
+        (2, 3), (4, 5), (6, 7), (8, 9),
+        (4, 6), (5, 7),
+        (3, 4), (5, 6), (7, 8),
+    ],
+    // 13 items: 45 comparators in 10 layers.
+    &[
+        (0, 12), (1, 10), (2, 9), (3, 7), (5, 11), (6, 8),
+        (1, 6), (2, 3), (4, 11), (7, 9), (8, 10),
+        (0, 4), (1, 2), (3, 6), (7, 8), (9, 10), (11, 12),
+        (4, 6), (5, 9), (8, 11), (10, 12),
+        (0, 5), (3, 8), (4, 7), (6, 11), (9, 10),
+        (0, 1), (2, 5), (6, 9), (7, 8), (10, 11),
+        (1, 3), (2, 4), (5, 6), (9, 10),
+        (1, 2), (3, 4), (5, 7), (6, 8),
+        (2, 3), (4, 5), (6, 7), (8, 9),
+        (3, 4), (5, 6),
+    ],
+    // 14 items: 51 comparators in 10 layers.
+    &[
+        (0, 1), (2, 3), (4, 5), (6, 7), (8, 9), (10, 11), (12, 13),
+        (0, 2), (1, 3), (4, 8), (5, 9), (10, 12), (11, 13),
+        (0, 4), (1, 2), (3, 7), (5, 8), (6, 10), (9, 13), (11, 12),
+        (0, 6), (1, 5), (3, 9), (4, 10), (7, 13), (8, 12),
+        (2, 10), (3, 11), (4, 6), (7, 9),
+        (1, 3), (2, 8), (5, 11), (6, 7), (10, 12),
+        (1, 4), (2, 6), (3, 5), (7, 11), (8, 10), (9, 12),
+        (2, 4), (3, 6), (5, 8), (7, 10), (9, 11),
+        (3, 4), (5, 6), (7, 8), (9, 10),
+        (6, 7),
+    ],
+    // 15 items: 56 comparators in 10 layers.
+    &[
+        (0, 13), (1, 12), (3, 14), (4, 8), (5, 6), (7, 11), (9, 10),
+        (0, 5), (1, 7), (2, 9), (3, 4), (6, 13), (8, 14), (11, 12),
+        (0, 1), (2, 3), (4, 5), (6, 8), (7, 9), (10, 11), (12, 13),
+        (0, 2), (1, 3), (4, 10), (5, 11), (6, 7), (8, 9), (12, 14),
+        (1, 2), (3, 12), (4, 6), (5, 7), (8, 10), (9, 11), (13, 14),
+        (1, 4), (2, 6), (5, 8), (7, 10), (9, 13), (11, 14),
+        (2, 4), (3, 6), (9, 12), (11, 13),
+        (3, 5), (6, 8), (7, 9), (10, 12),
+        (3, 4), (5, 6), (7, 8), (9, 10), (11, 12),
+        (6, 7), (8, 9),
+    ],
+    // 16 items: 60 comparators in 10 layers.
+    &[
+        (0, 13), (1, 12), (2, 15), (3, 14), (4, 8), (5, 6), (7, 11), (9, 10),
+        (0, 5), (1, 7), (2, 9), (3, 4), (6, 13), (8, 14), (10, 15), (11, 12),
+        (0, 1), (2, 3), (4, 5), (6, 8), (7, 9), (10, 11), (12, 13), (14, 15),
+        (0, 2), (1, 3), (4, 10), (5, 11), (6, 7), (8, 9), (12, 14), (13, 15),
+        (1, 2), (3, 12), (4, 6), (5, 7), (8, 10), (9, 11), (13, 14),
+        (1, 4), (2, 6), (5, 8), (7, 10), (9, 13), (11, 14),
+        (2, 4), (3, 6), (9, 12), (11, 13),
+        (3, 5), (6, 8), (7, 9), (10, 12),
+        (3, 4), (5, 6), (7, 8), (9, 10), (11, 12),
+        (6, 7), (8, 9),
+    ],
+];
+
+/// Returns the comparators of the sorting network used for `n` items, or `None` when n is bigger than 16.
+/// Each comparator `(i, j)`, with `i < j`, puts the smaller of the items at indexes i and j at index i, and the bigger
+/// one at index j. Applying all of them in order sorts any list of n items.
+pub fn sorting_network(n: usize) -> Option<&'static [(usize, usize)]> {
+    NETWORKS.get(n).copied()
+}
+
+/// Sorts an array of up to 16 items of a primitive type, like integers, in ascending order, with a sorting network.
+/// See `sort_network_by`.
+/// The compare-exchanges select the smaller and the bigger item from a single comparison, rather than branching on
+/// it, which lets the compiler use conditional moves, so the running time doesn't depend on the values.
+///
+/// ```
+/// use more_rust_structures::sorting::sort_network;
+///
+/// let mut array = [5, 2, 9, 1];
+/// sort_network(&mut array);
+/// assert_eq!(array, [1, 2, 5, 9]);
+/// ```
+pub fn sort_network<T: Copy + Ord, const N: usize>(array: &mut [T; N]) {
+    const {
+        assert!(
+            N <= MAX_NETWORK_LEN,
+            "sorting networks are only available for up to 16 items"
+        )
+    };
+    for &(i, j) in NETWORKS[N] {
+        let (a, b) = (array[i], array[j]);
+        let is_less = b < a;
+        array[i] = if is_less { b } else { a };
+        array[j] = if is_less { a } else { b };
+    }
+}
+
+///     ## Algorithm
+///     - This sorting algorithm sorts arrays of up to 16 items with a sorting network: a fixed sequence of
+///       compare-exchanges, which doesn't depend on the items.
+///     - The length of the array is checked at compile time: arrays of more than 16 items don't compile. See
+///       `sort_network_slice_by` for slices, whose length is only known at run time.
+///     - The networks are the smallest known: they are proven optimal in the number of comparators up to 12 items, and
+///       are the best known ones from 13 to 16 items.
+///     - Compare-exchanges swap the two items when they are out of order, so the sort is not stable.
+///     ## Complexity
+///     - It runs a fixed number of comparisons, from 0 for 1 item to 60 for 16 items, regardless of the input.
+///     - Space Complexity is `O(1)`, since the algorithm runs in place.
+pub fn sort_network_by<T, F, const N: usize>(array: &mut [T; N], mut compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    const {
+        assert!(
+            N <= MAX_NETWORK_LEN,
+            "sorting networks are only available for up to 16 items"
+        )
+    };
+    network_sort_by(array, &mut compare, &NoProbe);
+}
+
+/// Sorts a slice of up to 16 items, according to the order defined by the `compare` function, with the sorting
+/// network for its length, as `sort_network_by` does for arrays.
+/// Its length only needs to be known at run time, so it can serve as the small-list cutoff of a recursive sort, in
+/// place of `InsertionSort`. Networks run a fixed number of comparisons, and their branches only depend on the
+/// outcome of each comparison, which can suit cheap comparisons of random items. `InsertionSort` runs fewer
+/// comparisons on nearly sorted sub-lists: 15 instead of 60 for 16 sorted items. Which one is faster depends on the
+/// items and the machine, so measure both.
+///     ## Panics
+///     - When the slice has more than 16 items.
+///
+/// ```
+/// use more_rust_structures::sorting::sort_network_slice_by;
+///
+/// fn quick_sort(list: &mut [u32]) {
+///     if list.len() <= 16 {
+///         sort_network_slice_by(list, u32::cmp);
+///         return;
+///     }
+///     let mid = list.len() / 2;
+///     list.select_nth_unstable(mid);
+///     let (left, right) = list.split_at_mut(mid);
+///     quick_sort(left);
+///     quick_sort(&mut right[1..]);
+/// }
+///
+/// let mut list: Vec<u32> = (0..100).map(|i| i * 37 % 101).collect();
+/// quick_sort(&mut list);
+/// assert!(list.windows(2).all(|pair| pair[0] <= pair[1]));
+/// ```
+pub fn sort_network_slice_by<T, F>(list: &mut [T], mut compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    if list.len() > MAX_NETWORK_LEN {
+        panic!(
+            "sorting networks are only available for up to 16 items, but the list has {}",
+            list.len()
+        );
+    }
+    network_sort_by(list, &mut compare, &NoProbe);
+}
+
+/// Sorts a list of at most `MAX_NETWORK_LEN` items with the sorting network for its length, and reports its swaps to
+/// the `probe`.
+pub(crate) fn network_sort_by<T, F, P>(list: &mut [T], compare: &mut F, probe: &P)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    P: Probe,
+{
+    for &(i, j) in NETWORKS[list.len()] {
+        if compare(&list[j], &list[i]) == Ordering::Less {
+            swap(list, i, j, probe);
+        }
+    }
+}
+
+///     ## Algorithm
+///     - This sorting algorithm, by Ken Batcher, is a sorting network: the compare-exchanges it runs only depend on the
+///       number of items n, not on their values, so they can all run in parallel within each of its stages.
+///     - It recursively sorts the first half of the list L in descending order and the second half in ascending order,
+///       which makes L a bitonic sequence, first descending and then ascending, and then merges it.
+///     - The bitonic merge compare-exchanges `L[i]` with `L[i + m]`, for the biggest power of two m smaller than n, which
+///       leaves the smallest items in `L[..m]` and the biggest ones in `L[m..]`, each half still bitonic, and merges
+///       each half recursively. Choosing m this way makes the network work for any n, not only for powers of two.
+///     - Compare-exchanges swap the two items when they are out of order, so the sort is not stable.
+///     ## Complexity
+///     - The network has `log2(n) * (log2(n) + 1) / 2` stages, each of at most `n / 2` compare-exchanges.
+///     - Therefore, Time Complexity is `O(n log^2 n)` and Space Complexity is `O(log n)`, for the recursion. With one
+///       processor per pair of items, the parallel time is `O(log^2 n)`.
+pub struct BitonicSort;
+
+impl Sorter for BitonicSort {
+    fn sort_by<T, F>(&self, list: &mut [T], mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        bitonic_sort(list, true, &mut compare, &NoProbe);
+    }
+
+    fn sort_observed_by<T, F>(&self, list: &mut [T], compare: F, observer: &mut dyn SortObserver)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let probe = ObserverProbe::new(list, observer);
+        bitonic_sort(list, true, &mut probe.comparing(compare), &probe);
+    }
+}
+
+fn bitonic_sort<T, F, P>(list: &mut [T], ascending: bool, compare: &mut F, probe: &P)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    P: Probe,
+{
+    let len = list.len();
+    if len < 2 {
+        return;
+    }
+    let mid = len / 2;
+    bitonic_sort(&mut list[..mid], !ascending, compare, probe);
+    bitonic_sort(&mut list[mid..], ascending, compare, probe);
+    bitonic_merge(list, ascending, compare, probe);
+}
+
+fn bitonic_merge<T, F, P>(list: &mut [T], ascending: bool, compare: &mut F, probe: &P)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    P: Probe,
+{
+    let len = list.len();
+    if len < 2 {
+        return;
+    }
+    let m = len.next_power_of_two() / 2;
+    for i in 0..len - m {
+        compare_exchange(list, i, i + m, ascending, compare, probe);
+    }
+    bitonic_merge(&mut list[..m], ascending, compare, probe);
+    bitonic_merge(&mut list[m..], ascending, compare, probe);
+}
+
+///     ## Algorithm
+///     - This sorting algorithm, by Ken Batcher, is a sorting network: the compare-exchanges it runs only depend on the
+///       number of items n, not on their values, so they can all run in parallel within each of its stages.
+///     - It is the iterative formulation of odd-even merge sort known as merge exchange, from Knuth's The Art of
+///       Computer Programming (Algorithm 5.2.2M), which works for any n, not only for powers of two.
+///     - For each power of two p, from the biggest one smaller than n down to 1, it runs a sequence of stages at
+///       decreasing distances d, compare-exchanging `L[i]` with `L[i + d]` for all the i whose bit p has a given value.
+///       Each sequence of stages merges the sorted sub-lists of the items whose indexes differ by multiples of `2 * p`.
+///     - Compare-exchanges swap the two items when they are out of order, so the sort is not stable.
+///     ## Complexity
+///     - The network has `log2(n) * (log2(n) + 1) / 2` stages, and slightly fewer compare-exchanges than `BitonicSort`.
+///     - Therefore, Time Complexity is `O(n log^2 n)` and Space Complexity is `O(1)`, since the algorithm runs in place.
+pub struct OddEvenMergeSort;
+
+impl Sorter for OddEvenMergeSort {
+    fn sort_by<T, F>(&self, list: &mut [T], mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        odd_even_merge_sort(list, &mut compare, &NoProbe);
+    }
+
+    fn sort_observed_by<T, F>(&self, list: &mut [T], compare: F, observer: &mut dyn SortObserver)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let probe = ObserverProbe::new(list, observer);
+        odd_even_merge_sort(list, &mut probe.comparing(compare), &probe);
+    }
+}
+
+/// Sorts the `list` with `OddEvenMergeSort`, and reports its swaps to the `probe`.
+fn odd_even_merge_sort<T, F, P>(list: &mut [T], compare: &mut F, probe: &P)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    P: Probe,
+{
+    let len = list.len();
+    if len < 2 {
+        return;
+    }
+    let top = len.next_power_of_two() / 2;
+    let mut p = top;
+    while p > 0 {
+        let mut q = top;
+        let mut r = 0;
+        let mut d = p;
+        loop {
+            for i in 0..len - d {
+                if i & p == r {
+                    compare_exchange(list, i, i + d, true, compare, probe);
+                }
+            }
+            if q == p {
+                break;
+            }
+            d = q - p;
+            q /= 2;
+            r = p;
+        }
+        p /= 2;
+    }
+}
+
+/// Swaps `list[i]` and `list[j]`, with `i < j`, when they are not in the requested order.
+fn compare_exchange<T, F, P>(
+    list: &mut [T],
+    i: usize,
+    j: usize,
+    ascending: bool,
+    compare: &mut F,
+    probe: &P,
+) where
+    F: FnMut(&T, &T) -> Ordering,
+    P: Probe,
+{
+    let out_of_order = if ascending {
+        compare(&list[j], &list[i]) == Ordering::Less
+    } else {
+        compare(&list[i], &list[j]) == Ordering::Less
+    };
+    if out_of_order {
+        swap(list, i, j, probe);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sorting::{
+        disorder::is_sorted,
+        instrument::NoProbe,
+        sorting_network::{
+            network_sort_by, sort_network, sort_network_by, sort_network_slice_by, sorting_network,
+            BitonicSort, OddEvenMergeSort, MAX_NETWORK_LEN,
+        },
+        tests::{pseudo_random_list, pseudo_random_test, sorting_test},
+        trace::trace,
+        Sorter,
+    };
+
+    /// Checks that the sorter sorts all the lists of 0s and 1s of each length up to `max_len`, which by the 0-1
+    /// principle means that it sorts any list of those lengths, for sorters which only compare-exchange.
+    fn zero_one_test<F: FnMut(&mut [u8])>(max_len: usize, mut sort: F) {
+        for len in 0..=max_len {
+            for bits in 0..1u32 << len {
+                let mut list: Vec<u8> = (0..len).map(|i| (bits >> i & 1) as u8).collect();
+                sort(&mut list);
+                assert!(is_sorted(&list), "{:b} of length {}", bits, len);
+            }
+        }
+    }
+
+    #[test]
+    fn networks_sort_all_zero_one_lists() {
+        zero_one_test(MAX_NETWORK_LEN, |list| {
+            network_sort_by(list, &mut u8::cmp, &NoProbe)
+        });
+    }
+
+    #[test]
+    fn network_sizes() {
+        let sizes: Vec<usize> = (0..=MAX_NETWORK_LEN)
+            .map(|n| sorting_network(n).unwrap().len())
+            .collect();
+        assert_eq!(
+            sizes,
+            [0, 0, 1, 3, 5, 9, 12, 16, 19, 25, 29, 35, 39, 45, 51, 56, 60]
+        );
+        for n in 0..=MAX_NETWORK_LEN {
+            assert!(sorting_network(n)
+                .unwrap()
+                .iter()
+                .all(|&(i, j)| i < j && j < n));
+        }
+        assert_eq!(sorting_network(MAX_NETWORK_LEN + 1), None);
+    }
+
+    macro_rules! sort_network_tests {
+        ($($n:literal),*) => {
+            $({
+                let mut array: [u64; $n] = pseudo_random_list($n, $n + 7, 100).try_into().unwrap();
+                let mut expected = array;
+                expected.sort();
+                let mut by = array;
+                sort_network(&mut array);
+                assert_eq!(array, expected);
+                sort_network_by(&mut by, |a, b| b.cmp(a));
+                expected.reverse();
+                assert_eq!(by, expected);
+            })*
+        };
+    }
+
+    #[test]
+    fn sort_network_test() {
+        sort_network_tests!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16);
+        let mut array = [-1.5f64, 3.0, -7.25, 0.0];
+        sort_network_by(&mut array, f64::total_cmp);
+        assert_eq!(array, [-7.25, -1.5, 0.0, 3.0]);
+    }
+
+    #[test]
+    fn sort_network_slice_by_test() {
+        for len in 0..=MAX_NETWORK_LEN {
+            let mut list = pseudo_random_list(len, len as u64 + 3, 100);
+            let mut expected = list.clone();
+            expected.sort();
+            sort_network_slice_by(&mut list, u64::cmp);
+            assert_eq!(list, expected);
+        }
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "sorting networks are only available for up to 16 items, but the list has 17"
+    )]
+    fn sort_network_slice_by_too_long() {
+        sort_network_slice_by(&mut [0; 17], u8::cmp);
+    }
+
+    #[test]
+    fn network_sort_by_runs_the_network() {
+        let mut list: Vec<u64> = (0..16).rev().collect();
+        let operations = trace(&NetworkSorter, &mut list);
+        assert!(is_sorted(&list));
+        let comparisons = operations
+            .iter()
+            .filter(|operation| operation.to_string().starts_with("compare"))
+            .count();
+        assert_eq!(comparisons, 60);
+    }
+
+    /// Sorts lists of at most `MAX_NETWORK_LEN` items with `network_sort_by`, to trace it.
+    struct NetworkSorter;
+
+    impl Sorter for NetworkSorter {
+        fn sort_by<T, F>(&self, list: &mut [T], mut compare: F)
+        where
+            F: FnMut(&T, &T) -> std::cmp::Ordering,
+        {
+            network_sort_by(list, &mut compare, &NoProbe);
+        }
+    }
+
+    #[test]
+    fn bitonic_sort_test() {
+        sorting_test(BitonicSort);
+        pseudo_random_test(BitonicSort);
+        zero_one_test(14, |list| BitonicSort.sort(list));
+    }
+
+    #[test]
+    fn odd_even_merge_sort_test() {
+        sorting_test(OddEvenMergeSort);
+        pseudo_random_test(OddEvenMergeSort);
+        zero_one_test(14, |list| OddEvenMergeSort.sort(list));
+    }
+
+    #[test]
+    fn odd_even_merge_sort_is_smaller_than_bitonic_sort() {
+        for len in [16, 100, 1024] {
+            let list = pseudo_random_list(len, len as u64, 1000);
+            let bitonic = trace(&BitonicSort, &mut list.clone()).len();
+            let odd_even = trace(&OddEvenMergeSort, &mut list.clone()).len();
+            assert!(
+                odd_even <= bitonic,
+                "{} > {} for {} items",
+                odd_even,
+                bitonic,
+                len
+            );
+        }
+    }
+}
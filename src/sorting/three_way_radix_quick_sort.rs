@@ -0,0 +1,175 @@
+use std::cmp::Ordering;
+
+use crate::sorting::{
+    insertion_sort::insertion_sort,
+    instrument::{swap, NoProbe, ObserverProbe, Probe, SortObserver},
+    msd_radix_sort::byte_at,
+};
+
+/// Sub-lists up to this length are sorted with `InsertionSort`, comparing the remaining suffixes only.
+const INSERTION_SORT_CUTOFF: usize = 16;
+
+///     ## Algorithm
+///     - This sorting algorithm, by Bentley and Sedgewick, combines `ThreeWayQuickSort` with `msd_radix_sort`: it
+///       partitions the list L of byte strings in three parts, by the byte at index `d` of each string, initially 0.
+///     - The pivot is the byte at index `d` of the middle string. Strings with a smaller byte go first, then strings
+///       with the same byte, then strings with a bigger byte. Strings too short to have a byte at index `d` are smaller
+///       than any other.
+///     - The first and last parts are sorted recursively on the same byte `d`, while the middle part, where all strings
+///       share the same prefix up to `d`, is sorted on the next byte, `d + 1`.
+///     - Sub-lists of at most `INSERTION_SORT_CUTOFF` strings are sorted with `InsertionSort`.
+///     ## Complexity
+///     - Common prefixes are scanned once per partitioning, instead of once per comparison, and no byte counts are
+///       needed, which makes this algorithm fast on strings sharing long prefixes, such as URLs or paths.
+///     - Time Complexity is `O(n log n + D)` on average, where D is the total length of the distinguishing prefixes.
+///       Space Complexity is `O(log n + w)`, for strings of length w, for the recursion. The sort is not stable.
+pub fn three_way_radix_quick_sort<T: AsRef<[u8]>>(list: &mut [T]) {
+    three_way_radix_sort_by(list, 0, &|item: &T, depth| byte_at(item, depth), &NoProbe);
+}
+
+/// Sorts the list like `three_way_radix_quick_sort`, and reports its swaps, and the comparisons of suffixes run on
+/// small sub-lists, to the `observer`. See `observe_by`.
+pub fn three_way_radix_quick_sort_observed<T: AsRef<[u8]>>(
+    list: &mut [T],
+    observer: &mut dyn SortObserver,
+) {
+    let probe = ObserverProbe::new(list, observer);
+    three_way_radix_sort_by(list, 0, &|item: &T, depth| byte_at(item, depth), &probe);
+}
+
+/// Runs `three_way_radix_quick_sort` from byte `depth`, reading the bytes of each item with `byte`, which returns
+/// the byte at the given index shifted by one, or 0 when the item is too short to have one, and reports its swaps and
+/// comparisons to the `probe`.
+pub(crate) fn three_way_radix_sort_by<T, B, P>(
+    mut list: &mut [T],
+    mut depth: usize,
+    byte: &B,
+    probe: &P,
+) where
+    B: Fn(&T, usize) -> usize,
+    P: Probe,
+{
+    while list.len() > INSERTION_SORT_CUTOFF {
+        let middle = list.len() / 2;
+        swap(list, 0, middle, probe);
+        let pivot = byte(&list[0], depth);
+        let mut lt = 0;
+        let mut gt = list.len();
+        let mut i = 1;
+        while i < gt {
+            match byte(&list[i], depth).cmp(&pivot) {
+                Ordering::Less => {
+                    swap(list, lt, i, probe);
+                    lt += 1;
+                    i += 1;
+                }
+                Ordering::Greater => {
+                    gt -= 1;
+                    swap(list, i, gt, probe);
+                }
+                Ordering::Equal => i += 1,
+            }
+        }
+        let (smaller, rest) = list.split_at_mut(lt);
+        let (equal, bigger) = rest.split_at_mut(gt - lt);
+        three_way_radix_sort_by(smaller, depth, byte, probe);
+        three_way_radix_sort_by(bigger, depth, byte, probe);
+        if pivot == 0 {
+            return;
+        }
+        list = equal;
+        depth += 1;
+    }
+    let mut compare = |a: &T, b: &T| {
+        probe.compared(a, b);
+        suffix_cmp_by(a, b, depth, byte)
+    };
+    insertion_sort(list, &mut compare, probe);
+}
+
+/// Compares the suffixes of `a` and `b` from byte `depth`, reading the bytes with `byte`.
+pub(crate) fn suffix_cmp_by<T, B>(a: &T, b: &T, mut depth: usize, byte: &B) -> Ordering
+where
+    B: Fn(&T, usize) -> usize,
+{
+    loop {
+        let (x, y) = (byte(a, depth), byte(b, depth));
+        if x != y || x == 0 {
+            return x.cmp(&y);
+        }
+        depth += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sorting::{
+        tests::{path_corpus, url_corpus},
+        three_way_radix_quick_sort::three_way_radix_quick_sort,
+        InsertionSort, Sorter,
+    };
+
+    #[test]
+    fn three_way_radix_quick_sort_test() {
+        let mut list = vec![
+            "she",
+            "sells",
+            "seashells",
+            "by",
+            "the",
+            "sea",
+            "shore",
+            "",
+            "s",
+            "sea",
+        ];
+        three_way_radix_quick_sort(&mut list);
+        assert_eq!(
+            list,
+            vec![
+                "",
+                "by",
+                "s",
+                "sea",
+                "sea",
+                "seashells",
+                "sells",
+                "she",
+                "shore",
+                "the"
+            ]
+        );
+
+        let mut empty: Vec<String> = vec![];
+        three_way_radix_quick_sort(&mut empty);
+        assert_eq!(empty, Vec::<String>::new());
+    }
+
+    #[test]
+    fn urls() {
+        let mut list = url_corpus(1000);
+        let mut expected = list.clone();
+        InsertionSort.sort(&mut expected);
+        three_way_radix_quick_sort(&mut list);
+        assert_eq!(list, expected);
+    }
+
+    #[test]
+    fn paths() {
+        let mut list: Vec<Vec<u8>> = path_corpus(1000)
+            .into_iter()
+            .map(String::into_bytes)
+            .collect();
+        let mut expected = list.clone();
+        InsertionSort.sort(&mut expected);
+        three_way_radix_quick_sort(&mut list);
+        assert_eq!(list, expected);
+
+        let corpus = path_corpus(1000);
+        let mut list: Vec<&str> = corpus.iter().map(String::as_str).collect();
+        let mut expected = list.clone();
+        InsertionSort.sort(&mut expected);
+        three_way_radix_quick_sort(&mut list);
+        assert_eq!(list, expected);
+    }
+}
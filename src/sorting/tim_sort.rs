@@ -0,0 +1,401 @@
+use std::{cmp::Ordering, ptr, slice};
+
+use crate::{
+    sorting::{
+        insertion_sort::binary_insertion_sort,
+        instrument::{copy_nonoverlapping, reverse, NoProbe, ObserverProbe, Probe},
+        merge_sort::MergeHole,
+        search::gallop,
+        SortObserver, Sorter,
+    },
+    stack::{array_stack::ArrayStack, Stack},
+};
+
+/// Lists shorter than this are sorted with a single binary insertion sort, and runs are at least half this long.
+const MIN_MERGE: usize = 64;
+
+/// The number of consecutive items a merge has to take from the same run before switching to galloping mode.
+const MIN_GALLOP: usize = 7;
+
+/// A sorted sub-list `list[start..start + len]`.
+#[derive(Clone, Copy)]
+struct Run {
+    start: usize,
+    len: usize,
+}
+
+///     ## Algorithm
+///     - This sorting algorithm, by Tim Peters, is an adaptive merge sort which takes advantage of the runs, i.e. the
+///       sorted sub-lists, already present in the list L.
+///     - L is scanned from left to right, looking for the next run, either non-descending or strictly descending. A
+///       strictly descending run is reversed in place, which keeps the sort stable.
+///     - Runs shorter than a minimum length, between 32 and 64 and chosen so that the number of runs is a power of two
+///       or slightly less, are extended with a binary insertion sort.
+///     - Runs are pushed onto a `Stack`, and adjacent runs are merged as soon as the lengths of the runs on top of the
+///       stack, X, Y and Z from the bottom, break one of the invariants `|X| > |Y| + |Z|` and `|Y| > |Z|`. This keeps
+///       merges balanced and the stack at most `log(n)` runs deep.
+///     - The merge first skips the items which are already in place, at the start of the left run and at the end of the
+///       right run. When one run keeps providing the next item, the merge switches to galloping mode, where it looks
+///       for the number of items to take from that run with an exponential search, and moves them all at once.
+///     ## Complexity
+///     - On a list already made of a few runs, such as a sorted or reverse-sorted list, the algorithm only runs around
+///       n comparisons, and galloping makes merges of very unbalanced or clustered runs sub-linear.
+///     - Therefore, Time Complexity is `O(n)` in the best case and `O(n log n)` in the worst case, and Space Complexity
+///       is `O(n)`, for the merge buffer.
+pub struct TimSort;
+
+impl Sorter for TimSort {
+    fn sort_by<T, F>(&self, list: &mut [T], mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        tim_sort(list, &mut compare, &NoProbe);
+    }
+
+    fn sort_observed_by<T, F>(&self, list: &mut [T], compare: F, observer: &mut dyn SortObserver)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let probe = ObserverProbe::new(list, observer);
+        tim_sort(list, &mut probe.comparing(compare), &probe);
+    }
+}
+
+/// Sorts the `list` with `TimSort`, and reports its moves and swaps to the `probe`.
+fn tim_sort<T, F, P>(list: &mut [T], compare: &mut F, probe: &P)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    P: Probe,
+{
+    let len = list.len();
+    if len < 2 {
+        return;
+    }
+    let min_run = min_run_length(len);
+    let mut runs = ArrayStack::new(16);
+    let mut buffer = Vec::new();
+    let mut min_gallop = MIN_GALLOP;
+    let mut start = 0;
+    while start < len {
+        let mut run_len = count_run_and_make_ascending(&mut list[start..], compare, probe);
+        if run_len < min_run {
+            let forced = std::cmp::min(min_run, len - start);
+            binary_insertion_sort(&mut list[start..start + forced], run_len, compare, probe);
+            run_len = forced;
+        }
+        runs.push(Run {
+            start,
+            len: run_len,
+        });
+        start += run_len;
+        merge_collapse(
+            &mut runs,
+            list,
+            &mut buffer,
+            compare,
+            &mut min_gallop,
+            false,
+            probe,
+        );
+    }
+    merge_collapse(
+        &mut runs,
+        list,
+        &mut buffer,
+        compare,
+        &mut min_gallop,
+        true,
+        probe,
+    );
+}
+
+/// Returns `len` itself when smaller than `MIN_MERGE`, otherwise a length `k` between `MIN_MERGE / 2` and `MIN_MERGE`,
+/// such that `len / k` is a power of two or slightly less.
+fn min_run_length(mut len: usize) -> usize {
+    let mut remainder = 0;
+    while len >= MIN_MERGE {
+        remainder |= len & 1;
+        len >>= 1;
+    }
+    len + remainder
+}
+
+/// Returns the length of the run at the start of the list, reversing it when it is strictly descending.
+fn count_run_and_make_ascending<T, F, P>(list: &mut [T], compare: &mut F, probe: &P) -> usize
+where
+    F: FnMut(&T, &T) -> Ordering,
+    P: Probe,
+{
+    if list.len() < 2 {
+        return list.len();
+    }
+    let mut end = 2;
+    if compare(&list[1], &list[0]) == Ordering::Less {
+        while end < list.len() && compare(&list[end], &list[end - 1]) == Ordering::Less {
+            end += 1;
+        }
+        reverse(&mut list[..end], probe);
+    } else {
+        while end < list.len() && compare(&list[end], &list[end - 1]) != Ordering::Less {
+            end += 1;
+        }
+    }
+    end
+}
+
+/// Merges the runs on top of the stack until the invariants on their lengths hold again, or until a single run is
+/// left, when `force` is set.
+fn merge_collapse<T, F, P>(
+    runs: &mut ArrayStack<Run>,
+    list: &mut [T],
+    buffer: &mut Vec<T>,
+    compare: &mut F,
+    min_gallop: &mut usize,
+    force: bool,
+    probe: &P,
+) where
+    F: FnMut(&T, &T) -> Ordering,
+    P: Probe,
+{
+    while runs.count() > 1 {
+        // The stack only exposes its top item, so up to four runs are popped to check the invariants.
+        let mut top = Vec::with_capacity(4);
+        while top.len() < 4 && runs.count() > 0 {
+            top.push(runs.pop());
+        }
+        top.reverse();
+        let n = top.len();
+        let should_merge = force
+            || top[n - 2].len <= top[n - 1].len
+            || (n >= 3 && top[n - 3].len <= top[n - 2].len + top[n - 1].len)
+            || (n >= 4 && top[n - 4].len <= top[n - 3].len + top[n - 2].len);
+        if should_merge {
+            let at = if n >= 3 && top[n - 3].len < top[n - 1].len {
+                n - 3
+            } else {
+                n - 2
+            };
+            let (left, right) = (top[at], top[at + 1]);
+            merge_at(
+                &mut list[left.start..right.start + right.len],
+                left.len,
+                buffer,
+                compare,
+                min_gallop,
+                probe,
+            );
+            top[at] = Run {
+                start: left.start,
+                len: left.len + right.len,
+            };
+            top.remove(at + 1);
+        }
+        for run in top {
+            runs.push(run);
+        }
+        if !should_merge {
+            break;
+        }
+    }
+}
+
+/// Merges the adjacent runs `list[..mid]` and `list[mid..]`, leaving out the items already in their final position.
+fn merge_at<T, F, P>(
+    list: &mut [T],
+    mid: usize,
+    buffer: &mut Vec<T>,
+    compare: &mut F,
+    min_gallop: &mut usize,
+    probe: &P,
+) where
+    F: FnMut(&T, &T) -> Ordering,
+    P: Probe,
+{
+    let (left, right) = list.split_at(mid);
+    let skip = gallop(left, |item| compare(&right[0], item) != Ordering::Less);
+    let list = &mut list[skip..];
+    let mid = mid - skip;
+    if mid == 0 {
+        return;
+    }
+    let (left, right) = list.split_at(mid);
+    let keep = gallop(right, |item| {
+        compare(item, &left[mid - 1]) == Ordering::Less
+    });
+    merge_lo(
+        &mut list[..mid + keep],
+        mid,
+        buffer,
+        compare,
+        min_gallop,
+        probe,
+    );
+}
+
+/// Merges `list[..mid]` and `list[mid..]`, copying `list[..mid]` into `buffer`, and switching to galloping mode when
+/// either run provides at least `min_gallop` consecutive items.
+/// `min_gallop` is lowered while galloping pays off, and raised when it doesn't.
+fn merge_lo<T, F, P>(
+    list: &mut [T],
+    mid: usize,
+    buffer: &mut Vec<T>,
+    compare: &mut F,
+    min_gallop: &mut usize,
+    probe: &P,
+) where
+    F: FnMut(&T, &T) -> Ordering,
+    P: Probe,
+{
+    let len = list.len();
+    buffer.reserve(mid);
+    let list = list.as_mut_ptr();
+    // SAFETY: as in `merge_sort::merge`, every item is owned either by `hole` or by `right..right_end`, and the gap
+    // between `hole.dest` and `right` is always as long as the number of items left in `hole`. Right items may overlap
+    // their destination when moved in bulk, so they are moved with `ptr::copy`, and only reported once the pointers are
+    // updated, so that a panicking observer can't leave an item owned twice.
+    unsafe {
+        copy_nonoverlapping(list, buffer.as_mut_ptr(), mid, probe);
+        let mut hole = MergeHole {
+            start: buffer.as_mut_ptr(),
+            end: buffer.as_mut_ptr().add(mid),
+            dest: list,
+            probe,
+        };
+        let mut right = list.add(mid);
+        let right_end = list.add(len);
+        'merge: loop {
+            let mut left_wins = 0;
+            let mut right_wins = 0;
+            loop {
+                if hole.start == hole.end || right == right_end {
+                    break 'merge;
+                }
+                if compare(&*right, &*hole.start) == Ordering::Less {
+                    copy_nonoverlapping(right, hole.dest, 1, probe);
+                    right = right.add(1);
+                    right_wins += 1;
+                    left_wins = 0;
+                } else {
+                    copy_nonoverlapping(hole.start, hole.dest, 1, probe);
+                    hole.start = hole.start.add(1);
+                    left_wins += 1;
+                    right_wins = 0;
+                }
+                hole.dest = hole.dest.add(1);
+                if left_wins >= *min_gallop || right_wins >= *min_gallop {
+                    break;
+                }
+            }
+            loop {
+                if hole.start == hole.end || right == right_end {
+                    break 'merge;
+                }
+                let left_run =
+                    slice::from_raw_parts(hole.start, hole.end.offset_from(hole.start) as usize);
+                let left_count = gallop(left_run, |item| compare(&*right, item) != Ordering::Less);
+                copy_nonoverlapping(hole.start, hole.dest, left_count, probe);
+                hole.start = hole.start.add(left_count);
+                hole.dest = hole.dest.add(left_count);
+                if hole.start == hole.end {
+                    break 'merge;
+                }
+                copy_nonoverlapping(right, hole.dest, 1, probe);
+                right = right.add(1);
+                hole.dest = hole.dest.add(1);
+                if right == right_end {
+                    break 'merge;
+                }
+
+                let right_run = slice::from_raw_parts(right, right_end.offset_from(right) as usize);
+                let right_count = gallop(right_run, |item| {
+                    compare(item, &*hole.start) == Ordering::Less
+                });
+                ptr::copy(right, hole.dest, right_count);
+                right = right.add(right_count);
+                hole.dest = hole.dest.add(right_count);
+                probe.moved(
+                    right.sub(right_count),
+                    hole.dest.sub(right_count),
+                    right_count,
+                );
+                if right == right_end {
+                    break 'merge;
+                }
+                copy_nonoverlapping(hole.start, hole.dest, 1, probe);
+                hole.start = hole.start.add(1);
+                hole.dest = hole.dest.add(1);
+
+                if left_count < MIN_GALLOP && right_count < MIN_GALLOP {
+                    *min_gallop += 1;
+                    break;
+                }
+                *min_gallop = std::cmp::max(1, *min_gallop - 1);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sorting::{
+        instrument,
+        tests::{
+            adversarial_test, panic_safety_test, pseudo_random_list, pseudo_random_test,
+            sorting_test, stability_test,
+        },
+        tim_sort::TimSort,
+    };
+
+    #[test]
+    fn tim_sort_test() {
+        sorting_test(TimSort);
+        pseudo_random_test(TimSort);
+        adversarial_test(TimSort);
+    }
+
+    #[test]
+    fn tim_sort_stability() {
+        stability_test(TimSort);
+    }
+
+    #[test]
+    fn tim_sort_panic_safety() {
+        panic_safety_test(TimSort);
+    }
+
+    #[test]
+    fn linear_on_sorted_input() {
+        let len = 10_000;
+        let mut sorted: Vec<usize> = (0..len).collect();
+        assert_eq!(instrument(&TimSort, &mut sorted).comparisons, len - 1);
+        let mut reverse_sorted: Vec<usize> = (0..len).rev().collect();
+        assert_eq!(
+            instrument(&TimSort, &mut reverse_sorted).comparisons,
+            len - 1
+        );
+        assert_eq!(reverse_sorted, sorted);
+    }
+
+    #[test]
+    fn mostly_sorted_input() {
+        let len = 10_000;
+        let mut list: Vec<u64> = (0..len as u64).collect();
+        list.extend(pseudo_random_list(100, 3, len as u64));
+        let mut expected = list.clone();
+        expected.sort();
+        let comparisons = instrument(&TimSort, &mut list).comparisons;
+        assert_eq!(list, expected);
+        assert!(comparisons < 2 * len);
+    }
+
+    #[test]
+    fn galloping_merges() {
+        // Two runs made of interleaved blocks of 100 items, where galloping moves a whole block at a time.
+        let len = 10_000;
+        let mut list: Vec<usize> = (0..len).filter(|item| item / 100 % 2 == 0).collect();
+        list.extend((0..len).filter(|item| item / 100 % 2 == 1));
+        let comparisons = instrument(&TimSort, &mut list).comparisons;
+        assert_eq!(list, (0..len).collect::<Vec<usize>>());
+        assert!(comparisons < len + len / 4);
+    }
+}
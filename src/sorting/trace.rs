@@ -0,0 +1,281 @@
+use std::{cmp::Ordering, collections::HashMap, fmt, slice};
+
+use crate::sorting::{
+    instrument::{observe_by, Location, SortObserver},
+    Sorter,
+};
+
+/// An operation run by a sorter on the list it sorts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    /// Two items are compared.
+    Compare(Location, Location),
+    /// The items at the two indexes of the list are swapped.
+    Swap(usize, usize),
+    /// The item at the first location is copied to the second one, replacing the previous item.
+    Move(Location, Location),
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Location::List(index) => write!(f, "L[{}]", index),
+            Location::Aux(_) => write!(f, "buffer"),
+        }
+    }
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Operation::Compare(a, b) => write!(f, "compare {} with {}", a, b),
+            Operation::Swap(a, b) => write!(f, "swap L[{}] and L[{}]", a, b),
+            Operation::Move(from, to) => write!(f, "move {} to {}", from, to),
+        }
+    }
+}
+
+impl Operation {
+    /// Returns the indexes of the list involved in the operation.
+    pub fn indexes(&self) -> Vec<usize> {
+        let (a, b) = match *self {
+            Operation::Compare(a, b) | Operation::Move(a, b) => (a, b),
+            Operation::Swap(a, b) => (Location::List(a), Location::List(b)),
+        };
+        [a, b]
+            .into_iter()
+            .filter_map(|location| match location {
+                Location::List(index) => Some(index),
+                Location::Aux(_) => None,
+            })
+            .collect()
+    }
+}
+
+/// Passes every operation it observes to a callback.
+struct Tracer<C>(C);
+
+impl<C: FnMut(Operation)> SortObserver for Tracer<C> {
+    fn compare(&mut self, a: Location, b: Location) {
+        (self.0)(Operation::Compare(a, b));
+    }
+
+    fn swap(&mut self, a: usize, b: usize) {
+        (self.0)(Operation::Swap(a, b));
+    }
+
+    fn move_item(&mut self, from: Location, to: Location) {
+        (self.0)(Operation::Move(from, to));
+    }
+}
+
+/// Sorts the provided `list` in ascending order with the given `sorter`, and returns the operations it ran, in order.
+pub fn trace<S: Sorter, T: Ord>(sorter: &S, list: &mut [T]) -> Vec<Operation> {
+    let mut operations = vec![];
+    trace_by(sorter, list, T::cmp, |operation| operations.push(operation));
+    operations
+}
+
+/// Sorts the provided `list` with the given `sorter`, according to the order defined by the `compare` function, and
+/// calls `callback` with every operation it runs, in order. See `observe_by`.
+pub fn trace_by<S, T, F, C>(sorter: &S, list: &mut [T], compare: F, callback: C)
+where
+    S: Sorter,
+    F: FnMut(&T, &T) -> Ordering,
+    C: FnMut(Operation),
+{
+    observe_by(sorter, list, compare, &mut Tracer(callback));
+}
+
+/// Replays a trace, one operation at a time, on a copy of the list it was recorded on, to show the state of the list
+/// after each operation.
+///
+/// ```
+/// use more_rust_structures::sorting::{trace, InsertionSort, Replay};
+///
+/// let list = [3, 1, 2];
+/// let operations = trace(&InsertionSort, &mut list.clone());
+/// let mut replay = Replay::new(&list, &operations);
+/// replay.step();
+/// assert_eq!(replay.list(), [3, 1, 2]);
+/// replay.step();
+/// assert_eq!(replay.list(), [1, 3, 2]);
+/// ```
+pub struct Replay<'a, T> {
+    list: Vec<T>,
+    aux: HashMap<usize, T>,
+    operations: slice::Iter<'a, Operation>,
+}
+
+impl<'a, T: Clone> Replay<'a, T> {
+    /// Starts a replay of the `operations` traced while sorting a list equal to `list`.
+    pub fn new(list: &[T], operations: &'a [Operation]) -> Self {
+        Replay {
+            list: list.to_vec(),
+            aux: HashMap::new(),
+            operations: operations.iter(),
+        }
+    }
+
+    /// The state of the list after the operations replayed so far.
+    pub fn list(&self) -> &[T] {
+        &self.list
+    }
+
+    /// Applies the next operation to the list, and returns it, or returns `None` when all of them have been replayed.
+    ///     ## Panics
+    ///     - When an operation moves an item out of auxiliary memory which wasn't moved in there first, because the
+    ///       trace wasn't recorded on this list.
+    pub fn step(&mut self) -> Option<Operation> {
+        let operation = *self.operations.next()?;
+        match operation {
+            Operation::Compare(_, _) => {}
+            Operation::Swap(a, b) => self.list.swap(a, b),
+            Operation::Move(from, to) => {
+                let item = match from {
+                    Location::List(index) => self.list[index].clone(),
+                    Location::Aux(address) => match self.aux.get(&address) {
+                        Some(item) => item.clone(),
+                        None => panic!(
+                            "{} moves an item the trace never put in the buffer",
+                            operation
+                        ),
+                    },
+                };
+                match to {
+                    Location::List(index) => self.list[index] = item,
+                    Location::Aux(address) => {
+                        self.aux.insert(address, item);
+                    }
+                }
+            }
+        }
+        Some(operation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sorting::{
+        burst_sort_observed, counting_sort_observed,
+        instrument::{Location, SortObserver},
+        lsd_radix_sort_by_key_observed, msd_radix_sort_observed,
+        tests::{pseudo_random_list, url_corpus},
+        three_way_radix_quick_sort_observed,
+        trace::{trace, Operation, Replay, Tracer},
+        BinaryInsertionSort, BitonicSort, BottomUpMergeSort, DualPivotQuickSort, HeapSort,
+        InsertionSort, IntroSort, LibrarySort, MedianOfThreeQuickSort, MergeSort, OddEvenMergeSort,
+        PdqSort, RandomizedQuickSort, SelectionSort, ShellSort, Sorter, ThreeWayQuickSort, TimSort,
+    };
+
+    fn replay_test<S: Sorter>(sorter: S) {
+        for len in [0, 1, 2, 10, 100, 500] {
+            let original = pseudo_random_list(len, len as u64 + 3, 50);
+            let mut sorted = original.clone();
+            let operations = trace(&sorter, &mut sorted);
+            let mut replay = Replay::new(&original, &operations);
+            while replay.step().is_some() {}
+            assert_eq!(replay.list(), sorted);
+        }
+    }
+
+    #[test]
+    fn replays_reproduce_the_sort() {
+        replay_test(SelectionSort);
+        replay_test(InsertionSort);
+        replay_test(BinaryInsertionSort);
+        replay_test(LibrarySort);
+        replay_test(ShellSort::default());
+        replay_test(HeapSort);
+        replay_test(MergeSort);
+        replay_test(BottomUpMergeSort);
+        replay_test(RandomizedQuickSort);
+        replay_test(MedianOfThreeQuickSort);
+        replay_test(ThreeWayQuickSort);
+        replay_test(DualPivotQuickSort);
+        replay_test(IntroSort);
+        replay_test(TimSort);
+        replay_test(PdqSort);
+        replay_test(BitonicSort);
+        replay_test(OddEvenMergeSort);
+    }
+
+    /// Checks that replaying the operations reported by `sort` reproduces it, for the sorts which aren't `Sorter`s.
+    fn replay_observed_test<T, F>(original: &[T], mut sort: F) -> Vec<Operation>
+    where
+        T: Clone + PartialEq + std::fmt::Debug,
+        F: FnMut(&mut [T], &mut dyn SortObserver),
+    {
+        let mut sorted = original.to_vec();
+        let mut operations = vec![];
+        sort(
+            &mut sorted,
+            &mut Tracer(|operation| operations.push(operation)),
+        );
+        let mut replay = Replay::new(original, &operations);
+        while replay.step().is_some() {}
+        assert_eq!(replay.list(), sorted);
+        operations
+    }
+
+    #[test]
+    fn replays_reproduce_the_key_sorts() {
+        let numbers = pseudo_random_list(500, 11, 1000);
+        let operations = replay_observed_test(&numbers, |list, observer| {
+            counting_sort_observed(list, 1000, |&item| item as usize, observer)
+        });
+        assert!(!operations.is_empty());
+        replay_observed_test(&numbers, |list, observer| {
+            lsd_radix_sort_by_key_observed(list, |&item| item, observer)
+        });
+
+        let urls = url_corpus(500);
+        replay_observed_test(&urls, msd_radix_sort_observed);
+        replay_observed_test(&urls, burst_sort_observed);
+        let operations = replay_observed_test(&urls, three_way_radix_quick_sort_observed);
+        assert!(operations
+            .iter()
+            .any(|operation| matches!(operation, Operation::Compare(..))));
+    }
+
+    #[test]
+    fn insertion_sort_trace() {
+        let operations = trace(&InsertionSort, &mut [3, 1, 2]);
+        assert_eq!(
+            operations,
+            vec![
+                Operation::Compare(Location::List(1), Location::List(0)),
+                Operation::Swap(1, 0),
+                Operation::Compare(Location::List(2), Location::List(1)),
+                Operation::Swap(2, 1),
+                Operation::Compare(Location::List(1), Location::List(0)),
+            ]
+        );
+        assert_eq!(operations[0].to_string(), "compare L[1] with L[0]");
+        assert_eq!(operations[1].to_string(), "swap L[1] and L[0]");
+    }
+
+    #[test]
+    fn merge_sort_trace_uses_the_buffer() {
+        let mut list: Vec<u64> = (0..20).rev().collect();
+        let operations = trace(&MergeSort, &mut list);
+        let to_buffer = operations
+            .iter()
+            .find(|operation| {
+                matches!(
+                    operation,
+                    Operation::Move(Location::List(_), Location::Aux(_))
+                )
+            })
+            .unwrap();
+        assert!(to_buffer.to_string().ends_with("to buffer"));
+        assert_eq!(to_buffer.indexes().len(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "moves an item the trace never put in the buffer")]
+    fn replay_mismatched_trace() {
+        let operations = [Operation::Move(Location::Aux(8), Location::List(0))];
+        Replay::new(&[1], &operations).step();
+    }
+}
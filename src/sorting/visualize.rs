@@ -0,0 +1,197 @@
+use std::{
+    io::{self, Write},
+    thread,
+    time::Duration,
+};
+
+use crate::sorting::trace::{Operation, Replay};
+
+/// The number of rows of the bars of an ASCII frame.
+const ASCII_HEIGHT: usize = 12;
+
+/// The height, in pixels, of the tallest bar of an HTML frame.
+const HTML_HEIGHT: usize = 120;
+
+/// Draws the values as vertical bars, `height` rows tall, with the bars at the `highlighted` indexes drawn with `@`
+/// instead of `#`. Each bar is as tall as its value, scaled so that the biggest one fills all the rows.
+pub fn ascii_frame(values: &[u64], highlighted: &[usize], height: usize) -> String {
+    let max = values.iter().copied().max().unwrap_or(0).max(1);
+    let mut frame = String::new();
+    for row in (1..=height as u64).rev() {
+        let line: Vec<&str> = values
+            .iter()
+            .enumerate()
+            .map(|(index, &value)| {
+                if (value as u128) * (height as u128) < (row as u128) * (max as u128) {
+                    " "
+                } else if highlighted.contains(&index) {
+                    "@"
+                } else {
+                    "#"
+                }
+            })
+            .collect();
+        frame.push_str(line.join(" ").trim_end());
+        frame.push('\n');
+    }
+    frame
+}
+
+/// Plays the sort traced by `operations` on `values` as an animation in the terminal: before each frame the screen
+/// is cleared, then the bars are drawn with `ascii_frame`, followed by the operation which led to them, and the next
+/// frame is drawn after `delay`.
+pub fn render_ascii<W: Write>(
+    values: &[u64],
+    operations: &[Operation],
+    out: &mut W,
+    delay: Duration,
+) -> io::Result<()> {
+    let mut replay = Replay::new(values, operations);
+    let mut caption = String::from("start");
+    let mut highlighted = vec![];
+    let mut step = 0;
+    loop {
+        write!(out, "\x1b[2J\x1b[H")?;
+        write!(
+            out,
+            "{}",
+            ascii_frame(replay.list(), &highlighted, ASCII_HEIGHT)
+        )?;
+        writeln!(out, "{}/{}: {}", step, operations.len(), caption)?;
+        out.flush()?;
+        let Some(operation) = replay.step() else {
+            return Ok(());
+        };
+        thread::sleep(delay);
+        caption = operation.to_string();
+        highlighted = operation.indexes();
+        step += 1;
+    }
+}
+
+/// Writes a self-contained HTML page, without scripts or images, which shows the state of the list after each of the
+/// `operations` traced while sorting `values`, as a sequence of bar charts. The bars involved in each operation are
+/// drawn in red.
+pub fn render_html<W: Write>(
+    values: &[u64],
+    operations: &[Operation],
+    title: &str,
+    out: &mut W,
+) -> io::Result<()> {
+    let max = values.iter().copied().max().unwrap_or(0).max(1);
+    let title = escape_html(title);
+    writeln!(out, "<!DOCTYPE html>")?;
+    writeln!(out, "<html>")?;
+    writeln!(out, "<head>")?;
+    writeln!(out, "<meta charset=\"utf-8\">")?;
+    writeln!(out, "<title>{}</title>", title)?;
+    writeln!(out, "<style>")?;
+    writeln!(out, "body {{ font-family: monospace; }}")?;
+    writeln!(
+        out,
+        ".bars {{ display: flex; align-items: flex-end; gap: 2px; height: {}px; }}",
+        HTML_HEIGHT
+    )?;
+    writeln!(out, ".bar {{ width: 10px; background: #888; }}")?;
+    writeln!(out, ".bar.active {{ background: #d33; }}")?;
+    writeln!(out, "</style>")?;
+    writeln!(out, "</head>")?;
+    writeln!(out, "<body>")?;
+    writeln!(out, "<h1>{}</h1>", title)?;
+    let mut replay = Replay::new(values, operations);
+    let mut caption = String::from("start");
+    let mut highlighted = vec![];
+    let mut step = 0;
+    loop {
+        writeln!(out, "<div class=\"frame\">")?;
+        writeln!(out, "<p>{}/{}: {}</p>", step, operations.len(), caption)?;
+        writeln!(out, "<div class=\"bars\">")?;
+        for (index, &value) in replay.list().iter().enumerate() {
+            let class = if highlighted.contains(&index) {
+                "bar active"
+            } else {
+                "bar"
+            };
+            let height = (value as u128 * HTML_HEIGHT as u128 / max as u128) as usize;
+            writeln!(
+                out,
+                "<div class=\"{}\" style=\"height: {}px\" title=\"{}\"></div>",
+                class, height, value
+            )?;
+        }
+        writeln!(out, "</div>")?;
+        writeln!(out, "</div>")?;
+        let Some(operation) = replay.step() else {
+            break;
+        };
+        caption = operation.to_string();
+        highlighted = operation.indexes();
+        step += 1;
+    }
+    writeln!(out, "</body>")?;
+    writeln!(out, "</html>")
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::sorting::{
+        trace::trace,
+        visualize::{ascii_frame, render_ascii, render_html},
+        InsertionSort, MergeSort, SelectionSort,
+    };
+
+    #[test]
+    fn ascii_frame_test() {
+        assert_eq!(
+            ascii_frame(&[1, 4, 2, 0], &[2], 4),
+            ["  #", "  #", "  # @", "# # @", ""].join("\n")
+        );
+        assert_eq!(ascii_frame(&[], &[], 2), "\n\n");
+    }
+
+    #[test]
+    fn render_ascii_test() {
+        let values = [3, 1, 2];
+        let operations = trace(&InsertionSort, &mut values.clone());
+        let mut out = vec![];
+        render_ascii(&values, &operations, &mut out, Duration::ZERO).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert_eq!(out.matches("\x1b[2J").count(), operations.len() + 1);
+        assert!(out.contains("0/5: start"));
+        assert!(out.contains("2/5: swap L[1] and L[0]"));
+        assert!(out.ends_with("5/5: compare L[1] with L[0]\n"));
+    }
+
+    #[test]
+    fn render_html_test() {
+        let values: Vec<u64> = (1..=8).rev().collect();
+        for operations in [
+            trace(&SelectionSort, &mut values.clone()),
+            trace(&MergeSort, &mut values.clone()),
+        ] {
+            let mut out = vec![];
+            render_html(&values, &operations, "Sorting <8> items", &mut out).unwrap();
+            let out = String::from_utf8(out).unwrap();
+            assert!(out.starts_with("<!DOCTYPE html>"));
+            assert!(out.contains("<title>Sorting &lt;8&gt; items</title>"));
+            assert!(!out.contains("<script"));
+            assert_eq!(
+                out.matches("<div class=\"frame\">").count(),
+                operations.len() + 1
+            );
+            assert_eq!(
+                out.matches("style=\"height").count(),
+                8 * (operations.len() + 1)
+            );
+        }
+    }
+}
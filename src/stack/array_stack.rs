@@ -1,7 +1,7 @@
 use crate::stack::Stack;
 
 /// Uses a Vector of a given capacity (16 by default)
-struct ArrayStack<T: Clone> {
+pub struct ArrayStack<T: Clone> {
     array: Vec<Option<T>>,
     count: usize,
     capacity: usize,
@@ -13,7 +13,7 @@ impl<T: Clone> Stack<T> for ArrayStack<T> {
     /// Amortized complexity: O(1)
     fn push(&mut self, item: T) -> () {
         if self.capacity() == self.count {
-            self.update_capacity(self.capacity() * 2);
+            self.update_capacity(std::cmp::max(1, self.capacity() * 2));
         }
         self.array[self.count] = Some(item);
         self.count += 1;
@@ -52,7 +52,7 @@ impl<T: Clone> Stack<T> for ArrayStack<T> {
 }
 
 impl<T: Clone> ArrayStack<T> {
-    fn new(initial_capacity: usize) -> Self {
+    pub fn new(initial_capacity: usize) -> Self {
         let mut stack = ArrayStack {
             array: Vec::with_capacity(initial_capacity),
             count: 0,
@@ -62,7 +62,7 @@ impl<T: Clone> ArrayStack<T> {
         return stack;
     }
 
-    fn capacity(&self) -> usize {
+    pub fn capacity(&self) -> usize {
         self.capacity
     }
     /// Allocates a new vector with the updated capacity
@@ -112,4 +112,15 @@ mod tests {
         stack.pop();
         assert_eq!(stack.capacity(), 2);
     }
+
+    #[test]
+    fn push_after_emptying() {
+        let mut stack = ArrayStack::new(1);
+        stack.push(0);
+        stack.pop();
+        assert_eq!(stack.capacity(), 0);
+        stack.push(1);
+        assert_eq!(stack.capacity(), 1);
+        assert_eq!(stack.peek(), 1);
+    }
 }
@@ -1,3 +1,5 @@
+use std::cmp::Ordering;
+
 use crate::stack::Stack;
 
 #[derive(Clone)]
@@ -7,7 +9,7 @@ struct Node<T: Clone> {
 }
 
 #[derive(Clone)]
-struct LinkedListStack<T: Clone> {
+pub struct LinkedListStack<T: Clone> {
     head: Option<Box<Node<T>>>,
     size: usize,
 }
@@ -50,18 +52,120 @@ impl<T: Clone> Stack<T> for LinkedListStack<T> {
     }
 }
 
+impl<T: Clone> Default for LinkedListStack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<T: Clone> LinkedListStack<T> {
-    fn new() -> Self {
+    pub fn new() -> Self {
         LinkedListStack::<T> {
             head: None,
             size: 0,
         }
     }
+
+    /// Sorts the items of the stack in ascending order, so that the smallest item ends up on top.
+    /// See `sort_by`.
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.sort_by(T::cmp);
+    }
+
+    /// Sorts the items of the stack according to the order defined by the `compare` function, so that the first item
+    /// in that order ends up on top.
+    /// The sort is a stable merge sort which relinks the existing nodes, without moving their items or allocating.
+    /// Time complexity: O(n log n), and space complexity: O(log n), for the recursion.
+    pub fn sort_by<F>(&mut self, mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        self.head = merge_sort(self.head.take(), self.size, &mut compare);
+    }
+
+    /// Sorts the items of the stack in ascending order of the key extracted by the `key` function, so that the item
+    /// with the smallest key ends up on top. See `sort_by`.
+    pub fn sort_by_key<K, F>(&mut self, mut key: F)
+    where
+        K: Ord,
+        F: FnMut(&T) -> K,
+    {
+        self.sort_by(|a, b| key(a).cmp(&key(b)));
+    }
+}
+
+/// Sorts the first `len` nodes of `list`, which has exactly `len` nodes, splitting it in two halves, sorting each of
+/// them and merging them back.
+fn merge_sort<T, F>(
+    mut list: Option<Box<Node<T>>>,
+    len: usize,
+    compare: &mut F,
+) -> Option<Box<Node<T>>>
+where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    if len < 2 {
+        return list;
+    }
+    let mid = len / 2;
+    let mut cursor = &mut list;
+    for _ in 0..mid {
+        cursor = &mut cursor
+            .as_mut()
+            .expect("the list should have len nodes")
+            .next;
+    }
+    let right = cursor.take();
+    let left = merge_sort(list, mid, compare);
+    let right = merge_sort(right, len - mid, compare);
+    merge(left, right, compare)
+}
+
+/// Merges two sorted lists into a single sorted list, taking the node of `left` first when two nodes are equal.
+fn merge<T, F>(
+    mut left: Option<Box<Node<T>>>,
+    mut right: Option<Box<Node<T>>>,
+    compare: &mut F,
+) -> Option<Box<Node<T>>>
+where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let mut merged = None;
+    let mut tail = &mut merged;
+    while let (Some(l), Some(r)) = (&left, &right) {
+        let source = if compare(&r.item, &l.item) == Ordering::Less {
+            &mut right
+        } else {
+            &mut left
+        };
+        let mut node = source.take().expect("the source list should not be empty");
+        *source = node.next.take();
+        tail = &mut tail.insert(node).next;
+    }
+    *tail = left.or(right);
+    merged
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::stack::{linked_list_stack::LinkedListStack, Stack};
+    use crate::{
+        sorting::tests::pseudo_random_list,
+        stack::{linked_list_stack::LinkedListStack, Stack},
+    };
+
+    /// Pops all the items of the stack, from the top down.
+    fn drain<T: Clone>(stack: &mut LinkedListStack<T>) -> Vec<T> {
+        let mut items = vec![];
+        while stack.count() > 0 {
+            items.push(stack.pop());
+        }
+        items
+    }
 
     #[test]
     fn stack_push_pop() {
@@ -73,4 +177,48 @@ mod tests {
         assert_eq!(item, 42);
         assert_eq!(stack.count(), 0);
     }
+
+    #[test]
+    fn sort() {
+        for len in [0, 1, 2, 3, 10, 100, 1000] {
+            for modulo in [2, 10, u64::MAX] {
+                let items = pseudo_random_list(len, len as u64 ^ modulo, modulo);
+                let mut stack = LinkedListStack::new();
+                for &item in &items {
+                    stack.push(item);
+                }
+                stack.sort();
+                assert_eq!(stack.count(), len);
+                let mut expected = items.clone();
+                expected.sort();
+                assert_eq!(drain(&mut stack), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn sort_by_is_stable() {
+        let items = pseudo_random_list(500, 42, 10);
+        let mut stack = LinkedListStack::new();
+        for (index, &key) in items.iter().enumerate().rev() {
+            stack.push((key, index));
+        }
+        stack.sort_by(|a, b| b.0.cmp(&a.0));
+        let sorted = drain(&mut stack);
+        assert!(sorted
+            .windows(2)
+            .all(|w| w[0].0 > w[1].0 || (w[0].0 == w[1].0 && w[0].1 < w[1].1)));
+    }
+
+    #[test]
+    fn sort_by_key() {
+        let mut stack = LinkedListStack::new();
+        for word in ["ccc", "a", "dddd", "bb"] {
+            stack.push(word);
+        }
+        stack.sort_by_key(|word| word.len());
+        assert_eq!(stack.peek(), "a");
+        stack.push("z");
+        assert_eq!(drain(&mut stack), vec!["z", "a", "bb", "ccc", "dddd"]);
+    }
 }
@@ -1,5 +1,12 @@
-mod array_stack;
-mod linked_list_stack;
+// These modules predate the clippy checks, and keep their original style.
+#![allow(
+    clippy::explicit_auto_deref,
+    clippy::needless_return,
+    clippy::unused_unit
+)]
+
+pub mod array_stack;
+pub mod linked_list_stack;
 
 /// Defines the interface common to all *Stack* implementations.
 pub trait Stack<T> {
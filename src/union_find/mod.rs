@@ -1,7 +1,15 @@
-mod quick_find;
-mod quick_union;
-mod weighted_quick_union;
-mod weighted_quick_union_with_path_compression;
+// These modules predate the clippy checks, and keep their original style.
+#![allow(
+    clippy::bool_assert_comparison,
+    clippy::needless_return,
+    clippy::redundant_field_names,
+    clippy::unused_unit
+)]
+
+pub mod quick_find;
+pub mod quick_union;
+pub mod weighted_quick_union;
+pub mod weighted_quick_union_with_path_compression;
 
 /// A data structure modelling a collection of sets of non-negative consecutive integer values 0..k-1, where set can be
 /// easily merged together and values can be easily checked for membership to the same set.
@@ -2,7 +2,7 @@ use core::panic;
 
 use crate::union_find::UnionFind;
 
-struct QuickFind {
+pub struct QuickFind {
     array: Vec<u32>,
 }
 
@@ -49,7 +49,7 @@ impl UnionFind for QuickFind {
 }
 
 impl QuickFind {
-    fn new(n: u32) -> QuickFind {
+    pub fn new(n: u32) -> QuickFind {
         let mut i = 0;
         let mut array = vec![];
         while i < n {
@@ -1,7 +1,7 @@
 use crate::union_find::UnionFind;
 
 /// Implements union in `O(n)` and find in `O(n)`
-struct QuickUnion {
+pub struct QuickUnion {
     array: Vec<u32>,
 }
 
@@ -38,7 +38,7 @@ impl UnionFind for QuickUnion {
 }
 
 impl QuickUnion {
-    fn new(n: u32) -> QuickUnion {
+    pub fn new(n: u32) -> QuickUnion {
         let mut i = 0;
         let mut array = vec![];
         while i < n {
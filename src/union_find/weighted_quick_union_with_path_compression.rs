@@ -1,6 +1,6 @@
 use crate::{union_find::weighted_quick_union::WeightedQuickUnion, union_find::UnionFind};
 
-struct WeightedQuickUnionWithPathCompression<WQU> {
+pub struct WeightedQuickUnionWithPathCompression<WQU> {
     wqu: WQU,
 }
 
@@ -33,7 +33,7 @@ impl UnionFind for WeightedQuickUnionWithPathCompression<WeightedQuickUnion> {
 }
 
 impl WeightedQuickUnionWithPathCompression<WeightedQuickUnion> {
-    fn new(n: u32) -> WeightedQuickUnionWithPathCompression<WeightedQuickUnion> {
+    pub fn new(n: u32) -> WeightedQuickUnionWithPathCompression<WeightedQuickUnion> {
         let wqu = WeightedQuickUnion::new(n);
         return WeightedQuickUnionWithPathCompression { wqu };
     }